@@ -0,0 +1,18 @@
+#!/bin/sh
+# Builds sales_generated without its default std feature.
+#
+# The host build checks that nothing in the crate names std or alloc. Building for a bare metal
+# triple additionally proves it links against core only, that target ships no std at all.
+# Override the triple with NO_STD_TARGET. `rustup target add` needs network once, after that the
+# target is cached with the toolchain and the check runs offline.
+set -eu
+
+cd "$(dirname "$0")/.."
+
+cargo build -p sales_generated --no-default-features
+
+TARGET="${NO_STD_TARGET:-thumbv7em-none-eabihf}"
+if ! rustup target list --installed | grep -qx "$TARGET"; then
+    rustup target add "$TARGET"
+fi
+cargo build -p sales_generated --no-default-features --target "$TARGET"
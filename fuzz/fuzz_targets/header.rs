@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use my_sales_app::dispatch;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(header) = dispatch::header(data) {
+        let _ = (
+            header.block_length(),
+            header.template_id(),
+            header.version(),
+        );
+        sales_fuzz::round_trip(data);
+    }
+});
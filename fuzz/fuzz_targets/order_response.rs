@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sales_generated::order_response_codec::SBE_TEMPLATE_ID;
+
+fuzz_target!(|data: &[u8]| {
+    sales_fuzz::round_trip(&sales_fuzz::with_template(data, SBE_TEMPLATE_ID));
+});
@@ -0,0 +1,29 @@
+//! Shared checks of the fuzz targets.
+
+use my_sales_app::model::Message;
+use sales_generated::{SBE_SCHEMA_ID, ir, message_header_codec};
+
+/// Copy of `data` with template and schema id overwritten, so the fuzzer spends its time on
+/// the body of `template_id` instead of finding a valid header.
+pub fn with_template(data: &[u8], template_id: u16) -> Vec<u8> {
+    let mut frame = data.to_vec();
+    if frame.len() >= message_header_codec::ENCODED_LENGTH {
+        frame[2..4].copy_from_slice(&template_id.to_le_bytes());
+        frame[4..6].copy_from_slice(&SBE_SCHEMA_ID.to_le_bytes());
+    }
+    frame
+}
+
+/// Checked decode and the generic `ir::walk` must not panic, the walk accepts whatever checked
+/// decode accepts, and that must survive encode and decode.
+pub fn round_trip(frame: &[u8]) {
+    let walked = ir::walk(frame);
+    let Ok(message) = Message::decode_checked(frame) else {
+        return;
+    };
+    assert!(walked.is_ok(), "walk rejects an accepted frame: {walked:?}");
+    let mut buffer = vec![0u8; message.compute_length()];
+    let len = message.encode(&mut buffer);
+    let decoded = Message::decode_checked(&buffer[..len]).expect("re-encoded frame must decode");
+    assert_eq!(message, decoded);
+}
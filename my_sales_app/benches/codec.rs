@@ -0,0 +1,340 @@
+//! Encode and decode cost of the generated codecs, run with `cargo bench -p my_sales_app`.
+//!
+//! Every benchmark handles one message per iteration, so criterion's time is ns/message, and
+//! reports throughput in wire bytes of the SBE frame. The bincode numbers encode the same data
+//! through serde as a baseline.
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use my_sales_app::{
+    dispatch,
+    model::{Item, Message, OrderMessage, OrderResponse},
+};
+use sales_generated::{
+    ReadBuf, Reader, SbeResult,
+    message_header_codec::MessageHeaderDecoder,
+    order_flags::OrderFlags,
+    order_message_codec::{self, OrderMessageBlockDecoder, OrderMessageDecoder},
+    order_response_codec::OrderResponseDecoder,
+    order_status::OrderStatus,
+    order_type::OrderType,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct SerdeItem {
+    product_id: u32,
+    quantity: u16,
+    unit_price: i64,
+    currency: String,
+    sku: String,
+    discounts: Vec<(u16, i64)>,
+    item_note: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerdeOrder {
+    order_id: u64,
+    client_id: u64,
+    timestamp: u64,
+    order_type: u8,
+    order_flags: u8,
+    items: Vec<SerdeItem>,
+    customer_note: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerdeResponse {
+    order_id: u64,
+    timestamp: u64,
+    status: u8,
+    filled_qty: u32,
+    fill_price: i64,
+    server_note: String,
+}
+
+#[derive(Serialize, Deserialize)]
+enum SerdeMessage {
+    Order(SerdeOrder),
+    Response(SerdeResponse),
+}
+
+impl From<&Message> for SerdeMessage {
+    fn from(message: &Message) -> Self {
+        match message {
+            Message::Order(order) => Self::Order(SerdeOrder {
+                order_id: order.order_id,
+                client_id: order.client_id,
+                timestamp: order.timestamp,
+                order_type: order.order_type.into(),
+                order_flags: order.order_flags.0,
+                items: order
+                    .items
+                    .iter()
+                    .map(|item| SerdeItem {
+                        product_id: item.product_id,
+                        quantity: item.quantity,
+                        unit_price: item.unit_price,
+                        currency: item.currency.clone(),
+                        sku: item.sku.clone(),
+                        discounts: item
+                            .discounts
+                            .iter()
+                            .map(|discount| (discount.code, discount.amount))
+                            .collect(),
+                        item_note: item.item_note.clone(),
+                    })
+                    .collect(),
+                customer_note: order.customer_note.clone(),
+            }),
+            Message::Response(response) => Self::Response(SerdeResponse {
+                order_id: response.order_id,
+                timestamp: response.timestamp,
+                status: response.status.into(),
+                filled_qty: response.filled_qty,
+                fill_price: response.fill_price,
+                server_note: response.server_note.clone(),
+            }),
+        }
+    }
+}
+
+fn order(items: usize) -> Message {
+    Message::Order(OrderMessage {
+        order_id: 234,
+        client_id: 135,
+        timestamp: 1_700_000_000_000_000_000,
+        order_type: OrderType::New,
+        order_flags: *OrderFlags::default().set_post_only(true),
+        items: (0..items)
+            .map(|i| Item {
+                product_id: 1_000 + i as u32,
+                quantity: 1 + i as u16,
+                unit_price: 12_345,
+                ..Item::default()
+            })
+            .collect(),
+        customer_note: "leave at the door".to_string(),
+    })
+}
+
+fn response() -> Message {
+    Message::Response(OrderResponse {
+        order_id: 234,
+        timestamp: 1_700_000_000_000_000_000,
+        status: OrderStatus::PartiallyFilled,
+        filled_qty: 7,
+        fill_price: 12_345,
+        server_note: "partial".to_string(),
+    })
+}
+
+fn cases() -> Vec<(&'static str, Message)> {
+    vec![
+        ("order_0_items", order(0)),
+        ("order_2_items", order(2)),
+        ("order_100_items", order(100)),
+        ("response", response()),
+    ]
+}
+
+fn frame(message: &Message) -> Vec<u8> {
+    let mut buffer = vec![0u8; 4096];
+    let len = message.encode(&mut buffer);
+    buffer.truncate(len);
+    buffer
+}
+
+/// Reads every field through the flyweights without copying, returns a checksum so the reads
+/// are not optimised away.
+fn read_flyweight(frame: &[u8]) -> u64 {
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(frame), 0);
+    if header.template_id() == order_message_codec::SBE_TEMPLATE_ID {
+        let order = OrderMessageDecoder::default().header(header, 0);
+        let mut sum = order.order_id() ^ order.client_id() ^ order.timestamp();
+        sum ^= u8::from(order.order_type()) as u64 ^ order.order_flags().0 as u64;
+        let mut items = order.items_decoder();
+        while let Ok(Some(_)) = items.advance() {
+            sum ^= items.product_id() as u64 ^ items.quantity() as u64;
+            let mut price = items.unit_price_decoder();
+            sum ^= price.mantissa() as u64;
+            items = price.parent().unwrap();
+        }
+        let mut order = items.parent().unwrap();
+        let (offset, len) = order.customer_note_decoder();
+        sum ^ order.get_buf().get_slice_at(offset, len).len() as u64
+    } else {
+        let response = OrderResponseDecoder::default().header(header, 0);
+        let mut sum = response.order_id() ^ response.timestamp() ^ response.filled_qty() as u64;
+        sum ^= u8::from(response.status()) as u64;
+        let mut price = response.fill_price_decoder();
+        sum ^= price.mantissa() as u64;
+        let mut response = price.parent().unwrap();
+        let (offset, len) = response.server_note_decoder();
+        sum ^ response.get_buf().get_slice_at(offset, len).len() as u64
+    }
+}
+
+/// Same as `read_flyweight` through the bounds checked wrappers.
+fn read_flyweight_checked(frame: &[u8]) -> SbeResult<u64> {
+    let header = dispatch::header(frame).unwrap();
+    if header.template_id() == order_message_codec::SBE_TEMPLATE_ID {
+        let order = OrderMessageDecoder::default().header_checked(header, 0)?;
+        let mut sum = order.order_id() ^ order.client_id() ^ order.timestamp();
+        sum ^= u8::from(order.order_type()) as u64 ^ order.order_flags().0 as u64;
+        let mut items = order.items_decoder_checked()?;
+        while items.advance()?.is_some() {
+            sum ^= items.product_id() as u64 ^ items.quantity() as u64;
+            let mut price = items.unit_price_decoder();
+            sum ^= price.mantissa() as u64;
+            items = price.parent()?;
+        }
+        let mut order = items.parent()?;
+        let (offset, len) = order.customer_note_decoder_checked()?;
+        Ok(sum ^ order.get_buf().get_slice_at(offset, len).len() as u64)
+    } else {
+        let response = OrderResponseDecoder::default().header_checked(header, 0)?;
+        let mut sum = response.order_id() ^ response.timestamp() ^ response.filled_qty() as u64;
+        sum ^= u8::from(response.status()) as u64;
+        let mut price = response.fill_price_decoder();
+        sum ^= price.mantissa() as u64;
+        let mut response = price.parent()?;
+        let (offset, len) = response.server_note_decoder_checked()?;
+        Ok(sum ^ response.get_buf().get_slice_at(offset, len).len() as u64)
+    }
+}
+
+fn encode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encode");
+    let mut buffer = vec![0u8; 4096];
+    for (name, message) in cases() {
+        group.throughput(Throughput::Bytes(frame(&message).len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("sbe", name), &message, |b, message| {
+            b.iter(|| message.encode(black_box(&mut buffer)))
+        });
+
+        let mut growable = Vec::with_capacity(4096);
+        group.bench_with_input(BenchmarkId::new("sbe_vec", name), &message, |b, message| {
+            b.iter(|| message.encode_to(black_box(&mut growable)))
+        });
+
+        let serde = SerdeMessage::from(&message);
+        let mut out = Vec::with_capacity(4096);
+        group.bench_with_input(BenchmarkId::new("bincode", name), &serde, |b, serde| {
+            b.iter(|| {
+                out.clear();
+                bincode::serialize_into(black_box(&mut out), serde).unwrap();
+            })
+        });
+    }
+    group.finish();
+}
+
+fn decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode");
+    for (name, message) in cases() {
+        let frame = frame(&message);
+        group.throughput(Throughput::Bytes(frame.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("flyweight", name), &frame, |b, frame| {
+            b.iter(|| read_flyweight(black_box(frame)))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("flyweight_checked", name),
+            &frame,
+            |b, frame| b.iter(|| read_flyweight_checked(black_box(frame)).unwrap()),
+        );
+        group.bench_with_input(BenchmarkId::new("owned", name), &frame, |b, frame| {
+            b.iter(|| Message::decode(black_box(frame)).unwrap())
+        });
+        group.bench_with_input(
+            BenchmarkId::new("owned_checked", name),
+            &frame,
+            |b, frame| b.iter(|| Message::decode_checked(black_box(frame)).unwrap()),
+        );
+
+        let serialized = bincode::serialize(&SerdeMessage::from(&message)).unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("bincode", name),
+            &serialized,
+            |b, bytes| b.iter(|| bincode::deserialize::<SerdeMessage>(black_box(bytes)).unwrap()),
+        );
+    }
+    group.finish();
+}
+
+/// Fixed block of an order through the flyweight, which checks bounds on every field, against
+/// the block decoder, which checks once at wrap.
+fn fixed_block(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fixed_block");
+    let frame = frame(&order(0));
+    group.throughput(Throughput::Bytes(
+        order_message_codec::SBE_BLOCK_LENGTH as u64,
+    ));
+
+    group.bench_function("flyweight", |b| {
+        b.iter(|| {
+            let frame = black_box(frame.as_slice());
+            let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(frame), 0);
+            let order = OrderMessageDecoder::default().header(header, 0);
+            order.order_id()
+                ^ order.client_id()
+                ^ order.timestamp()
+                ^ u8::from(order.order_type()) as u64
+                ^ order.order_flags().0 as u64
+        })
+    });
+    group.bench_function("block", |b| {
+        b.iter(|| {
+            let frame = black_box(frame.as_slice());
+            let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(frame), 0);
+            let order = OrderMessageBlockDecoder::header(header, 0).unwrap();
+            order.order_id()
+                ^ order.client_id()
+                ^ order.timestamp()
+                ^ u8::from(order.order_type()) as u64
+                ^ order.order_flags().0 as u64
+        })
+    });
+    group.finish();
+}
+
+/// Order id and customer note of an order with 100 items, walking the items entry by entry
+/// against skipping them.
+fn route(c: &mut Criterion) {
+    let mut group = c.benchmark_group("route");
+    let frame = frame(&order(100));
+
+    let note_len = |mut order: OrderMessageDecoder<'_>| {
+        let (_, len) = order.customer_note_decoder_checked().unwrap();
+        order.order_id() ^ len as u64
+    };
+    group.bench_function("advance", |b| {
+        b.iter(|| {
+            let header = dispatch::header(black_box(&frame)).unwrap();
+            let order = OrderMessageDecoder::default()
+                .header_checked(header, 0)
+                .unwrap();
+            let mut items = order.items_decoder_checked().unwrap();
+            while items.advance().unwrap().is_some() {}
+            note_len(items.parent().unwrap())
+        })
+    });
+    group.bench_function("skip_all", |b| {
+        b.iter(|| {
+            let header = dispatch::header(black_box(&frame)).unwrap();
+            let order = OrderMessageDecoder::default()
+                .header_checked(header, 0)
+                .unwrap();
+            let mut items = order.items_decoder_checked().unwrap();
+            items.skip_all().unwrap();
+            note_len(items.parent().unwrap())
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, encode, decode, fixed_block, route);
+criterion_main!(benches);
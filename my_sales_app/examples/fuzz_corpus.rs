@@ -0,0 +1,84 @@
+//! Writes valid encodings of both messages as the seed corpus of the fuzz targets.
+//!
+//! > cargo run --example fuzz_corpus -- fuzz/corpus
+
+use std::{fs, path::Path};
+
+use my_sales_app::model::{Discount, Item, Message, OrderMessage, OrderResponse};
+use sales_generated::{order_flags::OrderFlags, order_status::OrderStatus, order_type::OrderType};
+
+fn orders() -> Vec<OrderMessage> {
+    let item = |i: u32| Item {
+        product_id: 222 + i,
+        quantity: 2 + i as u16,
+        unit_price: 234 * i as i64 - 1,
+        currency: ["EUR", "USD", ""][i as usize % 3].to_string(),
+        sku: format!("SKU-{i:012}"),
+        discounts: (0..i as u16 % 3)
+            .map(|code| Discount {
+                code,
+                amount: -(code as i64),
+            })
+            .collect(),
+        item_note: "ß".repeat(i as usize % 4),
+    };
+    vec![
+        OrderMessage::default(),
+        OrderMessage {
+            order_id: 234,
+            client_id: 135,
+            timestamp: 246,
+            order_type: OrderType::New,
+            order_flags: OrderFlags::default(),
+            items: (0..2).map(item).collect(),
+            customer_note: "duck is angry".to_string(),
+        },
+        OrderMessage {
+            order_id: u64::MAX,
+            client_id: 1,
+            timestamp: 2,
+            order_type: OrderType::Cancel,
+            order_flags: *OrderFlags::default().set_ioc(true).set_gift_wrap(true),
+            items: (0..40).map(item).collect(),
+            customer_note: "ünïcødé ✓".to_string(),
+        },
+    ]
+}
+
+fn responses() -> Vec<OrderResponse> {
+    vec![
+        OrderResponse::default(),
+        OrderResponse {
+            order_id: 234,
+            timestamp: 247,
+            status: OrderStatus::PartiallyFilled,
+            filled_qty: 3,
+            fill_price: i64::MIN + 1,
+            server_note: "partial".to_string(),
+        },
+    ]
+}
+
+fn write(dir: &Path, messages: &[Message]) {
+    fs::create_dir_all(dir).unwrap();
+    let mut buffer = Vec::new();
+    for (i, message) in messages.iter().enumerate() {
+        let len = message.encode_to(&mut buffer);
+        fs::write(dir.join(format!("seed-{i}")), &buffer[..len]).unwrap();
+    }
+}
+
+fn main() {
+    let corpus = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "fuzz/corpus".to_string());
+    let corpus = Path::new(&corpus);
+
+    let orders: Vec<Message> = orders().into_iter().map(Message::Order).collect();
+    let responses: Vec<Message> = responses().into_iter().map(Message::Response).collect();
+    let all: Vec<Message> = orders.iter().chain(&responses).cloned().collect();
+
+    write(&corpus.join("header"), &all);
+    write(&corpus.join("order_message"), &orders);
+    write(&corpus.join("order_response"), &responses);
+}
@@ -0,0 +1,178 @@
+//! Many frames back to back in one buffer, so a burst goes out with a single write.
+//!
+//! There is no extra framing: every frame is a complete message, header included, starting
+//! where the previous one ends. `BatchDecoder` finds the boundaries by walking each message.
+
+use sales_generated::{
+    Encoder, WriteBuf, message_header_codec, order_message_codec::OrderMessageEncoder,
+    order_response_codec::OrderResponseEncoder,
+};
+
+use crate::{
+    dispatch::{self, DispatchError},
+    model::Message,
+};
+
+/// Where a frame sits in the batch buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FramePosition {
+    pub offset: usize,
+    pub length: usize,
+}
+
+impl FramePosition {
+    pub fn end(&self) -> usize {
+        self.offset + self.length
+    }
+}
+
+/// Encodes frames one after another into a buffer that grows as needed and keeps the position of
+/// every frame. `clear` keeps the allocation for the next batch.
+#[derive(Clone, Debug, Default)]
+pub struct BatchEncoder {
+    buffer: Vec<u8>,
+    positions: Vec<FramePosition>,
+}
+
+impl BatchEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(bytes: usize) -> Self {
+        Self {
+            buffer: Vec::with_capacity(bytes),
+            positions: Vec::new(),
+        }
+    }
+
+    /// Wraps an `OrderMessageEncoder` at the end of the batch with its header written. `encode`
+    /// sets the fields and returns the encoder, its limit is the end of the frame.
+    pub fn order_message(
+        &mut self,
+        encode: impl FnOnce(OrderMessageEncoder<'_>) -> OrderMessageEncoder<'_>,
+    ) -> FramePosition {
+        let offset = self.buffer.len();
+        let end = {
+            let order = OrderMessageEncoder::default().wrap(
+                WriteBuf::growable(&mut self.buffer),
+                offset + message_header_codec::ENCODED_LENGTH,
+            );
+            encode(order.header(offset).parent().unwrap()).get_limit()
+        };
+        self.push(offset, end)
+    }
+
+    /// Same as `order_message` for an `OrderResponseEncoder`.
+    pub fn order_response(
+        &mut self,
+        encode: impl FnOnce(OrderResponseEncoder<'_>) -> OrderResponseEncoder<'_>,
+    ) -> FramePosition {
+        let offset = self.buffer.len();
+        let end = {
+            let response = OrderResponseEncoder::default().wrap(
+                WriteBuf::growable(&mut self.buffer),
+                offset + message_header_codec::ENCODED_LENGTH,
+            );
+            encode(response.header(offset).parent().unwrap()).get_limit()
+        };
+        self.push(offset, end)
+    }
+
+    /// Appends an owned message.
+    pub fn message(&mut self, message: &Message) -> FramePosition {
+        let offset = self.buffer.len();
+        self.buffer.resize(offset + message.compute_length(), 0);
+        let length = message.encode(&mut self.buffer[offset..]);
+        self.push(offset, offset + length)
+    }
+
+    fn push(&mut self, offset: usize, end: usize) -> FramePosition {
+        // fields the caller left unset were never written
+        self.buffer.resize(end, 0);
+        let position = FramePosition {
+            offset,
+            length: end - offset,
+        };
+        self.positions.push(position);
+        position
+    }
+
+    /// All frames, back to back.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    pub fn positions(&self) -> &[FramePosition] {
+        &self.positions
+    }
+
+    pub fn frame(&self, index: usize) -> Option<&[u8]> {
+        let position = self.positions.get(index)?;
+        Some(&self.buffer[position.offset..position.end()])
+    }
+
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.positions.clear();
+    }
+}
+
+/// Iterates the frames of a batch. Every length is checked, a malformed frame ends the
+/// iteration with its error since the frames after it cannot be found.
+#[derive(Clone, Debug)]
+pub struct BatchDecoder<'a> {
+    data: &'a [u8],
+    offset: usize,
+    failed: bool,
+}
+
+impl<'a> BatchDecoder<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            offset: 0,
+            failed: false,
+        }
+    }
+
+    /// Bytes consumed by the frames returned so far.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a> Iterator for BatchDecoder<'a> {
+    type Item = Result<(FramePosition, &'a [u8]), DispatchError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.offset == self.data.len() {
+            return None;
+        }
+        let rest = &self.data[self.offset..];
+        match dispatch::frame_length(rest) {
+            Ok(length) => {
+                let position = FramePosition {
+                    offset: self.offset,
+                    length,
+                };
+                self.offset += length;
+                Some(Ok((position, &rest[..length])))
+            }
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl std::iter::FusedIterator for BatchDecoder<'_> {}
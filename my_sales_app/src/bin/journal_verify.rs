@@ -0,0 +1,41 @@
+//! Checks the integrity of a journal directory, e.g. after a crash.
+//!
+//! > cargo run --bin journal_verify -- <journal dir>
+
+use std::process::ExitCode;
+
+use my_sales_app::journal;
+
+fn main() -> ExitCode {
+    let Some(dir) = std::env::args().nth(1) else {
+        eprintln!("usage: journal_verify <journal dir>");
+        return ExitCode::from(2);
+    };
+
+    let report = match journal::verify(&dir) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    println!(
+        "segments = {}, records = {}, first_sequence = {:?}, last_sequence = {:?}",
+        report.segments, report.records, report.first_sequence, report.last_sequence
+    );
+    for problem in &report.problems {
+        println!(
+            "{}: offset {}: {:?}",
+            problem.segment.display(),
+            problem.offset,
+            problem.kind
+        );
+    }
+
+    if report.is_clean() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
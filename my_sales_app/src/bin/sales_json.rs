@@ -0,0 +1,125 @@
+//! Turns JSON orders into frames and frames back into JSON, see `my_sales_app::json`.
+//!
+//! Frames are hex, one per line, like the golden files under tests/golden.
+//!
+//! > cargo run --bin sales_json -- encode orders.json > orders.hex
+//! > cargo run --bin sales_json -- decode orders.hex
+//!
+//! Without a file the input is read from stdin.
+
+use std::{
+    fmt::Write as _,
+    fs,
+    io::{self, Read},
+    process::ExitCode,
+};
+
+use my_sales_app::json;
+use serde_json::{Deserializer, Value};
+
+const USAGE: &str = "usage: sales_json encode|decode [file]
+  encode  JSON objects, one after the other or in arrays, to hex frames, one per line
+  decode  hex frames, one per line, to JSON objects, one per line";
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let (Some(command), path, None) = (args.next(), args.next(), args.next()) else {
+        eprintln!("{USAGE}");
+        return ExitCode::from(2);
+    };
+    let input = match path.as_deref() {
+        None | Some("-") => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input).map(|_| input)
+        }
+        Some(path) => fs::read_to_string(path),
+    };
+    let input = match input {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let failures = match command.as_str() {
+        "encode" => encode(&input),
+        "decode" => decode(&input),
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+    if failures == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Prints a frame per object, returns the number of objects that failed.
+fn encode(input: &str) -> usize {
+    let mut failures = 0;
+    let mut index = 0;
+    for value in Deserializer::from_str(input).into_iter::<Value>() {
+        let values = match value {
+            Ok(Value::Array(values)) => values,
+            Ok(value) => vec![value],
+            Err(e) => {
+                eprintln!("object {index}: {}", json::JsonError::from(e));
+                // the rest of the input cannot be split into objects
+                return failures + 1;
+            }
+        };
+        for value in values {
+            match json::to_frame(&value) {
+                Ok(frame) => println!("{}", to_hex(&frame)),
+                Err(e) => {
+                    eprintln!("object {index}: {e}");
+                    failures += 1;
+                }
+            }
+            index += 1;
+        }
+    }
+    failures
+}
+
+/// Prints an object per frame, returns the number of lines that failed.
+fn decode(input: &str) -> usize {
+    let mut failures = 0;
+    for (number, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let decoded = from_hex(line)
+            .ok_or("not hex".to_string())
+            .and_then(|frame| json::from_frame(&frame).map_err(|e| e.to_string()));
+        match decoded {
+            Ok(value) => println!("{value}"),
+            Err(e) => {
+                eprintln!("line {}: {e}", number + 1);
+                failures += 1;
+            }
+        }
+    }
+    failures
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, b| {
+        let _ = write!(hex, "{b:02x}");
+        hex
+    })
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
@@ -0,0 +1,107 @@
+//! Routes an encoded frame to the right decoder by looking at its message header.
+//!
+//! A frame is one SBE message: the 8 byte message header followed by the message body.
+
+use sales_generated::{
+    Decoder, ReadBuf, SBE_SCHEMA_ID, SbeErr,
+    message_header_codec::{self, MessageHeaderDecoder},
+    order_message_codec::{self, OrderMessageDecoder},
+    order_response_codec::{self, OrderResponseDecoder},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DispatchError {
+    /// frame is shorter than the message header
+    Truncated,
+    /// frame was encoded with a different schema
+    WrongSchema(u16),
+    /// template id is not part of the schema
+    UnknownTemplate(u16),
+    /// checked decoding found a block, group or var data that does not fit the frame
+    Malformed(SbeErr),
+    /// var data declared as UTF-8 is not
+    InvalidUtf8,
+}
+
+impl std::fmt::Display for DispatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "frame shorter than message header"),
+            Self::WrongSchema(id) => write!(f, "unexpected schema id {id}"),
+            Self::UnknownTemplate(id) => write!(f, "unknown template id {id}"),
+            Self::Malformed(e) => write!(f, "malformed frame: {e}"),
+            Self::InvalidUtf8 => write!(f, "var data is not valid UTF-8"),
+        }
+    }
+}
+
+impl std::error::Error for DispatchError {}
+
+impl From<SbeErr> for DispatchError {
+    fn from(e: SbeErr) -> Self {
+        Self::Malformed(e)
+    }
+}
+
+/// Callbacks for each template of the sales schema.
+pub trait MessageHandler {
+    fn on_order_message(&mut self, order: OrderMessageDecoder<'_>);
+
+    fn on_order_response(&mut self, response: OrderResponseDecoder<'_>);
+
+    /// called for templates this schema does not know, frame includes the header
+    fn on_unknown(&mut self, _template_id: u16, _frame: &[u8]) {}
+}
+
+/// Wraps the message header of `frame` after checking length and schema id.
+pub fn header(frame: &[u8]) -> Result<MessageHeaderDecoder<ReadBuf<'_>>, DispatchError> {
+    if frame.len() < message_header_codec::ENCODED_LENGTH {
+        return Err(DispatchError::Truncated);
+    }
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(frame), 0);
+    if header.schema_id() != SBE_SCHEMA_ID {
+        return Err(DispatchError::WrongSchema(header.schema_id()));
+    }
+    Ok(header)
+}
+
+/// Decodes the header of `frame` and hands the wrapped decoder to `handler`.
+///
+/// Only the header is validated, the frame must come from a trusted source. Use
+/// `Message::decode_checked` for frames that may be malformed.
+pub fn dispatch<H: MessageHandler>(frame: &[u8], handler: &mut H) -> Result<(), DispatchError> {
+    let header = header(frame)?;
+
+    match header.template_id() {
+        order_message_codec::SBE_TEMPLATE_ID => {
+            handler.on_order_message(OrderMessageDecoder::default().header(header, 0))
+        }
+        order_response_codec::SBE_TEMPLATE_ID => {
+            handler.on_order_response(OrderResponseDecoder::default().header(header, 0))
+        }
+        template_id => handler.on_unknown(template_id, frame),
+    }
+    Ok(())
+}
+
+/// Length of the message at the start of `data`, found by walking its block, groups and var
+/// data, every length is checked against `data`. Bytes after the message are ignored.
+pub fn frame_length(data: &[u8]) -> Result<usize, DispatchError> {
+    let header = header(data)?;
+    match header.template_id() {
+        order_message_codec::SBE_TEMPLATE_ID => {
+            let order = OrderMessageDecoder::default().header_checked(header, 0)?;
+            let mut items = order.items_decoder_checked()?;
+            items.skip_all()?;
+            let mut order = items.parent()?;
+            order.customer_note_skip_checked()?;
+            Ok(order.get_limit())
+        }
+        order_response_codec::SBE_TEMPLATE_ID => {
+            let mut response = OrderResponseDecoder::default().header_checked(header, 0)?;
+            response.server_note_skip_checked()?;
+            Ok(response.get_limit())
+        }
+        template_id => Err(DispatchError::UnknownTemplate(template_id)),
+    }
+}
@@ -0,0 +1,184 @@
+//! Deterministic generator of execution reports for accepted orders, for replaying scenarios in
+//! tests of downstream consumers.
+//!
+//! Every order is filled completely: zero or more `PartiallyFilled` responses followed by one
+//! `Filled`. As with the `OrderManager`, `filled_qty` is the cumulative filled quantity and
+//! `fill_price` the price of that single execution.
+
+use sales_generated::order_status::OrderStatus;
+
+use crate::model::{OrderMessage, OrderResponse};
+
+/// Shape of the generated fills, the same model and seed always produce the same fills.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FillModel {
+    pub seed: u64,
+    /// an order is split into between `min_fills` and `max_fills` executions, fewer if it has
+    /// less quantity than that
+    pub min_fills: u32,
+    pub max_fills: u32,
+    /// largest deviation of a fill price from the order's average unit price, in price ticks
+    pub max_slippage: i64,
+    /// ns from acceptance to the first fill and between fills
+    pub fill_interval: u64,
+    /// up to this many ns are randomly added to every interval
+    pub fill_interval_jitter: u64,
+}
+
+impl Default for FillModel {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            min_fills: 1,
+            max_fills: 4,
+            max_slippage: 0,
+            fill_interval: 1_000_000,
+            fill_interval_jitter: 0,
+        }
+    }
+}
+
+/// `FillModel` the simulator can not run with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FillModelError {
+    /// `fill_interval + fill_interval_jitter` does not fit in `u64`
+    IntervalOverflow,
+}
+
+impl std::fmt::Display for FillModelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::IntervalOverflow => f.write_str("fill interval plus jitter overflows u64"),
+        }
+    }
+}
+
+impl std::error::Error for FillModelError {}
+
+/// splitmix64, small and stable across releases unlike external rng crates
+#[derive(Clone, Copy, Debug)]
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// uniform in `0..=max`
+    fn up_to(&mut self, max: u64) -> u64 {
+        match max.checked_add(1) {
+            Some(bound) => self.next_u64() % bound,
+            None => self.next_u64(),
+        }
+    }
+}
+
+pub struct FillSimulator {
+    model: FillModel,
+    rng: Rng,
+    buffer: Vec<u8>,
+}
+
+impl FillSimulator {
+    /// Fails with `IntervalOverflow` if the longest interval between fills does not fit in
+    /// `u64`.
+    pub fn new(model: FillModel) -> Result<Self, FillModelError> {
+        if model
+            .fill_interval
+            .checked_add(model.fill_interval_jitter)
+            .is_none()
+        {
+            return Err(FillModelError::IntervalOverflow);
+        }
+        Ok(Self {
+            model,
+            rng: Rng(model.seed),
+            buffer: vec![0u8; 256],
+        })
+    }
+
+    pub fn model(&self) -> &FillModel {
+        &self.model
+    }
+
+    /// Fills for one order accepted at `accepted_at` (epoch ns). Orders without quantity get no
+    /// fills, timestamps stop at `u64::MAX` instead of wrapping.
+    pub fn simulate(&mut self, order: &OrderMessage, accepted_at: u64) -> Vec<OrderResponse> {
+        let total: u64 = order.items.iter().map(|item| item.quantity as u64).sum();
+        if total == 0 {
+            return Vec::new();
+        }
+        let (min_fills, max_fills) = (self.model.min_fills.max(1), self.model.max_fills.max(1));
+        let fills = min_fills as u64 + self.rng.up_to(max_fills.saturating_sub(min_fills) as u64);
+        let fills = fills.min(total);
+
+        let mut responses = Vec::with_capacity(fills as usize);
+        let mut filled = 0;
+        let mut timestamp = accepted_at;
+        for fill in 0..fills {
+            let left = fills - fill;
+            let remaining = total - filled;
+            let quantity = if left == 1 {
+                remaining
+            } else {
+                // leave at least one unit for every later fill, spread roughly evenly
+                let most = (remaining - (left - 1)).min(2 * remaining / left);
+                1 + self.rng.up_to(most - 1)
+            };
+            filled += quantity;
+            // `new` made sure the interval itself can not overflow
+            let interval =
+                self.model.fill_interval + self.rng.up_to(self.model.fill_interval_jitter);
+            timestamp = timestamp.saturating_add(interval);
+
+            responses.push(OrderResponse {
+                order_id: order.order_id,
+                timestamp,
+                status: if left == 1 {
+                    OrderStatus::Filled
+                } else {
+                    OrderStatus::PartiallyFilled
+                },
+                filled_qty: filled as u32,
+                fill_price: self.fill_price(order),
+                server_note: String::new(),
+            });
+        }
+        responses
+    }
+
+    /// Same as `simulate` but hands every response to `emit` encoded as a frame, returns the
+    /// number of frames.
+    pub fn simulate_encoded(
+        &mut self,
+        order: &OrderMessage,
+        accepted_at: u64,
+        mut emit: impl FnMut(&[u8]),
+    ) -> usize {
+        let responses = self.simulate(order, accepted_at);
+        for response in &responses {
+            let len = response.encode(&mut self.buffer);
+            emit(&self.buffer[..len]);
+        }
+        responses.len()
+    }
+
+    /// Quantity weighted average unit price plus random slippage.
+    fn fill_price(&mut self, order: &OrderMessage) -> i64 {
+        let (notional, quantity) = order.items.iter().fold((0i128, 0i128), |(n, q), item| {
+            (
+                n + item.unit_price as i128 * item.quantity as i128,
+                q + item.quantity as i128,
+            )
+        });
+        let average = (notional / quantity) as i64;
+        // i128 as `max_slippage` may be anything up to i64::MAX
+        let slippage = self.model.max_slippage.max(0) as u64;
+        let offset = self.rng.up_to(2 * slippage) as i128 - slippage as i128;
+        (average as i128 + offset).clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+}
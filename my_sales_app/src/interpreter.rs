@@ -0,0 +1,31 @@
+//! Decodes frames of any SBE schema loaded at runtime, for tools that read frames of schemas
+//! they were not compiled against, e.g. another team's schema or an older messages.xml.
+//!
+//! `ir::parse` reads the xml into the IR build.rs generates for messages.xml, allocated in a
+//! `Bump` the caller keeps. `decode` walks a frame with it like `ir::walk` does with
+//! `ir::SCHEMA`: the message header, group dimension and var data layouts are taken from the
+//! schema, both byte orders are supported.
+//!
+//! A frame is read with the layout of the schema it is decoded with. Version 4 of messages.xml
+//! widened `numInGroup`, frames of earlier versions need the messages.xml of their version.
+
+use sales_generated::ir::{self, MessageValue, Schema};
+
+use crate::dispatch::DispatchError;
+
+/// Decodes the frame at the start of `frame`, any message and version of `schema`. Fails like
+/// `dispatch::header` on the header, with `Malformed` for a body that does not fit the frame or
+/// `char` arrays outside printable US-ASCII, never panics.
+pub fn decode<'a>(
+    schema: &'a Schema<'a>,
+    frame: &'a [u8],
+) -> Result<MessageValue<'a>, DispatchError> {
+    let header = ir::header(schema, frame).map_err(|_| DispatchError::Truncated)?;
+    if header.schema_id != schema.id {
+        return Err(DispatchError::WrongSchema(header.schema_id));
+    }
+    if schema.message(header.template_id).is_none() {
+        return Err(DispatchError::UnknownTemplate(header.template_id));
+    }
+    ir::walk_schema(schema, frame).map_err(DispatchError::Malformed)
+}
@@ -0,0 +1,720 @@
+//! Append-only file journal of encoded SBE frames.
+//!
+//! Every frame is stored as a record: a 24 byte record header followed by the frame bytes.
+//! ```text
+//! 0       4       8               16              24
+//! +-------+-------+---------------+---------------+----------------+
+//! | length|  crc  |   sequence    |   timestamp   | frame (length) |
+//! +-------+-------+---------------+---------------+----------------+
+//! ```
+//! All integers are little endian like the SBE frames themselves. The crc32 covers length,
+//! sequence, timestamp and frame. Records are appended to segment files named after the sequence of their
+//! first record, a new segment is started once the current one reaches `segment_size`.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::dispatch::{self, DispatchError, MessageHandler};
+
+pub const RECORD_HEADER_LENGTH: usize = 24;
+pub const SEGMENT_EXTENSION: &str = "journal";
+pub const FIRST_SEQUENCE: u64 = 1;
+
+#[derive(Debug)]
+pub enum JournalError {
+    Io(io::Error),
+    /// record failed crc check or has an impossible length, replay can not continue past it
+    Corrupt {
+        segment: PathBuf,
+        offset: u64,
+    },
+    /// frame does not fit the 32 bit length of a record
+    FrameTooLong(usize),
+    /// record is intact but its frame could not be dispatched
+    Dispatch {
+        sequence: u64,
+        error: DispatchError,
+    },
+    /// order index entry no longer matches the record at its position, the journal lost its
+    /// tail after the record was indexed
+    StaleIndex {
+        sequence: u64,
+    },
+}
+
+impl std::fmt::Display for JournalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "journal io error: {e}"),
+            Self::Corrupt { segment, offset } => {
+                write!(
+                    f,
+                    "corrupt record in {} at offset {offset}",
+                    segment.display()
+                )
+            }
+            Self::FrameTooLong(len) => {
+                write!(f, "frame of {len} bytes is longer than {}", u32::MAX)
+            }
+            Self::Dispatch { sequence, error } => write!(f, "record {sequence}: {error}"),
+            Self::StaleIndex { sequence } => {
+                write!(
+                    f,
+                    "index entry of record {sequence} does not match the journal"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for JournalError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Dispatch { error, .. } => Some(error),
+            Self::Corrupt { .. } | Self::FrameTooLong(_) | Self::StaleIndex { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for JournalError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+pub type JournalResult<T> = Result<T, JournalError>;
+
+#[derive(Clone, Copy, Debug)]
+pub struct JournalConfig {
+    /// a segment is rolled once appending would take it past this many bytes
+    pub segment_size: u64,
+    /// fsync after every append, otherwise only on `sync` and segment roll
+    pub sync_on_append: bool,
+}
+
+impl Default for JournalConfig {
+    fn default() -> Self {
+        Self {
+            segment_size: 64 * 1024 * 1024,
+            sync_on_append: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct RecordHeader {
+    length: u32,
+    crc: u32,
+    sequence: u64,
+    timestamp: u64,
+}
+
+impl RecordHeader {
+    fn new(sequence: u64, timestamp: u64, frame: &[u8]) -> JournalResult<Self> {
+        let length =
+            u32::try_from(frame.len()).map_err(|_| JournalError::FrameTooLong(frame.len()))?;
+        Ok(Self {
+            length,
+            crc: checksum(length, sequence, timestamp, frame),
+            sequence,
+            timestamp,
+        })
+    }
+
+    fn write_to(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.length.to_le_bytes());
+        out.extend_from_slice(&self.crc.to_le_bytes());
+        out.extend_from_slice(&self.sequence.to_le_bytes());
+        out.extend_from_slice(&self.timestamp.to_le_bytes());
+    }
+
+    fn read_from(bytes: &[u8; RECORD_HEADER_LENGTH]) -> Self {
+        let u32_at = |i: usize| u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap());
+        let u64_at = |i: usize| u64::from_le_bytes(bytes[i..i + 8].try_into().unwrap());
+        Self {
+            length: u32_at(0),
+            crc: u32_at(4),
+            sequence: u64_at(8),
+            timestamp: u64_at(16),
+        }
+    }
+
+    fn record_length(&self) -> u64 {
+        RECORD_HEADER_LENGTH as u64 + self.length as u64
+    }
+}
+
+fn checksum(length: u32, sequence: u64, timestamp: u64, frame: &[u8]) -> u32 {
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(&length.to_le_bytes());
+    hasher.update(&sequence.to_le_bytes());
+    hasher.update(&timestamp.to_le_bytes());
+    hasher.update(frame);
+    hasher.finalize()
+}
+
+/// Location of a record, stable for the lifetime of the journal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RecordPosition {
+    /// first sequence of the segment, which is also its file name
+    pub segment: u64,
+    /// byte offset of the record header within the segment
+    pub offset: u64,
+}
+
+/// One record read back from the journal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    pub sequence: u64,
+    pub position: RecordPosition,
+    /// receive timestamp passed to `Journal::append`
+    pub timestamp: u64,
+    pub frame: Vec<u8>,
+}
+
+/// Segment file on disk, identified by the sequence of its first record.
+#[derive(Clone, Debug)]
+struct Segment {
+    first_sequence: u64,
+    path: PathBuf,
+}
+
+fn segment_path(dir: &Path, first_sequence: u64) -> PathBuf {
+    dir.join(format!("{first_sequence:020}.{SEGMENT_EXTENSION}"))
+}
+
+/// Lists the segments of a journal directory ordered by first sequence.
+fn list_segments(dir: &Path) -> io::Result<Vec<Segment>> {
+    let mut segments = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some(SEGMENT_EXTENSION) {
+            continue;
+        }
+        let first_sequence = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .and_then(|s| s.parse().ok());
+        if let Some(first_sequence) = first_sequence {
+            segments.push(Segment {
+                first_sequence,
+                path,
+            });
+        }
+    }
+    segments.sort_by_key(|s| s.first_sequence);
+    Ok(segments)
+}
+
+enum ReadOutcome {
+    Record(RecordHeader),
+    /// clean end of segment
+    End,
+    /// segment ends part way through its last record or in zeros, typical after a crash
+    /// during append
+    Torn,
+    /// header or frame does not match its crc
+    Corrupt,
+}
+
+/// Sequential reader over the records of one segment.
+struct SegmentReader {
+    path: PathBuf,
+    first_sequence: u64,
+    reader: BufReader<File>,
+    position: u64,
+    len: u64,
+    /// sequence the record at `position` should have
+    next_sequence: u64,
+}
+
+impl SegmentReader {
+    fn open(segment: &Segment) -> io::Result<Self> {
+        let file = File::open(&segment.path)?;
+        let len = file.metadata()?.len();
+        Ok(Self {
+            path: segment.path.clone(),
+            first_sequence: segment.first_sequence,
+            reader: BufReader::new(file),
+            position: 0,
+            len,
+            next_sequence: segment.first_sequence,
+        })
+    }
+
+    fn seek(&mut self, offset: u64) -> io::Result<()> {
+        self.reader.seek(SeekFrom::Start(offset))?;
+        self.position = offset.min(self.len);
+        Ok(())
+    }
+
+    /// Reads the next record into `frame`, on anything but `Record` the position is left at
+    /// the start of the offending record.
+    fn next(&mut self, frame: &mut Vec<u8>) -> io::Result<ReadOutcome> {
+        let remaining = self.len - self.position;
+        if remaining == 0 {
+            return Ok(ReadOutcome::End);
+        }
+        if remaining < RECORD_HEADER_LENGTH as u64 {
+            return Ok(ReadOutcome::Torn);
+        }
+        let mut bytes = [0u8; RECORD_HEADER_LENGTH];
+        self.reader.read_exact(&mut bytes)?;
+        if bytes == [0; RECORD_HEADER_LENGTH] {
+            // no record starts with sequence 0, a crash left the tail zero-filled if the file
+            // grew before the data reached the disk
+            let outcome = if self.zero_filled(frame)? {
+                ReadOutcome::Torn
+            } else {
+                ReadOutcome::Corrupt
+            };
+            self.reader.seek(SeekFrom::Start(self.position))?;
+            return Ok(outcome);
+        }
+        let header = RecordHeader::read_from(&bytes);
+        if header.record_length() > remaining {
+            // a bit flip in the length looks the same as a torn record, but then intact
+            // records follow it
+            let outcome = if self.intact_record_follows(frame)? {
+                ReadOutcome::Corrupt
+            } else {
+                ReadOutcome::Torn
+            };
+            self.reader.seek(SeekFrom::Start(self.position))?;
+            return Ok(outcome);
+        }
+        frame.resize(header.length as usize, 0);
+        self.reader.read_exact(frame)?;
+        if checksum(header.length, header.sequence, header.timestamp, frame) != header.crc {
+            self.reader
+                .seek_relative(-(header.record_length() as i64))?;
+            return Ok(ReadOutcome::Corrupt);
+        }
+        self.position += header.record_length();
+        self.next_sequence = header.sequence + 1;
+        Ok(ReadOutcome::Record(header))
+    }
+
+    /// Whether the rest of the segment, after the header just read, holds only zeros.
+    fn zero_filled(&mut self, rest: &mut Vec<u8>) -> io::Result<bool> {
+        rest.clear();
+        let rest_len = self.len - self.position - RECORD_HEADER_LENGTH as u64;
+        self.reader.by_ref().take(rest_len).read_to_end(rest)?;
+        Ok(rest.iter().all(|&byte| byte == 0))
+    }
+
+    /// Searches the rest of the segment, after the header just read, for an intact record
+    /// with the sequence following `next_sequence`.
+    fn intact_record_follows(&mut self, rest: &mut Vec<u8>) -> io::Result<bool> {
+        rest.clear();
+        let rest_len = self.len - self.position - RECORD_HEADER_LENGTH as u64;
+        self.reader.by_ref().take(rest_len).read_to_end(rest)?;
+        let following = (self.next_sequence + 1).to_le_bytes();
+        let found = (0..rest.len().saturating_sub(RECORD_HEADER_LENGTH - 1)).any(|offset| {
+            let candidate = &rest[offset..];
+            if candidate[8..16] != following {
+                return false;
+            }
+            let header =
+                RecordHeader::read_from(candidate[..RECORD_HEADER_LENGTH].try_into().unwrap());
+            let end = header.record_length();
+            end <= candidate.len() as u64
+                && checksum(
+                    header.length,
+                    header.sequence,
+                    header.timestamp,
+                    &candidate[RECORD_HEADER_LENGTH..end as usize],
+                ) == header.crc
+        });
+        Ok(found)
+    }
+}
+
+/// Writer side of the journal, only one should be open per directory.
+pub struct Journal {
+    dir: PathBuf,
+    config: JournalConfig,
+    segment: File,
+    segment_len: u64,
+    next_sequence: u64,
+    scratch: Vec<u8>,
+}
+
+impl Journal {
+    /// Opens or creates the journal in `dir`. A torn record at the end of the last segment is
+    /// truncated away so appends continue after the last intact record, any other damage fails
+    /// with `Corrupt` and leaves the segment as it is.
+    pub fn open(dir: impl AsRef<Path>, config: JournalConfig) -> JournalResult<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+
+        let (segment, segment_len, next_sequence) = match list_segments(&dir)?.pop() {
+            None => {
+                let path = segment_path(&dir, FIRST_SEQUENCE);
+                (create_segment(&path)?, 0, FIRST_SEQUENCE)
+            }
+            Some(last) => {
+                let (reader, next_sequence) = scan_last(&last)?;
+                let file = OpenOptions::new().append(true).open(&last.path)?;
+                if reader.position < reader.len {
+                    file.set_len(reader.position)?;
+                    file.sync_all()?;
+                }
+                (file, reader.position, next_sequence)
+            }
+        };
+
+        Ok(Self {
+            dir,
+            config,
+            segment,
+            segment_len,
+            next_sequence,
+            scratch: Vec::new(),
+        })
+    }
+
+    /// Sequence the next appended record will get.
+    pub fn next_sequence(&self) -> u64 {
+        self.next_sequence
+    }
+
+    /// Appends one encoded frame received at `timestamp` (epoch ns), returns its sequence.
+    /// Frames of 4 GiB or more fail with `FrameTooLong`.
+    pub fn append(&mut self, frame: &[u8], timestamp: u64) -> JournalResult<u64> {
+        let sequence = self.next_sequence;
+        let header = RecordHeader::new(sequence, timestamp, frame)?;
+        let record_length = header.record_length();
+        if self.segment_len > 0 && self.segment_len + record_length > self.config.segment_size {
+            self.roll()?;
+        }
+
+        self.scratch.clear();
+        header.write_to(&mut self.scratch);
+        self.scratch.extend_from_slice(frame);
+        // single write so a crash leaves at most one torn record at the tail
+        self.segment.write_all(&self.scratch)?;
+        if self.config.sync_on_append {
+            self.segment.sync_data()?;
+        }
+
+        self.segment_len += record_length;
+        self.next_sequence += 1;
+        Ok(sequence)
+    }
+
+    /// Flushes appended records to disk.
+    pub fn sync(&mut self) -> JournalResult<()> {
+        self.segment.sync_data()?;
+        Ok(())
+    }
+
+    fn roll(&mut self) -> JournalResult<()> {
+        self.segment.sync_all()?;
+        self.segment = create_segment(&segment_path(&self.dir, self.next_sequence))?;
+        self.segment_len = 0;
+        Ok(())
+    }
+}
+
+/// Reads the last segment up to its end or torn tail, returns the reader positioned after the
+/// last intact record and the sequence following it.
+fn scan_last(last: &Segment) -> JournalResult<(SegmentReader, u64)> {
+    let mut reader = SegmentReader::open(last)?;
+    let mut frame = Vec::new();
+    let mut next_sequence = last.first_sequence;
+    loop {
+        match reader.next(&mut frame)? {
+            ReadOutcome::Record(header) => next_sequence = header.sequence + 1,
+            ReadOutcome::End | ReadOutcome::Torn => return Ok((reader, next_sequence)),
+            ReadOutcome::Corrupt => {
+                return Err(JournalError::Corrupt {
+                    segment: last.path.clone(),
+                    offset: reader.position,
+                });
+            }
+        }
+    }
+}
+
+fn create_segment(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// Where a replay starts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Start {
+    Sequence(u64),
+    Timestamp(u64),
+}
+
+/// Reads records back from a journal directory.
+pub struct Replayer {
+    dir: PathBuf,
+    segments: Vec<Segment>,
+}
+
+impl Replayer {
+    /// Snapshot of the segments currently in `dir`, segments rolled later are not seen.
+    pub fn open(dir: impl AsRef<Path>) -> JournalResult<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        let segments = list_segments(&dir)?;
+        Ok(Self { dir, segments })
+    }
+
+    /// Sequence following the last intact record, the one `Journal::open` continues with.
+    pub fn next_sequence(&self) -> JournalResult<u64> {
+        match self.segments.last() {
+            Some(last) => Ok(scan_last(last)?.1),
+            None => Ok(FIRST_SEQUENCE),
+        }
+    }
+
+    /// Reads the single record at `position`, as returned in `Record::position`.
+    pub fn read(&self, position: RecordPosition) -> JournalResult<Record> {
+        let segment = Segment {
+            first_sequence: position.segment,
+            path: segment_path(&self.dir, position.segment),
+        };
+        let mut reader = SegmentReader::open(&segment)?;
+        reader.seek(position.offset)?;
+        let mut frame = Vec::new();
+        match reader.next(&mut frame)? {
+            ReadOutcome::Record(header) => Ok(Record {
+                sequence: header.sequence,
+                position,
+                timestamp: header.timestamp,
+                frame,
+            }),
+            _ => Err(JournalError::Corrupt {
+                segment: segment.path,
+                offset: position.offset,
+            }),
+        }
+    }
+
+    /// Records with a sequence of at least `sequence`.
+    pub fn from_sequence(&self, sequence: u64) -> Records {
+        // skip whole segments that end before the requested sequence
+        let first = self
+            .segments
+            .iter()
+            .rposition(|s| s.first_sequence <= sequence)
+            .unwrap_or(0);
+        Records::new(self.segments[first..].to_vec(), Start::Sequence(sequence))
+    }
+
+    /// Records starting at the first one received at or after `timestamp` (epoch ns).
+    pub fn from_timestamp(&self, timestamp: u64) -> Records {
+        Records::new(self.segments.clone(), Start::Timestamp(timestamp))
+    }
+
+    /// Dispatches every record from `sequence` onwards to `handler`, returns how many were
+    /// replayed.
+    pub fn replay<H: MessageHandler>(&self, sequence: u64, handler: &mut H) -> JournalResult<u64> {
+        Self::dispatch_all(self.from_sequence(sequence), handler)
+    }
+
+    /// Dispatches every record received at or after `timestamp` to `handler`.
+    pub fn replay_from_timestamp<H: MessageHandler>(
+        &self,
+        timestamp: u64,
+        handler: &mut H,
+    ) -> JournalResult<u64> {
+        Self::dispatch_all(self.from_timestamp(timestamp), handler)
+    }
+
+    fn dispatch_all<H: MessageHandler>(records: Records, handler: &mut H) -> JournalResult<u64> {
+        let mut count = 0;
+        for record in records {
+            let record = record?;
+            dispatch::dispatch(&record.frame, handler).map_err(|error| JournalError::Dispatch {
+                sequence: record.sequence,
+                error,
+            })?;
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+/// Iterator over journal records, see `Replayer`.
+pub struct Records {
+    segments: std::vec::IntoIter<Segment>,
+    current: Option<SegmentReader>,
+    start: Option<Start>,
+    frame: Vec<u8>,
+}
+
+impl Records {
+    fn new(segments: Vec<Segment>, start: Start) -> Self {
+        Self {
+            segments: segments.into_iter(),
+            current: None,
+            start: Some(start),
+            frame: Vec::new(),
+        }
+    }
+
+    fn next_record(&mut self) -> JournalResult<Option<Record>> {
+        loop {
+            let reader = match self.current.as_mut() {
+                Some(reader) => reader,
+                None => match self.segments.next() {
+                    Some(segment) => self.current.insert(SegmentReader::open(&segment)?),
+                    None => return Ok(None),
+                },
+            };
+
+            let position = RecordPosition {
+                segment: reader.first_sequence,
+                offset: reader.position,
+            };
+            let header = match reader.next(&mut self.frame)? {
+                ReadOutcome::Record(header) => header,
+                // a torn tail is only legal on the segment still being written
+                ReadOutcome::End | ReadOutcome::Torn if self.segments.len() == 0 => {
+                    return Ok(None);
+                }
+                ReadOutcome::End => {
+                    self.current = None;
+                    continue;
+                }
+                ReadOutcome::Torn | ReadOutcome::Corrupt => {
+                    return Err(JournalError::Corrupt {
+                        segment: reader.path.clone(),
+                        offset: reader.position,
+                    });
+                }
+            };
+
+            let started = match self.start {
+                Some(Start::Sequence(sequence)) => header.sequence >= sequence,
+                Some(Start::Timestamp(timestamp)) => header.timestamp >= timestamp,
+                None => true,
+            };
+            if started {
+                self.start = None;
+                return Ok(Some(Record {
+                    sequence: header.sequence,
+                    position,
+                    timestamp: header.timestamp,
+                    frame: self.frame.clone(),
+                }));
+            }
+        }
+    }
+}
+
+impl Iterator for Records {
+    type Item = JournalResult<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.next_record();
+        if next.is_err() {
+            // do not read past a corrupt record
+            self.current = None;
+            self.segments = Vec::new().into_iter();
+        }
+        next.transpose()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProblemKind {
+    /// segment ends part way through a record
+    TornRecord,
+    /// record does not match its crc, the rest of the segment is not checked
+    CrcMismatch,
+    /// record sequence does not follow the previous one, or a segment does not start where the
+    /// previous one ended
+    SequenceGap { expected: u64, found: u64 },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    pub segment: PathBuf,
+    pub offset: u64,
+    pub kind: ProblemKind,
+}
+
+/// Result of `verify`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VerifyReport {
+    pub segments: usize,
+    pub records: u64,
+    pub first_sequence: Option<u64>,
+    pub last_sequence: Option<u64>,
+    pub problems: Vec<Problem>,
+}
+
+impl VerifyReport {
+    pub fn is_clean(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Checks every record of the journal in `dir` without modifying it, e.g. after a crash.
+pub fn verify(dir: impl AsRef<Path>) -> JournalResult<VerifyReport> {
+    let segments = list_segments(dir.as_ref())?;
+    let mut report = VerifyReport {
+        segments: segments.len(),
+        ..VerifyReport::default()
+    };
+    let mut frame = Vec::new();
+    // sequence the next segment starts with, unknown after a damaged segment
+    let mut next_segment = None;
+
+    for segment in segments {
+        if let Some(expected) = next_segment
+            && expected != segment.first_sequence
+        {
+            report.problems.push(Problem {
+                segment: segment.path.clone(),
+                offset: 0,
+                kind: ProblemKind::SequenceGap {
+                    expected,
+                    found: segment.first_sequence,
+                },
+            });
+        }
+        let mut reader = SegmentReader::open(&segment)?;
+        let mut expected = segment.first_sequence;
+        let kind = loop {
+            match reader.next(&mut frame)? {
+                ReadOutcome::Record(header) => {
+                    if header.sequence != expected {
+                        report.problems.push(Problem {
+                            segment: segment.path.clone(),
+                            offset: reader.position - header.record_length(),
+                            kind: ProblemKind::SequenceGap {
+                                expected,
+                                found: header.sequence,
+                            },
+                        });
+                    }
+                    expected = header.sequence + 1;
+                    report.records += 1;
+                    report.first_sequence.get_or_insert(header.sequence);
+                    report.last_sequence = Some(header.sequence);
+                }
+                ReadOutcome::End => break None,
+                ReadOutcome::Torn => break Some(ProblemKind::TornRecord),
+                ReadOutcome::Corrupt => break Some(ProblemKind::CrcMismatch),
+            }
+        };
+        next_segment = kind.is_none().then_some(expected);
+        if let Some(kind) = kind {
+            report.problems.push(Problem {
+                segment: segment.path,
+                offset: reader.position,
+                kind,
+            });
+        }
+    }
+    Ok(report)
+}
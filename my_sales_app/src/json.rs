@@ -0,0 +1,525 @@
+//! JSON form of the schema messages, for test orders written by hand and for reading frames.
+//!
+//! An object names its message in `"type"` and its fields as in messages.xml:
+//!
+//! ```json
+//! { "type": "OrderMessage", "orderId": 234, "clientId": 135, "timestamp": 246,
+//!   "orderType": "New", "orderFlags": ["PostOnly"],
+//!   "items": [{ "productId": 222, "quantity": 2, "unitPrice": "2.34", "currency": "EUR",
+//!               "sku": "SKU-222", "discounts": [{ "code": 7, "amount": "0.50" }],
+//!               "itemNote": "gift" }],
+//!   "customerNote": "duck is angry" }
+//! ```
+//!
+//! Fields of a block are required. `orderFlags`, `currency`, `sku`, groups and var data default
+//! to empty. Integers take the range of their type without the null value, enums and sets their
+//! names, `null` is the null value of an enum. Prices are decimals with at most two fraction
+//! digits, as a string or a number, and are written as strings since an `i64` mantissa does not
+//! fit a JSON number. Constants are not part of the JSON.
+
+use std::fmt;
+
+use sales_generated::{
+    ir, order_flags::OrderFlags, order_status::OrderStatus, order_type::OrderType,
+};
+use serde_json::{Map, Value, json};
+
+use crate::{
+    dispatch::DispatchError,
+    model::{Discount, Item, Message, OrderMessage, OrderResponse},
+};
+
+/// Exponent of every `Decimal` of the schema
+const DECIMAL_PLACES: u32 = 2;
+/// Largest group count and var data length, `uint16` without the null value
+const MAX_COUNT: usize = u16::MAX as usize - 1;
+
+/// Errors name the field by its path, e.g. `items[1].quantity`, `$` is the object itself
+#[derive(Debug)]
+pub enum JsonError {
+    Syntax(serde_json::Error),
+    /// `type` is not a message of the schema
+    UnknownMessage(String),
+    MissingField(String),
+    UnknownField(String),
+    WrongType {
+        path: String,
+        expected: &'static str,
+    },
+    OutOfRange {
+        path: String,
+        value: String,
+        min: String,
+        max: String,
+    },
+    /// name of neither a valid value nor a choice, `expected` lists those
+    UnknownName {
+        path: String,
+        name: String,
+        expected: Vec<&'static str>,
+    },
+    /// price with more than two fraction digits, an exponent or no digits
+    NotDecimal {
+        path: String,
+        value: String,
+    },
+    NotAscii(String),
+    /// text or group longer than its field holds
+    TooLong {
+        path: String,
+        len: usize,
+        max: usize,
+    },
+    Decode(DispatchError),
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax(e) => write!(f, "invalid json: {e}"),
+            Self::UnknownMessage(name) => write!(
+                f,
+                "type: unknown message {name:?}, expected OrderMessage or OrderResponse"
+            ),
+            Self::MissingField(path) => write!(f, "{path}: missing"),
+            Self::UnknownField(path) => write!(f, "{path}: unknown field"),
+            Self::WrongType { path, expected } => write!(f, "{path}: expected {expected}"),
+            Self::OutOfRange {
+                path,
+                value,
+                min,
+                max,
+            } => write!(f, "{path}: {value} is out of range {min}..={max}"),
+            Self::UnknownName {
+                path,
+                name,
+                expected,
+            } => write!(
+                f,
+                "{path}: unknown name {name:?}, expected one of {}",
+                expected.join(", ")
+            ),
+            Self::NotDecimal { path, value } => write!(
+                f,
+                "{path}: {value} is not a decimal with at most {DECIMAL_PLACES} fraction digits"
+            ),
+            Self::NotAscii(path) => write!(f, "{path}: not printable US-ASCII"),
+            Self::TooLong { path, len, max } => {
+                write!(f, "{path}: length {len} is longer than {max}")
+            }
+            Self::Decode(e) => write!(f, "cannot decode frame: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for JsonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Syntax(e) => Some(e),
+            Self::Decode(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for JsonError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Syntax(e)
+    }
+}
+
+impl From<DispatchError> for JsonError {
+    fn from(e: DispatchError) -> Self {
+        Self::Decode(e)
+    }
+}
+
+/// Encodes the message of a JSON object into a frame, header included.
+pub fn to_frame(value: &Value) -> Result<Vec<u8>, JsonError> {
+    let mut frame = Vec::new();
+    match to_message(value)? {
+        Message::Order(order) => order.encode_to(&mut frame),
+        Message::Response(response) => response.encode_to(&mut frame),
+    };
+    Ok(frame)
+}
+
+/// Decodes a frame into its JSON object, see `Message::decode_checked`.
+pub fn from_frame(frame: &[u8]) -> Result<Value, JsonError> {
+    Ok(from_message(&Message::decode_checked(frame)?))
+}
+
+/// Checks a JSON object against the schema, all that passes encodes without panicking.
+pub fn to_message(value: &Value) -> Result<Message, JsonError> {
+    let Value::Object(map) = value else {
+        return Err(wrong_type("$".to_string(), "an object"));
+    };
+    let object = Object {
+        map,
+        path: String::new(),
+    };
+    let message = match object.required("type")? {
+        Value::String(name) if name == "OrderMessage" => Message::Order(order_message(&object)?),
+        Value::String(name) if name == "OrderResponse" => {
+            Message::Response(order_response(&object)?)
+        }
+        Value::String(name) => return Err(JsonError::UnknownMessage(name.clone())),
+        _ => return Err(wrong_type("type".to_string(), "a string")),
+    };
+    Ok(message)
+}
+
+pub fn from_message(message: &Message) -> Value {
+    match message {
+        Message::Order(order) => json!({
+            "type": "OrderMessage",
+            "orderId": order.order_id,
+            "clientId": order.client_id,
+            "timestamp": order.timestamp,
+            "orderType": enum_name(&ir::ORDER_TYPE, u8::from(order.order_type)),
+            "orderFlags": ir::ORDER_FLAGS
+                .choices
+                .iter()
+                .filter(|choice| order.order_flags.0 & (1 << choice.bit) != 0)
+                .map(|choice| choice.name)
+                .collect::<Vec<_>>(),
+            "items": order.items.iter().map(|item| json!({
+                "productId": item.product_id,
+                "quantity": item.quantity,
+                "unitPrice": decimal_string(item.unit_price),
+                "currency": item.currency,
+                "sku": item.sku,
+                "discounts": item.discounts.iter().map(|discount| json!({
+                    "code": discount.code,
+                    "amount": decimal_string(discount.amount),
+                })).collect::<Vec<_>>(),
+                "itemNote": item.item_note,
+            })).collect::<Vec<_>>(),
+            "customerNote": order.customer_note,
+        }),
+        Message::Response(response) => json!({
+            "type": "OrderResponse",
+            "orderId": response.order_id,
+            "timestamp": response.timestamp,
+            "status": enum_name(&ir::ORDER_STATUS, u8::from(response.status)),
+            "filledQty": response.filled_qty,
+            "fillPrice": decimal_string(response.fill_price),
+            "serverNote": response.server_note,
+        }),
+    }
+}
+
+fn order_message(object: &Object) -> Result<OrderMessage, JsonError> {
+    object.deny_unknown(&[
+        "type",
+        "orderId",
+        "clientId",
+        "timestamp",
+        "orderType",
+        "orderFlags",
+        "items",
+        "customerNote",
+    ])?;
+    let items = match object.optional("items") {
+        Some(items) => object
+            .array(items, "items")?
+            .map(|(item, path)| self::item(&Object::new(item, path)?))
+            .collect::<Result<_, _>>()?,
+        None => Vec::new(),
+    };
+    Ok(OrderMessage {
+        order_id: object.uint("orderId", u64::MAX)?,
+        client_id: object.uint("clientId", u64::MAX)?,
+        timestamp: object.uint("timestamp", u64::MAX)?,
+        order_type: OrderType::from(object.enum_value("orderType", &ir::ORDER_TYPE)?),
+        order_flags: match object.optional("orderFlags") {
+            Some(flags) => order_flags(object, flags)?,
+            None => OrderFlags::default(),
+        },
+        items,
+        customer_note: object.text("customerNote")?,
+    })
+}
+
+fn item(object: &Object) -> Result<Item, JsonError> {
+    object.deny_unknown(&[
+        "productId",
+        "quantity",
+        "unitPrice",
+        "currency",
+        "sku",
+        "discounts",
+        "itemNote",
+    ])?;
+    let discounts = match object.optional("discounts") {
+        Some(discounts) => object
+            .array(discounts, "discounts")?
+            .map(|(discount, path)| {
+                let object = Object::new(discount, path)?;
+                object.deny_unknown(&["code", "amount"])?;
+                Ok(Discount {
+                    code: object.uint("code", u16::MAX)?,
+                    amount: object.decimal("amount")?,
+                })
+            })
+            .collect::<Result<_, JsonError>>()?,
+        None => Vec::new(),
+    };
+    Ok(Item {
+        product_id: object.uint("productId", u32::MAX)?,
+        quantity: object.uint("quantity", u16::MAX)?,
+        unit_price: object.decimal("unitPrice")?,
+        currency: object.char_array("currency", 3)?,
+        sku: object.char_array("sku", 16)?,
+        discounts,
+        item_note: object.text("itemNote")?,
+    })
+}
+
+fn order_flags(object: &Object, flags: &Value) -> Result<OrderFlags, JsonError> {
+    let mut raw = 0u8;
+    for (flag, path) in object.array(flags, "orderFlags")? {
+        let Value::String(name) = flag else {
+            return Err(wrong_type(path, "a string"));
+        };
+        let choice = ir::ORDER_FLAGS
+            .choices
+            .iter()
+            .find(|choice| choice.name == name)
+            .ok_or_else(|| JsonError::UnknownName {
+                path,
+                name: name.clone(),
+                expected: ir::ORDER_FLAGS
+                    .choices
+                    .iter()
+                    .map(|choice| choice.name)
+                    .collect(),
+            })?;
+        raw |= 1 << choice.bit;
+    }
+    Ok(OrderFlags::new(raw))
+}
+
+fn order_response(object: &Object) -> Result<OrderResponse, JsonError> {
+    object.deny_unknown(&[
+        "type",
+        "orderId",
+        "timestamp",
+        "status",
+        "filledQty",
+        "fillPrice",
+        "serverNote",
+    ])?;
+    Ok(OrderResponse {
+        order_id: object.uint("orderId", u64::MAX)?,
+        timestamp: object.uint("timestamp", u64::MAX)?,
+        status: OrderStatus::from(object.enum_value("status", &ir::ORDER_STATUS)?),
+        filled_qty: object.uint("filledQty", u32::MAX)?,
+        fill_price: object.decimal("fillPrice")?,
+        server_note: object.text("serverNote")?,
+    })
+}
+
+fn wrong_type(path: String, expected: &'static str) -> JsonError {
+    JsonError::WrongType { path, expected }
+}
+
+/// valid value name of `raw`, `null` for the null value
+fn enum_name(ty: &ir::Enum, raw: u8) -> Value {
+    match ty.value(raw as u64) {
+        Some(value) => Value::from(value.name),
+        None => Value::Null,
+    }
+}
+
+fn decimal_string(mantissa: i64) -> String {
+    let scale = 10u64.pow(DECIMAL_PLACES);
+    let sign = if mantissa < 0 { "-" } else { "" };
+    let abs = mantissa.unsigned_abs();
+    format!(
+        "{sign}{}.{:0width$}",
+        abs / scale,
+        abs % scale,
+        width = DECIMAL_PLACES as usize
+    )
+}
+
+/// mantissa of `text`, `None` if it is no plain decimal or has too many fraction digits
+fn parse_decimal(text: &str) -> Option<i128> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text),
+    };
+    let (int, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if int.is_empty() || !is_digits(int) || !is_digits(fraction) {
+        return None;
+    }
+    // zeros past the exponent do not change the value
+    let places = DECIMAL_PLACES as usize;
+    if fraction.len() > places && fraction[places..].bytes().any(|b| b != b'0') {
+        return None;
+    }
+    let mut mantissa: i128 = 0;
+    for digit in int
+        .bytes()
+        .chain(fraction.bytes().chain(std::iter::repeat(b'0')).take(places))
+    {
+        mantissa = mantissa
+            .checked_mul(10)?
+            .checked_add((digit - b'0') as i128)?;
+    }
+    Some(if negative { -mantissa } else { mantissa })
+}
+
+/// JSON object at `path`, fields are looked up by name
+struct Object<'a> {
+    map: &'a Map<String, Value>,
+    path: String,
+}
+
+impl<'a> Object<'a> {
+    fn new(value: &'a Value, path: String) -> Result<Self, JsonError> {
+        match value {
+            Value::Object(map) => Ok(Self { map, path }),
+            _ => Err(wrong_type(path, "an object")),
+        }
+    }
+
+    fn path(&self, key: &str) -> String {
+        if self.path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{key}", self.path)
+        }
+    }
+
+    fn deny_unknown(&self, known: &[&str]) -> Result<(), JsonError> {
+        match self.map.keys().find(|key| !known.contains(&key.as_str())) {
+            Some(key) => Err(JsonError::UnknownField(self.path(key))),
+            None => Ok(()),
+        }
+    }
+
+    fn optional(&self, key: &str) -> Option<&'a Value> {
+        self.map.get(key)
+    }
+
+    fn required(&self, key: &str) -> Result<&'a Value, JsonError> {
+        self.map
+            .get(key)
+            .ok_or_else(|| JsonError::MissingField(self.path(key)))
+    }
+
+    /// entries of the array `value` of field `key` with their paths
+    fn array(
+        &self,
+        value: &'a Value,
+        key: &str,
+    ) -> Result<impl Iterator<Item = (&'a Value, String)>, JsonError> {
+        let path = self.path(key);
+        let Value::Array(entries) = value else {
+            return Err(wrong_type(path, "an array"));
+        };
+        if entries.len() > MAX_COUNT {
+            return Err(JsonError::TooLong {
+                path,
+                len: entries.len(),
+                max: MAX_COUNT,
+            });
+        }
+        Ok(entries
+            .iter()
+            .enumerate()
+            .map(move |(i, entry)| (entry, format!("{path}[{i}]"))))
+    }
+
+    /// unsigned integer below the null value `null`
+    fn uint<T: TryFrom<u64>>(&self, key: &str, null: T) -> Result<T, JsonError>
+    where
+        u64: From<T>,
+    {
+        let value = self.required(key)?;
+        let max = u64::from(null) - 1;
+        match value.as_u64() {
+            Some(raw) if raw <= max => Ok(T::try_from(raw).ok().expect("below the null value")),
+            _ if value.is_i64() || value.is_u64() => Err(JsonError::OutOfRange {
+                path: self.path(key),
+                value: value.to_string(),
+                min: "0".to_string(),
+                max: max.to_string(),
+            }),
+            _ => Err(wrong_type(self.path(key), "an integer")),
+        }
+    }
+
+    /// mantissa of a `Decimal`, `int64` without the null value
+    fn decimal(&self, key: &str) -> Result<i64, JsonError> {
+        let text = match self.required(key)? {
+            Value::String(text) => text.trim().to_string(),
+            // the digits as written, see `arbitrary_precision` in Cargo.toml
+            Value::Number(number) => number.to_string(),
+            _ => return Err(wrong_type(self.path(key), "a decimal")),
+        };
+        let mantissa = parse_decimal(&text).ok_or_else(|| JsonError::NotDecimal {
+            path: self.path(key),
+            value: text.clone(),
+        })?;
+        match i64::try_from(mantissa) {
+            Ok(mantissa) if mantissa != i64::MIN => Ok(mantissa),
+            _ => Err(JsonError::OutOfRange {
+                path: self.path(key),
+                value: text,
+                min: decimal_string(i64::MIN + 1),
+                max: decimal_string(i64::MAX),
+            }),
+        }
+    }
+
+    fn enum_value(&self, key: &str, ty: &ir::Enum<'static>) -> Result<u8, JsonError> {
+        match self.required(key)? {
+            // null value of the uint8 encoding of both enums
+            Value::Null => Ok(u8::MAX),
+            Value::String(name) => ty
+                .value_by_name(name)
+                .map(|value| value.value as u8)
+                .ok_or_else(|| JsonError::UnknownName {
+                    path: self.path(key),
+                    name: name.clone(),
+                    expected: ty.values.iter().map(|value| value.name).collect(),
+                }),
+            _ => Err(wrong_type(self.path(key), "a string or null")),
+        }
+    }
+
+    /// optional text of a `char` array of `len` bytes
+    fn char_array(&self, key: &str, len: usize) -> Result<String, JsonError> {
+        let text = self.text(key)?;
+        // same as `sales_generated::char_array`, NUL and control characters do not read back
+        if !text.bytes().all(|b| matches!(b, b' '..=b'~')) {
+            return Err(JsonError::NotAscii(self.path(key)));
+        }
+        if text.len() > len {
+            return Err(JsonError::TooLong {
+                path: self.path(key),
+                len: text.len(),
+                max: len,
+            });
+        }
+        Ok(text)
+    }
+
+    /// optional text of var data, the length in UTF-8 bytes
+    fn text(&self, key: &str) -> Result<String, JsonError> {
+        match self.optional(key) {
+            None => Ok(String::new()),
+            Some(Value::String(text)) if text.len() > MAX_COUNT => Err(JsonError::TooLong {
+                path: self.path(key),
+                len: text.len(),
+                max: MAX_COUNT,
+            }),
+            Some(Value::String(text)) => Ok(text.clone()),
+            Some(_) => Err(wrong_type(self.path(key), "a string")),
+        }
+    }
+}
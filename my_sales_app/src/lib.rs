@@ -0,0 +1,10 @@
+pub mod batch;
+pub mod dispatch;
+pub mod fill_simulator;
+pub mod interpreter;
+pub mod journal;
+pub mod json;
+pub mod model;
+pub mod order_index;
+pub mod order_manager;
+pub mod risk;
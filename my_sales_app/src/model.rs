@@ -0,0 +1,400 @@
+//! Owned versions of the schema messages, for when a decoded message has to outlive its buffer.
+//!
+//! Prices are kept as the `Decimal` mantissa, the exponent is the schema constant -2.
+
+use sales_generated::{
+    Encoder, Reader, WriteBuf, WriteBuffer, message_header_codec,
+    order_flags::OrderFlags,
+    order_message_codec::{
+        self, OrderMessageDecoder, OrderMessageEncoder,
+        encoder::{DiscountsEncoder, ItemsEncoder},
+    },
+    order_response_codec::{self, OrderResponseDecoder, OrderResponseEncoder},
+    order_status::OrderStatus,
+    order_type::OrderType,
+};
+
+use crate::dispatch::{self, DispatchError, MessageHandler};
+
+type DecodeResult<T> = Result<T, DispatchError>;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Discount {
+    pub code: u16,
+    /// mantissa of the amount off the unit price, exponent -2
+    pub amount: i64,
+}
+
+/// Discounts and item note are empty when decoded from a frame before version 3, currency and
+/// sku before version 5.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Item {
+    pub product_id: u32,
+    pub quantity: u16,
+    /// mantissa of the unit price, exponent -2
+    pub unit_price: i64,
+    /// ASCII, at most 3 bytes
+    pub currency: String,
+    /// ASCII, at most 16 bytes
+    pub sku: String,
+    pub discounts: Vec<Discount>,
+    pub item_note: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct OrderMessage {
+    pub order_id: u64,
+    pub client_id: u64,
+    pub timestamp: u64,
+    pub order_type: OrderType,
+    /// empty when decoded from a version 1 frame
+    pub order_flags: OrderFlags,
+    pub items: Vec<Item>,
+    pub customer_note: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct OrderResponse {
+    pub order_id: u64,
+    pub timestamp: u64,
+    pub status: OrderStatus,
+    pub filled_qty: u32,
+    /// mantissa of the fill price, exponent -2
+    pub fill_price: i64,
+    pub server_note: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Message {
+    Order(OrderMessage),
+    Response(OrderResponse),
+}
+
+impl OrderMessage {
+    /// Encodes header and message into `buffer`, returns the frame length.
+    /// Panics if `buffer` is too small, there are more items or discounts of an item than
+    /// `numInGroup` can count, see `SbeErr::CountOutOfRange`, a currency or sku does not fit
+    /// its `char` array, see `SbeErr::CharArrayTooLong` and `SbeErr::NotAscii`, or a note is
+    /// longer than its `length` can count, see `SbeErr::DataTooLong`.
+    pub fn encode(&self, buffer: &mut [u8]) -> usize {
+        self.encode_buf(WriteBuf::new(buffer))
+    }
+
+    /// Same as `encode` into the start of a buffer that grows to fit, e.g. a `Vec<u8>`, which is
+    /// then truncated to the frame.
+    pub fn encode_to(&self, buffer: &mut dyn WriteBuffer) -> usize {
+        let len = self.encode_buf(WriteBuf::growable(buffer));
+        buffer.finish(len);
+        len
+    }
+
+    /// Exact length of the frame `encode` writes.
+    pub fn compute_length(&self) -> usize {
+        OrderMessageEncoder::compute_length(
+            self.items
+                .iter()
+                .map(|item| (item.discounts.len(), item.item_note.len())),
+            self.customer_note.len(),
+        )
+    }
+
+    #[inline]
+    fn encode_buf(&self, buf: WriteBuf<'_>) -> usize {
+        let mut order =
+            OrderMessageEncoder::default().wrap(buf, message_header_codec::ENCODED_LENGTH);
+        order = order.header(0).parent().unwrap();
+        order.order_id(self.order_id);
+        order.client_id(self.client_id);
+        order.timestamp(self.timestamp);
+        order.order_type(self.order_type);
+        order.order_flags(self.order_flags);
+
+        let mut items = order
+            .items_encoder_checked(self.items.len(), ItemsEncoder::default())
+            .expect("too many items");
+        for item in &self.items {
+            let _r = items.advance();
+            items.product_id(item.product_id);
+            items.quantity(item.quantity);
+            let mut price = items.unit_price_encoder();
+            price.mantissa(item.unit_price);
+            items = price.parent().unwrap();
+            items.currency_str(&item.currency).expect("currency");
+            items.sku_str(&item.sku).expect("sku");
+
+            let mut discounts = items
+                .discounts_encoder_checked(item.discounts.len(), DiscountsEncoder::default())
+                .expect("too many discounts");
+            for discount in &item.discounts {
+                let _r = discounts.advance();
+                discounts.code(discount.code);
+                let mut amount = discounts.amount_encoder();
+                amount.mantissa(discount.amount);
+                discounts = amount.parent().unwrap();
+            }
+            items = discounts.parent().unwrap();
+            items.item_note(&item.item_note);
+        }
+        order = items.parent().unwrap();
+
+        order.customer_note(&self.customer_note);
+        order.get_limit()
+    }
+}
+
+impl From<OrderMessageDecoder<'_>> for OrderMessage {
+    fn from(mut order: OrderMessageDecoder<'_>) -> Self {
+        let mut message = Self {
+            order_id: order.order_id(),
+            client_id: order.client_id(),
+            timestamp: order.timestamp(),
+            order_type: order.order_type(),
+            order_flags: order.order_flags(),
+            ..Self::default()
+        };
+
+        let mut items = order.items_decoder();
+        while let Ok(Some(_)) = items.advance() {
+            let mut item = Item {
+                product_id: items.product_id(),
+                quantity: items.quantity(),
+                ..Item::default()
+            };
+            let mut price = items.unit_price_decoder();
+            item.unit_price = price.mantissa();
+            items = price.parent().unwrap();
+            item.currency = char_array(&items.currency());
+            item.sku = char_array(&items.sku());
+
+            if items.acting_version() >= 3 {
+                let mut discounts = items.discounts_decoder().unwrap();
+                while let Ok(Some(_)) = discounts.advance() {
+                    let code = discounts.code();
+                    let mut amount = discounts.amount_decoder();
+                    item.discounts.push(Discount {
+                        code,
+                        amount: amount.mantissa(),
+                    });
+                    discounts = amount.parent().unwrap();
+                }
+                items = discounts.parent().unwrap();
+            }
+            let (offset, len) = items.item_note_decoder();
+            item.item_note = utf8(items.get_buf().get_slice_at(offset, len));
+            message.items.push(item);
+        }
+        order = items.parent().unwrap();
+
+        let (offset, len) = order.customer_note_decoder();
+        message.customer_note = utf8(order.get_buf().get_slice_at(offset, len));
+        message
+    }
+}
+
+impl OrderMessage {
+    /// Bounds checked decode, fails instead of panicking on malformed input.
+    pub fn decode_checked(order: OrderMessageDecoder<'_>) -> DecodeResult<Self> {
+        let mut message = Self {
+            order_id: order.order_id(),
+            client_id: order.client_id(),
+            timestamp: order.timestamp(),
+            order_type: order.order_type(),
+            order_flags: order.order_flags(),
+            ..Self::default()
+        };
+
+        let mut items = order.items_decoder_checked()?;
+        while items.advance()?.is_some() {
+            let mut item = Item {
+                product_id: items.product_id(),
+                quantity: items.quantity(),
+                ..Item::default()
+            };
+            let mut price = items.unit_price_decoder();
+            item.unit_price = price.mantissa();
+            items = price.parent()?;
+            item.currency = items.currency_str()?.to_owned();
+            item.sku = items.sku_str()?.to_owned();
+
+            let mut discounts = items.discounts_decoder_checked()?;
+            while discounts.advance()?.is_some() {
+                let code = discounts.code();
+                let mut amount = discounts.amount_decoder();
+                item.discounts.push(Discount {
+                    code,
+                    amount: amount.mantissa(),
+                });
+                discounts = amount.parent()?;
+            }
+            items = discounts.parent()?;
+            let (offset, len) = items.item_note_decoder_checked()?;
+            item.item_note = utf8_checked(items.get_buf().get_slice_at(offset, len))?;
+            message.items.push(item);
+        }
+        let mut order = items.parent()?;
+
+        let (offset, len) = order.customer_note_decoder_checked()?;
+        message.customer_note = utf8_checked(order.get_buf().get_slice_at(offset, len))?;
+        Ok(message)
+    }
+}
+
+impl OrderResponse {
+    /// Encodes header and message into `buffer`, returns the frame length.
+    /// Panics if `buffer` is too small or the server note is longer than its `length` can count,
+    /// see `SbeErr::DataTooLong`.
+    pub fn encode(&self, buffer: &mut [u8]) -> usize {
+        self.encode_buf(WriteBuf::new(buffer))
+    }
+
+    /// Same as `encode` into the start of a buffer that grows to fit, e.g. a `Vec<u8>`, which is
+    /// then truncated to the frame.
+    pub fn encode_to(&self, buffer: &mut dyn WriteBuffer) -> usize {
+        let len = self.encode_buf(WriteBuf::growable(buffer));
+        buffer.finish(len);
+        len
+    }
+
+    /// Exact length of the frame `encode` writes.
+    pub fn compute_length(&self) -> usize {
+        OrderResponseEncoder::compute_length(self.server_note.len())
+    }
+
+    #[inline]
+    fn encode_buf(&self, buf: WriteBuf<'_>) -> usize {
+        let mut response =
+            OrderResponseEncoder::default().wrap(buf, message_header_codec::ENCODED_LENGTH);
+        response = response.header(0).parent().unwrap();
+        response.order_id(self.order_id);
+        response.timestamp(self.timestamp);
+        response.status(self.status);
+        response.filled_qty(self.filled_qty);
+        let mut price = response.fill_price_encoder();
+        price.mantissa(self.fill_price);
+        response = price.parent().unwrap();
+        response.server_note(&self.server_note);
+        response.get_limit()
+    }
+}
+
+impl From<OrderResponseDecoder<'_>> for OrderResponse {
+    fn from(response: OrderResponseDecoder<'_>) -> Self {
+        let mut price = response.fill_price_decoder();
+        let fill_price = price.mantissa();
+        let mut response = price.parent().unwrap();
+        let (offset, len) = response.server_note_decoder();
+        Self {
+            order_id: response.order_id(),
+            timestamp: response.timestamp(),
+            status: response.status(),
+            filled_qty: response.filled_qty(),
+            fill_price,
+            server_note: utf8(response.get_buf().get_slice_at(offset, len)),
+        }
+    }
+}
+
+impl OrderResponse {
+    /// Bounds checked decode, fails instead of panicking on malformed input.
+    pub fn decode_checked(response: OrderResponseDecoder<'_>) -> DecodeResult<Self> {
+        let mut price = response.fill_price_decoder();
+        let fill_price = price.mantissa();
+        let mut response = price.parent()?;
+        let (offset, len) = response.server_note_decoder_checked()?;
+        Ok(Self {
+            order_id: response.order_id(),
+            timestamp: response.timestamp(),
+            status: response.status(),
+            filled_qty: response.filled_qty(),
+            fill_price,
+            server_note: utf8_checked(response.get_buf().get_slice_at(offset, len))?,
+        })
+    }
+}
+
+impl Message {
+    /// Decodes one frame, header included. Only for trusted input, e.g. frames this process
+    /// encoded: lengths on the wire are not checked and a malformed frame panics. Frames from
+    /// the network or disk go through `decode_checked`.
+    pub fn decode(frame: &[u8]) -> Result<Self, DispatchError> {
+        let mut decoded = Decoded(Err(DispatchError::Truncated));
+        dispatch::dispatch(frame, &mut decoded)?;
+        decoded.0
+    }
+
+    /// Decodes one frame from an untrusted source, every length on the wire is checked against
+    /// the frame and var data must be valid UTF-8.
+    pub fn decode_checked(frame: &[u8]) -> DecodeResult<Self> {
+        let header = dispatch::header(frame)?;
+        match header.template_id() {
+            order_message_codec::SBE_TEMPLATE_ID => {
+                let order = OrderMessageDecoder::default().header_checked(header, 0)?;
+                OrderMessage::decode_checked(order).map(Self::Order)
+            }
+            order_response_codec::SBE_TEMPLATE_ID => {
+                let response = OrderResponseDecoder::default().header_checked(header, 0)?;
+                OrderResponse::decode_checked(response).map(Self::Response)
+            }
+            template_id => Err(DispatchError::UnknownTemplate(template_id)),
+        }
+    }
+
+    pub fn encode(&self, buffer: &mut [u8]) -> usize {
+        match self {
+            Self::Order(order) => order.encode(buffer),
+            Self::Response(response) => response.encode(buffer),
+        }
+    }
+
+    pub fn compute_length(&self) -> usize {
+        match self {
+            Self::Order(order) => order.compute_length(),
+            Self::Response(response) => response.compute_length(),
+        }
+    }
+
+    pub fn encode_to(&self, buffer: &mut dyn WriteBuffer) -> usize {
+        match self {
+            Self::Order(order) => order.encode_to(buffer),
+            Self::Response(response) => response.encode_to(buffer),
+        }
+    }
+
+    pub fn order_id(&self) -> u64 {
+        match self {
+            Self::Order(order) => order.order_id,
+            Self::Response(response) => response.order_id,
+        }
+    }
+}
+
+fn utf8(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+/// `char` array up to its first NUL
+fn char_array(bytes: &[u8]) -> String {
+    utf8(bytes.split(|&b| b == 0).next().unwrap_or_default())
+}
+
+fn utf8_checked(bytes: &[u8]) -> DecodeResult<String> {
+    std::str::from_utf8(bytes)
+        .map(str::to_owned)
+        .map_err(|_| DispatchError::InvalidUtf8)
+}
+
+struct Decoded(Result<Message, DispatchError>);
+
+impl MessageHandler for Decoded {
+    fn on_order_message(&mut self, order: OrderMessageDecoder<'_>) {
+        self.0 = Ok(Message::Order(order.into()));
+    }
+
+    fn on_order_response(&mut self, response: OrderResponseDecoder<'_>) {
+        self.0 = Ok(Message::Response(response.into()));
+    }
+
+    fn on_unknown(&mut self, template_id: u16, _frame: &[u8]) {
+        self.0 = Err(DispatchError::UnknownTemplate(template_id));
+    }
+}
@@ -0,0 +1,441 @@
+//! Persistent index from `order_id` to the journal records of that order.
+//!
+//! Two memory-mapped files live next to the journal segments:
+//! - `order_index.entries` is append only, one 40 byte entry per indexed record
+//!   (order_id, sequence, segment, offset, previous entry of the same order). The header holds
+//!   the entry count and the next journal sequence to index.
+//! - `order_index.heads` is an open addressing hash table from order_id to the latest entry of
+//!   that order. It can always be rebuilt from the entries file and is on open if it lags behind.
+//!
+//! A lookup walks the chain of entries for one order and reads only those records from the
+//! journal, each checked against the sequence and order_id of its entry.
+//!
+//! A crash can leave entries of records the journal lost with its unsynced tail, their sequences
+//! are reused by later appends. `open` drops those entries so `catch_up` indexes the new records.
+//! Both files are locked exclusively while the index is open.
+
+use std::{
+    fs::{File, OpenOptions, TryLockError},
+    io,
+    path::{Path, PathBuf},
+};
+
+use memmap2::MmapMut;
+use sales_generated::{
+    order_message_codec::OrderMessageDecoder, order_response_codec::OrderResponseDecoder,
+};
+
+use crate::{
+    dispatch::{self, DispatchError, MessageHandler},
+    journal::{FIRST_SEQUENCE, JournalError, JournalResult, RecordPosition, Replayer},
+    model::Message,
+};
+
+pub const ENTRIES_FILE: &str = "order_index.entries";
+pub const HEADS_FILE: &str = "order_index.heads";
+
+const ENTRIES_MAGIC: u64 = u64::from_le_bytes(*b"SBEOIDX1");
+const HEADS_MAGIC: u64 = u64::from_le_bytes(*b"SBEOHDS1");
+
+// entries file: magic, count, next_sequence then entries
+const ENTRIES_HEADER_LENGTH: usize = 24;
+const ENTRY_LENGTH: usize = 40;
+// heads file: magic, slot_count, entry_count covered, used slots, then slots of
+// (order_id, entry + 1)
+const HEADS_HEADER_LENGTH: usize = 32;
+const SLOT_LENGTH: usize = 16;
+const MIN_SLOTS: u64 = 1024;
+const MIN_ENTRIES: u64 = 1024;
+
+/// One indexed journal record.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IndexEntry {
+    pub order_id: u64,
+    pub sequence: u64,
+    pub position: RecordPosition,
+}
+
+/// Decoded message together with where it sits in the journal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JournalMessage {
+    pub sequence: u64,
+    pub timestamp: u64,
+    pub message: Message,
+}
+
+/// File mapped read/write as a whole, grown by remapping.
+struct MappedFile {
+    file: File,
+    map: MmapMut,
+}
+
+impl MappedFile {
+    fn open(path: &Path, min_len: usize) -> JournalResult<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        if let Err(TryLockError::WouldBlock) = file.try_lock() {
+            return Err(io::Error::new(
+                io::ErrorKind::WouldBlock,
+                format!("{} is open in another OrderIndex", path.display()),
+            )
+            .into());
+        }
+        if (file.metadata()?.len() as usize) < min_len {
+            file.set_len(min_len as u64)?;
+        }
+        // SAFETY: the exclusive lock keeps every other OrderIndex from mapping the file, nothing
+        // else resizes or writes the index files
+        let map = unsafe { MmapMut::map_mut(&file)? };
+        Ok(Self { file, map })
+    }
+
+    fn resize(&mut self, len: usize) -> JournalResult<()> {
+        self.map.flush()?;
+        self.file.set_len(len as u64)?;
+        // SAFETY: see `open`
+        self.map = unsafe { MmapMut::map_mut(&self.file)? };
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    fn get(&self, offset: usize) -> u64 {
+        u64::from_le_bytes(self.map[offset..offset + 8].try_into().unwrap())
+    }
+
+    fn put(&mut self, offset: usize, value: u64) {
+        self.map[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+    }
+}
+
+pub struct OrderIndex {
+    dir: PathBuf,
+    entries: MappedFile,
+    heads: MappedFile,
+}
+
+impl OrderIndex {
+    /// Opens or creates the index for the journal in `dir`, call `catch_up` to index records
+    /// appended since the last run.
+    pub fn open(dir: impl AsRef<Path>) -> JournalResult<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        let mut entries = MappedFile::open(
+            &dir.join(ENTRIES_FILE),
+            ENTRIES_HEADER_LENGTH + MIN_ENTRIES as usize * ENTRY_LENGTH,
+        )?;
+        if entries.get(0) != ENTRIES_MAGIC {
+            entries.put(0, ENTRIES_MAGIC);
+            entries.put(8, 0);
+            entries.put(16, FIRST_SEQUENCE);
+        }
+        let count = entries.get(8);
+        if ENTRIES_HEADER_LENGTH + count as usize * ENTRY_LENGTH > entries.len() {
+            return Err(JournalError::Corrupt {
+                segment: dir.join(ENTRIES_FILE),
+                offset: 8,
+            });
+        }
+
+        let heads = MappedFile::open(
+            &dir.join(HEADS_FILE),
+            HEADS_HEADER_LENGTH + MIN_SLOTS as usize * SLOT_LENGTH,
+        )?;
+        let mut index = Self {
+            dir,
+            entries,
+            heads,
+        };
+        index.drop_lost()?;
+        if !index.heads_valid() {
+            index.rebuild_heads(slots_for(count))?;
+        }
+        Ok(index)
+    }
+
+    /// Number of indexed records.
+    pub fn len(&self) -> u64 {
+        self.entries.get(8)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// First journal sequence not yet looked at by `catch_up`.
+    pub fn next_sequence(&self) -> u64 {
+        self.entries.get(16)
+    }
+
+    /// Indexes journal records appended since the last call, returns how many were added.
+    pub fn catch_up(&mut self) -> JournalResult<u64> {
+        let replayer = Replayer::open(&self.dir)?;
+        // records up to the last entry are already indexed even if next_sequence was not
+        // persisted before a crash
+        let mut next_sequence = self.next_sequence();
+        if let Some(last) = self.len().checked_sub(1) {
+            next_sequence = next_sequence.max(self.entry(last).sequence + 1);
+        }
+
+        let mut added = 0;
+        for record in replayer.from_sequence(next_sequence) {
+            let record = record?;
+            let order_id = order_id_of(&record.frame).map_err(|error| JournalError::Dispatch {
+                sequence: record.sequence,
+                error,
+            })?;
+            if let Some(order_id) = order_id {
+                self.push(IndexEntry {
+                    order_id,
+                    sequence: record.sequence,
+                    position: record.position,
+                })?;
+                added += 1;
+            }
+            next_sequence = record.sequence + 1;
+        }
+        self.entries.put(16, next_sequence);
+        Ok(added)
+    }
+
+    /// Index entries of `order_id` in journal order, without touching the journal.
+    pub fn entries(&self, order_id: u64) -> Vec<IndexEntry> {
+        let mut entries = Vec::new();
+        let mut next = self.find_slot(order_id).map_or(0, |slot| self.slot(slot).1);
+        while next != 0 {
+            let entry = self.entry(next - 1);
+            entries.push(entry);
+            next = self.entry_prev(next - 1);
+        }
+        entries.reverse();
+        entries
+    }
+
+    /// Every message journaled for `order_id`, in journal order. Fails with `StaleIndex` if a
+    /// record was replaced since it was indexed, reopen the index to drop such entries.
+    pub fn lookup(&self, order_id: u64) -> JournalResult<Vec<JournalMessage>> {
+        let replayer = Replayer::open(&self.dir)?;
+        self.entries(order_id)
+            .into_iter()
+            .map(|entry| {
+                let record = replayer.read(entry.position)?;
+                if record.sequence != entry.sequence {
+                    return Err(JournalError::StaleIndex {
+                        sequence: entry.sequence,
+                    });
+                }
+                let message = Message::decode_checked(&record.frame).map_err(|error| {
+                    JournalError::Dispatch {
+                        sequence: record.sequence,
+                        error,
+                    }
+                })?;
+                if message.order_id() != order_id {
+                    return Err(JournalError::StaleIndex {
+                        sequence: entry.sequence,
+                    });
+                }
+                Ok(JournalMessage {
+                    sequence: record.sequence,
+                    timestamp: record.timestamp,
+                    message,
+                })
+            })
+            .collect()
+    }
+
+    /// Flushes both mapped files to disk.
+    pub fn flush(&self) -> JournalResult<()> {
+        self.entries.map.flush()?;
+        self.heads.map.flush()?;
+        Ok(())
+    }
+
+    /// Drops the entries at the end that the journal no longer has, those at or past its next
+    /// sequence and those whose record was replaced by appends after a crash.
+    fn drop_lost(&mut self) -> JournalResult<()> {
+        let replayer = Replayer::open(&self.dir)?;
+        let journal_next = replayer.next_sequence()?;
+        let mut count = self.len();
+        while let Some(last) = count.checked_sub(1) {
+            let entry = self.entry(last);
+            if entry.sequence < journal_next && self.in_journal(&replayer, entry)? {
+                break;
+            }
+            count = last;
+        }
+
+        let mut next_sequence = self.next_sequence().min(journal_next);
+        if count < self.len() {
+            next_sequence = match count.checked_sub(1) {
+                Some(last) => self.entry(last).sequence + 1,
+                None => FIRST_SEQUENCE,
+            };
+            self.entries.put(8, count);
+        }
+        self.entries.put(16, next_sequence);
+        Ok(())
+    }
+
+    /// Whether the record at the position of `entry` is still the one it was made for.
+    fn in_journal(&self, replayer: &Replayer, entry: IndexEntry) -> JournalResult<bool> {
+        let record = match replayer.read(entry.position) {
+            Ok(record) => record,
+            // the record or its whole segment is gone
+            Err(JournalError::Corrupt { .. }) => return Ok(false),
+            Err(JournalError::Io(e)) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+            Err(e) => return Err(e),
+        };
+        Ok(record.sequence == entry.sequence
+            && order_id_of(&record.frame).is_ok_and(|id| id == Some(entry.order_id)))
+    }
+
+    fn push(&mut self, entry: IndexEntry) -> JournalResult<()> {
+        let count = self.len();
+        let offset = entry_offset(count);
+        if offset + ENTRY_LENGTH > self.entries.len() {
+            self.entries.resize(entry_offset(count * 2))?;
+        }
+        if (self.heads_used() + 1) * 2 > self.slot_count() {
+            self.rebuild_heads(self.slot_count() * 2)?;
+        }
+
+        let slot = self.probe(entry.order_id);
+        let (_, prev) = self.slot(slot);
+        if prev == 0 {
+            self.heads.put(24, self.heads_used() + 1);
+        }
+        self.entries.put(offset, entry.order_id);
+        self.entries.put(offset + 8, entry.sequence);
+        self.entries.put(offset + 16, entry.position.segment);
+        self.entries.put(offset + 24, entry.position.offset);
+        self.entries.put(offset + 32, prev);
+        self.entries.put(8, count + 1);
+        self.set_slot(slot, entry.order_id, count + 1);
+        self.heads.put(16, count + 1);
+        Ok(())
+    }
+
+    fn entry(&self, index: u64) -> IndexEntry {
+        let offset = entry_offset(index);
+        IndexEntry {
+            order_id: self.entries.get(offset),
+            sequence: self.entries.get(offset + 8),
+            position: RecordPosition {
+                segment: self.entries.get(offset + 16),
+                offset: self.entries.get(offset + 24),
+            },
+        }
+    }
+
+    fn entry_prev(&self, index: u64) -> u64 {
+        self.entries.get(entry_offset(index) + 32)
+    }
+
+    fn heads_valid(&self) -> bool {
+        let slot_count = self.slot_count();
+        self.heads.get(0) == HEADS_MAGIC
+            && self.heads.get(16) == self.len()
+            && slot_count.is_power_of_two()
+            && slot_offset(slot_count) <= self.heads.len()
+    }
+
+    fn slot_count(&self) -> u64 {
+        self.heads.get(8)
+    }
+
+    fn heads_used(&self) -> u64 {
+        self.heads.get(24)
+    }
+
+    fn slot(&self, slot: u64) -> (u64, u64) {
+        let offset = slot_offset(slot);
+        (self.heads.get(offset), self.heads.get(offset + 8))
+    }
+
+    fn set_slot(&mut self, slot: u64, order_id: u64, head: u64) {
+        let offset = slot_offset(slot);
+        self.heads.put(offset, order_id);
+        self.heads.put(offset + 8, head);
+    }
+
+    /// Slot holding `order_id` or the empty slot where it would go.
+    fn probe(&self, order_id: u64) -> u64 {
+        let mask = self.slot_count() - 1;
+        let mut slot = hash(order_id) & mask;
+        loop {
+            let (id, head) = self.slot(slot);
+            if head == 0 || id == order_id {
+                return slot;
+            }
+            slot = (slot + 1) & mask;
+        }
+    }
+
+    fn find_slot(&self, order_id: u64) -> Option<u64> {
+        let slot = self.probe(order_id);
+        (self.slot(slot).1 != 0).then_some(slot)
+    }
+
+    /// Recreates the hash table with `slot_count` slots from the entries file.
+    fn rebuild_heads(&mut self, slot_count: u64) -> JournalResult<()> {
+        self.heads.resize(slot_offset(slot_count))?;
+        self.heads.map[..].fill(0);
+        self.heads.put(0, HEADS_MAGIC);
+        self.heads.put(8, slot_count);
+        let mut used = 0;
+        for index in 0..self.len() {
+            let order_id = self.entry(index).order_id;
+            let slot = self.probe(order_id);
+            if self.slot(slot).1 == 0 {
+                used += 1;
+            }
+            self.set_slot(slot, order_id, index + 1);
+        }
+        self.heads.put(16, self.len());
+        self.heads.put(24, used);
+        Ok(())
+    }
+}
+
+fn entry_offset(index: u64) -> usize {
+    ENTRIES_HEADER_LENGTH + index as usize * ENTRY_LENGTH
+}
+
+fn slot_offset(slot: u64) -> usize {
+    HEADS_HEADER_LENGTH + slot as usize * SLOT_LENGTH
+}
+
+/// Enough slots to keep the table at most half full.
+fn slots_for(entries: u64) -> u64 {
+    (entries * 2).next_power_of_two().max(MIN_SLOTS)
+}
+
+fn hash(order_id: u64) -> u64 {
+    // fibonacci hashing, order ids are often sequential
+    order_id.wrapping_mul(0x9E37_79B9_7F4A_7C15).rotate_left(32)
+}
+
+/// order_id of an `OrderMessage` or `OrderResponse` frame.
+fn order_id_of(frame: &[u8]) -> Result<Option<u64>, DispatchError> {
+    let mut order_id = OrderIdOf(None);
+    dispatch::dispatch(frame, &mut order_id)?;
+    Ok(order_id.0)
+}
+
+struct OrderIdOf(Option<u64>);
+
+impl MessageHandler for OrderIdOf {
+    fn on_order_message(&mut self, order: OrderMessageDecoder<'_>) {
+        self.0 = Some(order.order_id());
+    }
+
+    fn on_order_response(&mut self, response: OrderResponseDecoder<'_>) {
+        self.0 = Some(response.order_id());
+    }
+}
@@ -0,0 +1,245 @@
+//! Order state machine driven by `OrderType` on the way in and `OrderStatus` on the way out.
+//!
+//! ```text
+//!   New ──> Open ──fill──> PartiallyFilled ──fill──> Filled
+//!            │ ▲               │ ▲
+//!     Update └─┘        Update └─┘
+//!            │                 │
+//!            └──Cancel──> Cancelled <──Cancel──┘
+//! ```
+//! Every `OrderMessage` is answered with `Accepted` or `Rejected`, executions are answered with
+//! `PartiallyFilled` or `Filled`. `OrderResponse.filled_qty` is always the cumulative filled
+//! quantity of the order.
+
+use std::collections::HashMap;
+
+use sales_generated::{
+    order_message_codec::OrderMessageDecoder, order_status::OrderStatus, order_type::OrderType,
+};
+
+use crate::{
+    dispatch::DispatchError,
+    model::{Item, OrderMessage, OrderResponse},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OrderState {
+    Open,
+    PartiallyFilled,
+    Filled,
+    Cancelled,
+}
+
+impl OrderState {
+    /// Filled and cancelled orders accept no further messages.
+    pub fn is_terminal(self) -> bool {
+        matches!(self, Self::Filled | Self::Cancelled)
+    }
+}
+
+/// Reason an order message or fill was refused, used as `server_note` of the rejection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Rejection {
+    UnknownOrder,
+    DuplicateOrder,
+    OrderFilled,
+    OrderCancelled,
+    UnknownOrderType,
+    /// New or Update without any quantity
+    EmptyOrder,
+    /// Update would leave less quantity than already filled
+    QuantityBelowFilled,
+    /// fill of zero or more than the open quantity
+    InvalidFillQuantity,
+}
+
+impl Rejection {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::UnknownOrder => "unknown order",
+            Self::DuplicateOrder => "duplicate order",
+            Self::OrderFilled => "order filled",
+            Self::OrderCancelled => "order cancelled",
+            Self::UnknownOrderType => "unknown order type",
+            Self::EmptyOrder => "order has no quantity",
+            Self::QuantityBelowFilled => "quantity below filled quantity",
+            Self::InvalidFillQuantity => "invalid fill quantity",
+        }
+    }
+}
+
+impl std::fmt::Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::error::Error for Rejection {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManagedOrder {
+    pub client_id: u64,
+    pub items: Vec<Item>,
+    pub state: OrderState,
+    pub filled_qty: u32,
+}
+
+impl ManagedOrder {
+    pub fn total_qty(&self) -> u32 {
+        total_qty(&self.items)
+    }
+
+    pub fn open_qty(&self) -> u32 {
+        self.total_qty() - self.filled_qty
+    }
+}
+
+fn total_qty(items: &[Item]) -> u32 {
+    items.iter().map(|item| item.quantity as u32).sum()
+}
+
+#[derive(Debug, Default)]
+pub struct OrderManager {
+    orders: HashMap<u64, ManagedOrder>,
+}
+
+impl OrderManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn order(&self, order_id: u64) -> Option<&ManagedOrder> {
+        self.orders.get(&order_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.orders.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+
+    /// Applies an order message straight from the wire, see `apply`. A frame that does not
+    /// decode fails without touching any order.
+    pub fn on_order_message(
+        &mut self,
+        order: OrderMessageDecoder<'_>,
+        timestamp: u64,
+    ) -> Result<OrderResponse, DispatchError> {
+        let order = OrderMessage::decode_checked(order)?;
+        Ok(self.apply(&order, timestamp))
+    }
+
+    /// Applies one order message and returns the `Accepted` or `Rejected` response for it,
+    /// stamped with `timestamp`.
+    pub fn apply(&mut self, order: &OrderMessage, timestamp: u64) -> OrderResponse {
+        let result = match order.order_type {
+            OrderType::New => self.create(order),
+            OrderType::Update => self.update(order),
+            OrderType::Cancel => self.cancel(order.order_id),
+            OrderType::NullVal => Err(Rejection::UnknownOrderType),
+        };
+
+        let filled_qty = self.order(order.order_id).map_or(0, |o| o.filled_qty);
+        let (status, server_note) = match result {
+            Ok(status) => (status, String::new()),
+            Err(rejection) => (OrderStatus::Rejected, rejection.to_string()),
+        };
+        OrderResponse {
+            order_id: order.order_id,
+            timestamp,
+            status,
+            filled_qty,
+            fill_price: 0,
+            server_note,
+        }
+    }
+
+    /// Records an execution of `quantity` at `price` (mantissa, exponent -2) and returns the
+    /// `PartiallyFilled` or `Filled` response for it.
+    pub fn fill(
+        &mut self,
+        order_id: u64,
+        quantity: u32,
+        price: i64,
+        timestamp: u64,
+    ) -> Result<OrderResponse, Rejection> {
+        let order = self.live_order(order_id)?;
+        if quantity == 0 || quantity > order.open_qty() {
+            return Err(Rejection::InvalidFillQuantity);
+        }
+
+        order.filled_qty += quantity;
+        let status = if order.open_qty() == 0 {
+            order.state = OrderState::Filled;
+            OrderStatus::Filled
+        } else {
+            order.state = OrderState::PartiallyFilled;
+            OrderStatus::PartiallyFilled
+        };
+        Ok(OrderResponse {
+            order_id,
+            timestamp,
+            status,
+            filled_qty: order.filled_qty,
+            fill_price: price,
+            server_note: String::new(),
+        })
+    }
+
+    fn create(&mut self, order: &OrderMessage) -> Result<OrderStatus, Rejection> {
+        if self.orders.contains_key(&order.order_id) {
+            return Err(Rejection::DuplicateOrder);
+        }
+        if total_qty(&order.items) == 0 {
+            return Err(Rejection::EmptyOrder);
+        }
+        self.orders.insert(
+            order.order_id,
+            ManagedOrder {
+                client_id: order.client_id,
+                items: order.items.clone(),
+                state: OrderState::Open,
+                filled_qty: 0,
+            },
+        );
+        Ok(OrderStatus::Accepted)
+    }
+
+    /// Replaces the items of a live order, quantity already filled stays filled.
+    fn update(&mut self, update: &OrderMessage) -> Result<OrderStatus, Rejection> {
+        let order = self.live_order(update.order_id)?;
+        let total = total_qty(&update.items);
+        if total == 0 {
+            return Err(Rejection::EmptyOrder);
+        }
+        if total < order.filled_qty {
+            return Err(Rejection::QuantityBelowFilled);
+        }
+
+        order.items = update.items.clone();
+        if total == order.filled_qty {
+            order.state = OrderState::Filled;
+            return Ok(OrderStatus::Filled);
+        }
+        Ok(OrderStatus::Accepted)
+    }
+
+    fn cancel(&mut self, order_id: u64) -> Result<OrderStatus, Rejection> {
+        self.live_order(order_id)?.state = OrderState::Cancelled;
+        Ok(OrderStatus::Accepted)
+    }
+
+    fn live_order(&mut self, order_id: u64) -> Result<&mut ManagedOrder, Rejection> {
+        let order = self
+            .orders
+            .get_mut(&order_id)
+            .ok_or(Rejection::UnknownOrder)?;
+        match order.state {
+            OrderState::Filled => Err(Rejection::OrderFilled),
+            OrderState::Cancelled => Err(Rejection::OrderCancelled),
+            OrderState::Open | OrderState::PartiallyFilled => Ok(order),
+        }
+    }
+}
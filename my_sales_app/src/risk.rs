@@ -0,0 +1,261 @@
+//! Pre-trade risk checks run on the wire decoder before an order reaches the `OrderManager`.
+//!
+//! Checks read the flyweight directly and do not allocate per order. The first failing check
+//! rejects the order, its rule name becomes the `server_note` of the `Rejected` response. The
+//! checks fail closed: an items group that does not fit the frame rejects the order.
+
+use std::collections::{HashMap, HashSet};
+
+use sales_generated::{
+    Encoder, WriteBuf, message_header_codec, order_message_codec::OrderMessageDecoder,
+    order_response_codec::OrderResponseEncoder, order_status::OrderStatus,
+};
+
+/// Limits applied to the orders of one client.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientLimits {
+    pub max_items: u16,
+    pub max_quantity: u16,
+    /// sum of quantity * |unit_price| as a mantissa with exponent -2
+    pub max_notional: i64,
+    /// `None` allows every product
+    pub allowed_products: Option<HashSet<u32>>,
+    /// order messages per second, counted in fixed one second windows
+    pub max_messages_per_second: u32,
+}
+
+impl Default for ClientLimits {
+    fn default() -> Self {
+        Self {
+            max_items: u16::MAX,
+            max_quantity: u16::MAX,
+            max_notional: i64::MAX,
+            allowed_products: None,
+            max_messages_per_second: u32::MAX,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RiskConfig {
+    /// used for clients without their own entry
+    pub default_limits: ClientLimits,
+    pub client_limits: HashMap<u64, ClientLimits>,
+}
+
+impl RiskConfig {
+    pub fn limits(&self, client_id: u64) -> &ClientLimits {
+        self.client_limits
+            .get(&client_id)
+            .unwrap_or(&self.default_limits)
+    }
+}
+
+/// One rule of the chain.
+pub trait RiskCheck {
+    /// name reported in the `server_note` of a rejection
+    fn rule(&self) -> &'static str;
+
+    /// `now` is the epoch ns receive time, returns false to reject the order
+    fn check(&mut self, order: &OrderMessageDecoder<'_>, limits: &ClientLimits, now: u64) -> bool;
+}
+
+pub struct MaxItems;
+
+impl RiskCheck for MaxItems {
+    fn rule(&self) -> &'static str {
+        "max_items"
+    }
+
+    fn check(&mut self, order: &OrderMessageDecoder<'_>, limits: &ClientLimits, _now: u64) -> bool {
+        order
+            .items_decoder_checked()
+            .is_ok_and(|items| items.count() <= limits.max_items)
+    }
+}
+
+pub struct MaxQuantity;
+
+impl RiskCheck for MaxQuantity {
+    fn rule(&self) -> &'static str {
+        "max_quantity"
+    }
+
+    fn check(&mut self, order: &OrderMessageDecoder<'_>, limits: &ClientLimits, _now: u64) -> bool {
+        let Ok(mut items) = order.items_decoder_checked() else {
+            return false;
+        };
+        loop {
+            match items.advance() {
+                Ok(Some(_)) if items.quantity() > limits.max_quantity => return false,
+                Ok(Some(_)) => {}
+                Ok(None) => return true,
+                Err(_) => return false,
+            }
+        }
+    }
+}
+
+pub struct MaxNotional;
+
+impl RiskCheck for MaxNotional {
+    fn rule(&self) -> &'static str {
+        "max_notional"
+    }
+
+    fn check(&mut self, order: &OrderMessageDecoder<'_>, limits: &ClientLimits, _now: u64) -> bool {
+        let mut notional = 0i128;
+        let Ok(mut items) = order.items_decoder_checked() else {
+            return false;
+        };
+        loop {
+            match items.advance() {
+                Ok(Some(_)) => {}
+                Ok(None) => return notional <= limits.max_notional as i128,
+                Err(_) => return false,
+            }
+            let quantity = items.quantity() as i128;
+            let mut price = items.unit_price_decoder();
+            // a negative price must not offset the other items
+            notional += quantity * price.mantissa().unsigned_abs() as i128;
+            items = price.parent().unwrap();
+        }
+    }
+}
+
+pub struct AllowedProducts;
+
+impl RiskCheck for AllowedProducts {
+    fn rule(&self) -> &'static str {
+        "allowed_products"
+    }
+
+    fn check(&mut self, order: &OrderMessageDecoder<'_>, limits: &ClientLimits, _now: u64) -> bool {
+        let Some(allowed) = &limits.allowed_products else {
+            return true;
+        };
+        let Ok(mut items) = order.items_decoder_checked() else {
+            return false;
+        };
+        loop {
+            match items.advance() {
+                Ok(Some(_)) if !allowed.contains(&items.product_id()) => return false,
+                Ok(Some(_)) => {}
+                Ok(None) => return true,
+                Err(_) => return false,
+            }
+        }
+    }
+}
+
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+
+/// Counts messages per client in fixed one second windows.
+#[derive(Default)]
+pub struct MessageRate {
+    /// client_id -> (window start second, messages in window)
+    windows: HashMap<u64, (u64, u32)>,
+}
+
+impl RiskCheck for MessageRate {
+    fn rule(&self) -> &'static str {
+        "message_rate"
+    }
+
+    fn check(&mut self, order: &OrderMessageDecoder<'_>, limits: &ClientLimits, now: u64) -> bool {
+        let second = now / NANOS_PER_SECOND;
+        let window = self.windows.entry(order.client_id()).or_insert((second, 0));
+        if window.0 != second {
+            *window = (second, 0);
+        }
+        // rejected messages count too, a client flooding us stays throttled
+        window.1 = window.1.saturating_add(1);
+        window.1 <= limits.max_messages_per_second
+    }
+}
+
+/// Order failed the named rule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RiskRejection {
+    pub rule: &'static str,
+}
+
+impl RiskRejection {
+    /// Encodes the `Rejected` response for `order_id` into `buffer`, returns the frame length.
+    pub fn encode_response(&self, order_id: u64, timestamp: u64, buffer: &mut [u8]) -> usize {
+        let mut response = OrderResponseEncoder::default()
+            .wrap(WriteBuf::new(buffer), message_header_codec::ENCODED_LENGTH);
+        response = response.header(0).parent().unwrap();
+        response.order_id(order_id);
+        response.timestamp(timestamp);
+        response.status(OrderStatus::Rejected);
+        response.filled_qty(0);
+        let mut price = response.fill_price_encoder();
+        price.mantissa(0);
+        response = price.parent().unwrap();
+        response.server_note(self.rule);
+        response.get_limit()
+    }
+}
+
+impl std::fmt::Display for RiskRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "risk check {} failed", self.rule)
+    }
+}
+
+impl std::error::Error for RiskRejection {}
+
+/// Ordered list of checks, evaluated until the first failure.
+pub struct RiskChain {
+    config: RiskConfig,
+    checks: Vec<Box<dyn RiskCheck + Send>>,
+}
+
+impl RiskChain {
+    /// Chain without any checks, add them with `with_check`.
+    pub fn new(config: RiskConfig) -> Self {
+        Self {
+            config,
+            checks: Vec::new(),
+        }
+    }
+
+    /// Rate, item count, quantity, product and notional checks in that order.
+    pub fn standard(config: RiskConfig) -> Self {
+        Self::new(config)
+            .with_check(MessageRate::default())
+            .with_check(MaxItems)
+            .with_check(MaxQuantity)
+            .with_check(AllowedProducts)
+            .with_check(MaxNotional)
+    }
+
+    pub fn with_check(mut self, check: impl RiskCheck + Send + 'static) -> Self {
+        self.checks.push(Box::new(check));
+        self
+    }
+
+    pub fn config(&self) -> &RiskConfig {
+        &self.config
+    }
+
+    pub fn config_mut(&mut self) -> &mut RiskConfig {
+        &mut self.config
+    }
+
+    /// Runs every check against `order` received at `now` (epoch ns).
+    pub fn evaluate(
+        &mut self,
+        order: &OrderMessageDecoder<'_>,
+        now: u64,
+    ) -> Result<(), RiskRejection> {
+        let limits = self.config.limits(order.client_id());
+        for check in self.checks.iter_mut() {
+            if !check.check(order, limits, now) {
+                return Err(RiskRejection { rule: check.rule() });
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,177 @@
+use my_sales_app::{
+    batch::{BatchDecoder, BatchEncoder, FramePosition},
+    dispatch::{self, DispatchError},
+    model::{Item, Message, OrderMessage, OrderResponse},
+};
+use sales_generated::{
+    SbeErr, order_flags::OrderFlags, order_message_codec::encoder::ItemsEncoder,
+    order_status::OrderStatus, order_type::OrderType,
+};
+
+fn response(order_id: u64, note: &str) -> Message {
+    Message::Response(OrderResponse {
+        order_id,
+        timestamp: 42,
+        status: OrderStatus::PartiallyFilled,
+        filled_qty: order_id as u32,
+        fill_price: 1_000,
+        server_note: note.to_string(),
+    })
+}
+
+fn order(order_id: u64, items: u32) -> Message {
+    Message::Order(OrderMessage {
+        order_id,
+        client_id: 135,
+        timestamp: 246,
+        order_type: OrderType::New,
+        order_flags: OrderFlags::default(),
+        items: (0..items)
+            .map(|i| Item {
+                product_id: i,
+                quantity: 1,
+                unit_price: 99,
+                ..Item::default()
+            })
+            .collect(),
+        customer_note: "x".repeat(items as usize),
+    })
+}
+
+fn decode_all(data: &[u8]) -> Vec<Message> {
+    BatchDecoder::new(data)
+        .map(|frame| Message::decode_checked(frame.unwrap().1).unwrap())
+        .collect()
+}
+
+#[test]
+fn owned_messages_round_trip() {
+    let messages: Vec<Message> = (0..20)
+        .map(|i| {
+            if i % 3 == 0 {
+                order(i, i as u32)
+            } else {
+                response(i, &"é".repeat(i as usize))
+            }
+        })
+        .collect();
+
+    let mut batch = BatchEncoder::new();
+    for message in &messages {
+        batch.message(message);
+    }
+    assert_eq!(messages.len(), batch.len());
+    assert_eq!(messages, decode_all(batch.as_bytes()));
+
+    let decoded: Vec<FramePosition> = BatchDecoder::new(batch.as_bytes())
+        .map(|frame| frame.unwrap().0)
+        .collect();
+    assert_eq!(batch.positions(), decoded.as_slice());
+    assert_eq!(
+        batch.as_bytes().len(),
+        batch.positions().last().unwrap().end()
+    );
+}
+
+#[test]
+fn wraps_generated_encoders_at_the_limit() {
+    let mut batch = BatchEncoder::with_capacity(64);
+    let first = batch.order_response(|mut response| {
+        response.order_id(1);
+        response.timestamp(42);
+        response.status(OrderStatus::Filled);
+        response.filled_qty(3);
+        let mut price = response.fill_price_encoder();
+        price.mantissa(1_000);
+        response = price.parent().unwrap();
+        response.server_note("first");
+        response
+    });
+    let second = batch.order_message(|mut order| {
+        order.order_id(2);
+        order.client_id(135);
+        order.timestamp(246);
+        order.order_type(OrderType::Cancel);
+        let mut items = order.items_encoder(1, ItemsEncoder::default());
+        let _r = items.advance();
+        items.product_id(7);
+        items.quantity(1);
+        let mut price = items.unit_price_encoder();
+        price.mantissa(99);
+        items = price.parent().unwrap();
+        order = items.parent().unwrap();
+        order.customer_note("second");
+        order
+    });
+    // fields left unset are zero
+    let third = batch.order_response(|mut response| {
+        response.order_id(3);
+        response.server_note("");
+        response
+    });
+
+    assert_eq!(0, first.offset);
+    assert_eq!(first.end(), second.offset);
+    assert_eq!(second.end(), third.offset);
+    assert_eq!(third.end(), batch.as_bytes().len());
+
+    let decoded = decode_all(batch.as_bytes());
+    assert_eq!(
+        Some(batch.frame(1).unwrap()),
+        batch.as_bytes().get(second.offset..second.end())
+    );
+    let Message::Response(first) = &decoded[0] else {
+        panic!("expected OrderResponse");
+    };
+    assert_eq!("first", first.server_note);
+    let Message::Order(second) = &decoded[1] else {
+        panic!("expected OrderMessage");
+    };
+    assert_eq!(OrderType::Cancel, second.order_type);
+    assert_eq!(7, second.items[0].product_id);
+    assert_eq!("second", second.customer_note);
+    let Message::Response(third) = &decoded[2] else {
+        panic!("expected OrderResponse");
+    };
+    assert_eq!(3, third.order_id);
+    assert_eq!(0, third.filled_qty);
+}
+
+#[test]
+fn clear_reuses_the_buffer() {
+    let mut batch = BatchEncoder::new();
+    batch.message(&order(1, 50));
+    batch.clear();
+    assert!(batch.is_empty());
+    assert!(batch.as_bytes().is_empty());
+    batch.message(&response(2, "ok"));
+    assert_eq!(vec![response(2, "ok")], decode_all(batch.as_bytes()));
+}
+
+#[test]
+fn stops_at_the_first_malformed_frame() {
+    let mut batch = BatchEncoder::new();
+    batch.message(&response(1, "a"));
+    batch.message(&order(2, 3));
+    let data = batch.as_bytes();
+
+    let mut decoder = BatchDecoder::new(&data[..data.len() - 1]);
+    assert!(decoder.next().unwrap().is_ok());
+    assert_eq!(
+        Some(Err(DispatchError::Malformed(SbeErr::BufferTooShort))),
+        decoder
+            .next()
+            .map(|frame| frame.map(|(position, _)| position))
+    );
+    assert!(decoder.next().is_none());
+    assert_eq!(batch.positions()[0].end(), decoder.offset());
+
+    assert_eq!(
+        Ok(batch.positions()[0].length),
+        dispatch::frame_length(data)
+    );
+    assert_eq!(
+        Err(DispatchError::Truncated),
+        dispatch::frame_length(&data[..3])
+    );
+}
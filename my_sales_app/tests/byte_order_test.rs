@@ -0,0 +1,274 @@
+//! `sales_generated_be` is generated from messages_be.xml, the same schema with
+//! `byteOrder="bigEndian"`.
+
+use my_sales_app::{
+    dispatch::{self, DispatchError},
+    model::{Discount, Item, Message, OrderMessage},
+};
+use proptest::prelude::*;
+use sales_generated::{char_array_str, order_flags::OrderFlags, order_type::OrderType};
+use sales_generated_be::{
+    Encoder, ReadBuf, Reader, WriteBuf,
+    message_header_codec::{self, MessageHeaderDecoder},
+    order_message_codec::{
+        OrderMessageDecoder, OrderMessageEncoder,
+        encoder::{DiscountsEncoder, ItemsEncoder},
+    },
+    order_response_codec::{OrderResponseDecoder, OrderResponseEncoder},
+};
+
+fn encode_order_be(order: &OrderMessage, buffer: &mut [u8]) -> usize {
+    let mut encoder = OrderMessageEncoder::default()
+        .wrap(WriteBuf::new(buffer), message_header_codec::ENCODED_LENGTH);
+    encoder = encoder.header(0).parent().unwrap();
+    encoder.order_id(order.order_id);
+    encoder.client_id(order.client_id);
+    encoder.timestamp(order.timestamp);
+    encoder.order_type(u8::from(order.order_type).into());
+    encoder.order_flags(sales_generated_be::order_flags::OrderFlags::new(
+        order.order_flags.0,
+    ));
+
+    let mut items = encoder.items_encoder(order.items.len() as u16, ItemsEncoder::default());
+    for item in &order.items {
+        let _r = items.advance();
+        items.product_id(item.product_id);
+        items.quantity(item.quantity);
+        let mut price = items.unit_price_encoder();
+        price.mantissa(item.unit_price);
+        items = price.parent().unwrap();
+        items.currency_zero_padded(item.currency.as_bytes());
+        items.sku_zero_padded(item.sku.as_bytes());
+        let mut discounts =
+            items.discounts_encoder(item.discounts.len() as u16, DiscountsEncoder::default());
+        for discount in &item.discounts {
+            let _r = discounts.advance();
+            discounts.code(discount.code);
+            let mut amount = discounts.amount_encoder();
+            amount.mantissa(discount.amount);
+            discounts = amount.parent().unwrap();
+        }
+        items = discounts.parent().unwrap();
+        items.item_note(&item.item_note);
+    }
+    encoder = items.parent().unwrap();
+    encoder.customer_note(&order.customer_note);
+    encoder.get_limit()
+}
+
+fn decode_order_be(frame: &[u8]) -> OrderMessage {
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(frame), 0);
+    let decoder = OrderMessageDecoder::default().header(header, 0);
+    let mut order = OrderMessage {
+        order_id: decoder.order_id(),
+        client_id: decoder.client_id(),
+        timestamp: decoder.timestamp(),
+        order_type: u8::from(decoder.order_type()).into(),
+        order_flags: OrderFlags::new(decoder.order_flags().0),
+        ..OrderMessage::default()
+    };
+    let mut items = decoder.items_decoder();
+    while let Ok(Some(_)) = items.advance() {
+        let mut item = Item {
+            product_id: items.product_id(),
+            quantity: items.quantity(),
+            ..Item::default()
+        };
+        let mut price = items.unit_price_decoder();
+        item.unit_price = price.mantissa();
+        items = price.parent().unwrap();
+        // char arrays are single bytes, the text helper of either crate reads them
+        item.currency = char_array_str(&items.currency()).unwrap().to_owned();
+        item.sku = char_array_str(&items.sku()).unwrap().to_owned();
+        // the generated big endian decoders need the nested parts read in order
+        let mut discounts = items.discounts_decoder().unwrap();
+        while let Ok(Some(_)) = discounts.advance() {
+            let code = discounts.code();
+            let mut amount = discounts.amount_decoder();
+            item.discounts.push(Discount {
+                code,
+                amount: amount.mantissa(),
+            });
+            discounts = amount.parent().unwrap();
+        }
+        items = discounts.parent().unwrap();
+        let (offset, len) = items.item_note_decoder();
+        item.item_note =
+            String::from_utf8(items.get_buf().get_slice_at(offset, len).to_vec()).unwrap();
+        order.items.push(item);
+    }
+    let mut decoder = items.parent().unwrap();
+    let (offset, len) = decoder.customer_note_decoder();
+    order.customer_note =
+        String::from_utf8(decoder.get_buf().get_slice_at(offset, len).to_vec()).unwrap();
+    order
+}
+
+fn order() -> OrderMessage {
+    OrderMessage {
+        order_id: 234,
+        client_id: 135,
+        timestamp: 246,
+        order_type: OrderType::New,
+        order_flags: *OrderFlags::default().set_post_only(true),
+        items: vec![
+            Item {
+                product_id: 222,
+                quantity: 2,
+                unit_price: 234,
+                currency: "EUR".to_string(),
+                sku: "SKU-222".to_string(),
+                discounts: vec![Discount {
+                    code: 7,
+                    amount: 50,
+                }],
+                item_note: "gift".to_string(),
+            },
+            Item {
+                product_id: 111,
+                quantity: 3,
+                unit_price: 123,
+                ..Item::default()
+            },
+        ],
+        customer_note: "duck is angry".to_string(),
+    }
+}
+
+#[test]
+fn big_endian_wire_layout() {
+    let mut buffer = [0u8; 256];
+    let len = encode_order_be(&order(), &mut buffer);
+    let frame = &buffer[..len];
+
+    // header: block length, template id, schema id, version
+    assert_eq!([0, 26, 0, 1, 0, 100, 0, 5], frame[..8]);
+    assert_eq!(234u64.to_be_bytes(), frame[8..16]);
+    assert_eq!(135u64.to_be_bytes(), frame[16..24]);
+    assert_eq!(246u64.to_be_bytes(), frame[24..32]);
+    assert_eq!(0, frame[32]);
+    // a single byte set reads the same in both byte orders
+    assert_eq!(0b10, frame[33]);
+    // group dimension: block length u16, numInGroup u16
+    assert_eq!([0, 33, 0, 2], frame[34..38]);
+    assert_eq!(222u32.to_be_bytes(), frame[38..42]);
+    assert_eq!(2u16.to_be_bytes(), frame[42..44]);
+    assert_eq!(234i64.to_be_bytes(), frame[44..52]);
+    // char arrays are NUL padded and have no byte order
+    assert_eq!(b"EUR", &frame[52..55]);
+    assert_eq!(b"SKU-222\0\0\0\0\0\0\0\0\0", &frame[55..71]);
+    // nested discounts group and item note of the first item
+    assert_eq!([0, 10, 0, 1], frame[71..75]);
+    assert_eq!(7u16.to_be_bytes(), frame[75..77]);
+    assert_eq!(50i64.to_be_bytes(), frame[77..85]);
+    assert_eq!(4u16.to_be_bytes(), frame[85..87]);
+    assert_eq!(b"gift", &frame[87..91]);
+    // second item has neither
+    assert_eq!([0, 10, 0, 0, 0, 0], frame[124..130]);
+    // var data length
+    assert_eq!(13u16.to_be_bytes(), frame[130..132]);
+    assert_eq!(b"duck is angry", &frame[132..]);
+
+    assert_eq!(order(), decode_order_be(frame));
+}
+
+#[test]
+fn same_length_different_bytes() {
+    let (mut le, mut be) = ([0u8; 256], [0u8; 256]);
+    let le_len = order().encode(&mut le);
+    let be_len = encode_order_be(&order(), &mut be);
+    assert_eq!(le_len, be_len);
+    assert_ne!(le[..le_len], be[..be_len]);
+}
+
+#[test]
+fn little_endian_dispatch_rejects_big_endian_frames() {
+    let mut buffer = [0u8; 256];
+    let len = encode_order_be(&order(), &mut buffer);
+    assert_eq!(
+        Err(DispatchError::WrongSchema(100u16.swap_bytes())),
+        dispatch::header(&buffer[..len]).map(|_| ())
+    );
+}
+
+#[test]
+fn response_round_trips_big_endian() {
+    let mut buffer = [0u8; 128];
+    let mut encoder = OrderResponseEncoder::default().wrap(
+        WriteBuf::new(&mut buffer),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    encoder = encoder.header(0).parent().unwrap();
+    encoder.order_id(234);
+    encoder.timestamp(42);
+    encoder.filled_qty(7);
+    let mut price = encoder.fill_price_encoder();
+    price.mantissa(-12_345);
+    encoder = price.parent().unwrap();
+    encoder.server_note("ok");
+    let len = encoder.get_limit();
+    assert_eq!(234u64.to_be_bytes(), buffer[8..16]);
+
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(&buffer[..len]), 0);
+    let decoder = OrderResponseDecoder::default().header(header, 0);
+    assert_eq!(234, decoder.order_id());
+    assert_eq!(42, decoder.timestamp());
+    assert_eq!(7, decoder.filled_qty());
+    let mut price = decoder.fill_price_decoder();
+    assert_eq!(-12_345, price.mantissa());
+    let mut decoder = price.parent().unwrap();
+    let (offset, note_len) = decoder.server_note_decoder();
+    assert_eq!(b"ok", decoder.get_buf().get_slice_at(offset, note_len));
+    assert_eq!(len, offset + note_len);
+
+    // the owned model is little endian only, a big endian frame is not mistaken for one
+    assert!(Message::decode(&buffer[..len]).is_err());
+}
+
+proptest! {
+    #[test]
+    fn both_orders_decode_to_the_same_order(
+        ids in any::<(u64, u64, u64)>(),
+        flags in any::<u8>(),
+        items in prop::collection::vec(
+            (
+                any::<(u32, u16, i64)>(),
+                ("[A-Z]{0,3}", "[ -~]{0,16}"),
+                prop::collection::vec(any::<(u16, i64)>(), 0..=3),
+                "\\PC{0,8}",
+            ),
+            0..=20,
+        ),
+        customer_note in "\\PC{0,32}",
+    ) {
+        let order = OrderMessage {
+            order_id: ids.0,
+            client_id: ids.1,
+            timestamp: ids.2,
+            order_type: OrderType::Update,
+            order_flags: OrderFlags::new(flags),
+            items: items
+                .into_iter()
+                .map(|((product_id, quantity, unit_price), (currency, sku), discounts, item_note)| Item {
+                    product_id,
+                    quantity,
+                    unit_price,
+                    currency,
+                    sku,
+                    discounts: discounts
+                        .into_iter()
+                        .map(|(code, amount)| Discount { code, amount })
+                        .collect(),
+                    item_note,
+                })
+                .collect(),
+            customer_note,
+        };
+        let (mut le, mut be) = (vec![0u8; 4096], vec![0u8; 4096]);
+        let le_len = order.encode(&mut le);
+        let be_len = encode_order_be(&order, &mut be);
+        prop_assert_eq!(le_len, be_len);
+        prop_assert_eq!(Message::Order(order.clone()), Message::decode(&le[..le_len]).unwrap());
+        prop_assert_eq!(order, decode_order_be(&be[..be_len]));
+    }
+}
@@ -0,0 +1,149 @@
+use std::{sync::mpsc, thread};
+
+use bytes::Bytes;
+use my_sales_app::model::{Discount, Item, OrderMessage, OrderResponse};
+use sales_generated::{
+    SbeErr, order_flags::OrderFlags, order_message_codec::shared::OrderMessageBytesDecoder,
+    order_response_codec::shared::OrderResponseBytesDecoder, order_status::OrderStatus,
+    order_type::OrderType,
+};
+
+fn order() -> OrderMessage {
+    OrderMessage {
+        order_id: 234,
+        client_id: 135,
+        timestamp: 246,
+        order_type: OrderType::Update,
+        order_flags: *OrderFlags::default().set_ioc(true).set_post_only(true),
+        items: (0..5)
+            .map(|i| Item {
+                product_id: 100 + i,
+                quantity: i as u16,
+                unit_price: -(i as i64),
+                currency: "CHF".to_string(),
+                sku: format!("sku {i}"),
+                discounts: (0..i as u16)
+                    .map(|code| Discount {
+                        code,
+                        amount: code as i64 * 10,
+                    })
+                    .collect(),
+                item_note: "ä".repeat(i as usize),
+            })
+            .collect(),
+        customer_note: "duck is angry".to_string(),
+    }
+}
+
+fn owned(decoder: &OrderMessageBytesDecoder) -> OrderMessage {
+    let mut items = decoder.items_decoder();
+    let mut decoded = Vec::new();
+    while items.advance().is_some() {
+        let mut discounts = items.discounts_decoder();
+        let mut item = Item {
+            product_id: items.product_id(),
+            quantity: items.quantity(),
+            unit_price: items.unit_price_decoder().mantissa(),
+            currency: items.currency().to_string(),
+            sku: items.sku().to_string(),
+            item_note: String::from_utf8(items.item_note().to_vec()).unwrap(),
+            ..Item::default()
+        };
+        while discounts.advance().is_some() {
+            item.discounts.push(Discount {
+                code: discounts.code(),
+                amount: discounts.amount_decoder().mantissa(),
+            });
+        }
+        decoded.push(item);
+    }
+    OrderMessage {
+        order_id: decoder.order_id(),
+        client_id: decoder.client_id(),
+        timestamp: decoder.timestamp(),
+        order_type: decoder.order_type(),
+        order_flags: decoder.order_flags(),
+        items: decoded,
+        customer_note: String::from_utf8(decoder.customer_note().to_vec()).unwrap(),
+    }
+}
+
+fn frame(order: &OrderMessage) -> Bytes {
+    let mut buffer = Vec::new();
+    order.encode_to(&mut buffer);
+    Bytes::from(buffer)
+}
+
+fn assert_send_static<T: Send + 'static>() {}
+
+#[test]
+fn decoders_are_send_and_static() {
+    assert_send_static::<OrderMessageBytesDecoder>();
+    assert_send_static::<OrderResponseBytesDecoder>();
+}
+
+#[test]
+fn order_crosses_threads() {
+    let decoder = OrderMessageBytesDecoder::header(frame(&order()), 0).unwrap();
+    let (tx, rx) = mpsc::channel();
+    let worker = thread::spawn(move || {
+        for decoder in rx {
+            assert_eq!(order(), owned(&decoder));
+        }
+    });
+    for _ in 0..4 {
+        tx.send(decoder.clone()).unwrap();
+    }
+    drop(tx);
+    worker.join().unwrap();
+
+    assert_eq!(frame(&order()).len() - 8, decoder.encoded_length());
+    assert_eq!(OrderMessage::from(decoder.decoder()), owned(&decoder));
+}
+
+#[test]
+fn var_data_shares_the_frame() {
+    let frame = frame(&order());
+    let decoder = OrderMessageBytesDecoder::header(frame.clone(), 0).unwrap();
+    let note = decoder.customer_note();
+    assert_eq!(&b"duck is angry"[..], note);
+    let start = frame.as_ptr() as usize;
+    assert!((start..start + frame.len()).contains(&(note.as_ptr() as usize)));
+}
+
+#[test]
+fn response_decoder() {
+    let response = OrderResponse {
+        order_id: 234,
+        timestamp: 42,
+        status: OrderStatus::Filled,
+        filled_qty: 7,
+        fill_price: -12_345,
+        server_note: "done".to_string(),
+    };
+    let mut buffer = Vec::new();
+    response.encode_to(&mut buffer);
+
+    let decoder = OrderResponseBytesDecoder::header(Bytes::from(buffer), 0).unwrap();
+    let decoder = thread::spawn(move || decoder).join().unwrap();
+    assert_eq!(234, decoder.order_id());
+    assert_eq!(42, decoder.timestamp());
+    assert_eq!(OrderStatus::Filled, decoder.status());
+    assert_eq!(7, decoder.filled_qty());
+    assert_eq!(-12_345, decoder.fill_price_decoder().mantissa());
+    assert_eq!(&b"done"[..], decoder.server_note());
+    assert_eq!(response, OrderResponse::from(decoder.decoder()));
+}
+
+#[test]
+fn malformed_frames_fail_at_wrap() {
+    let frame = frame(&order());
+    assert_eq!(
+        SbeErr::BufferTooShort,
+        OrderMessageBytesDecoder::header(frame.slice(..frame.len() - 1), 0).unwrap_err()
+    );
+    assert_eq!(
+        SbeErr::WrongTemplateId,
+        OrderResponseBytesDecoder::header(frame, 0).unwrap_err()
+    );
+}
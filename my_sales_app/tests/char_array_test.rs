@@ -0,0 +1,157 @@
+use bytes::Bytes;
+use my_sales_app::{
+    dispatch::DispatchError,
+    model::{Item, Message, OrderMessage},
+};
+use proptest::prelude::*;
+use sales_generated::{
+    ReadBuf, SbeErr, WriteBuf, char_array, char_array_str,
+    message_header_codec::{self, MessageHeaderDecoder},
+    order_message_codec::{
+        OrderMessageDecoder, OrderMessageEncoder, encoder::ItemsEncoder,
+        shared::OrderMessageBytesDecoder,
+    },
+};
+
+fn order() -> OrderMessage {
+    OrderMessage {
+        order_id: 234,
+        items: vec![Item {
+            product_id: 1,
+            currency: "EUR".to_string(),
+            sku: "0123456789abcdef".to_string(),
+            ..Item::default()
+        }],
+        ..OrderMessage::default()
+    }
+}
+
+fn frame(order: &OrderMessage) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    order.encode_to(&mut buffer);
+    buffer
+}
+
+// header, block, items dimension, then the entry
+const CURRENCY_OFFSET: usize = 8 + 26 + 4 + 14;
+
+#[test]
+fn char_array_pads_with_nul() {
+    assert_eq!(Ok(*b"EUR"), char_array::<3>("EUR"));
+    assert_eq!(Ok(*b"US\0"), char_array::<3>("US"));
+    assert_eq!(Ok([0; 3]), char_array::<3>(""));
+    assert_eq!(Err(SbeErr::CharArrayTooLong), char_array::<3>("EURO"));
+    assert_eq!(Err(SbeErr::NotAscii), char_array::<3>("€"));
+    // would read back as "A" and "A"
+    assert_eq!(Err(SbeErr::NotAscii), char_array::<3>("A\0B"));
+    assert_eq!(Err(SbeErr::NotAscii), char_array::<3>("A\0"));
+    assert_eq!(Err(SbeErr::NotAscii), char_array::<3>("A\tB"));
+    assert_eq!(Err(SbeErr::NotAscii), char_array::<3>("\x7f"));
+    assert_eq!(Err(SbeErr::NotAscii), char_array_str(b"A\nB"));
+}
+
+#[test]
+fn char_array_str_stops_at_the_first_nul() {
+    assert_eq!(Ok("EUR"), char_array_str(b"EUR"));
+    assert_eq!(Ok("US"), char_array_str(b"US\0"));
+    assert_eq!(Ok("A"), char_array_str(b"A\0B"));
+    assert_eq!(Ok(""), char_array_str(b"\0\0\0"));
+    assert_eq!(Err(SbeErr::NotAscii), char_array_str(b"E\xffR"));
+    // bytes after the NUL are padding and not checked
+    assert_eq!(Ok("E"), char_array_str(b"E\0\xff"));
+}
+
+#[test]
+fn encoder_rejects_values_that_do_not_fit() {
+    let mut buffer = [0u8; 128];
+    let order = OrderMessageEncoder::default().wrap(
+        WriteBuf::new(&mut buffer),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    let mut items = order
+        .header(0)
+        .parent()
+        .unwrap()
+        .items_encoder(1, ItemsEncoder::default());
+    let _r = items.advance();
+    assert_eq!(Ok(()), items.currency_str("GBP"));
+    assert_eq!(Err(SbeErr::CharArrayTooLong), items.currency_str("GBPS"));
+    assert_eq!(Err(SbeErr::NotAscii), items.currency_str("£"));
+    assert_eq!(
+        Err(SbeErr::CharArrayTooLong),
+        items.sku_str("0123456789abcdefg")
+    );
+    let _order = items.parent().unwrap();
+    // a rejected value leaves the field as it was
+    assert_eq!(b"GBP", &buffer[CURRENCY_OFFSET..CURRENCY_OFFSET + 3]);
+}
+
+#[test]
+#[should_panic(expected = "currency")]
+fn owned_encode_panics_on_a_long_currency() {
+    let mut order = order();
+    order.items[0].currency = "EURO".to_string();
+    frame(&order);
+}
+
+#[test]
+fn decoders_read_text() {
+    let frame = frame(&order());
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(&frame), 0);
+    let mut items = OrderMessageDecoder::default()
+        .header(header, 0)
+        .items_decoder();
+    items.advance().unwrap();
+    assert_eq!(*b"EUR", items.currency());
+    assert_eq!(Ok("EUR"), items.currency_str());
+    assert_eq!(Ok("0123456789abcdef"), items.sku_str());
+
+    let decoder = OrderMessageBytesDecoder::header(Bytes::from(frame), 0).unwrap();
+    let mut items = decoder.items_decoder();
+    items.advance();
+    assert_eq!("EUR", items.currency());
+    assert_eq!("0123456789abcdef", items.sku());
+}
+
+#[test]
+fn checked_decoders_reject_non_ascii() {
+    let mut frame = frame(&order());
+    frame[CURRENCY_OFFSET + 1] = 0xc3;
+    assert_eq!(
+        Err(DispatchError::Malformed(SbeErr::NotAscii)),
+        Message::decode_checked(&frame)
+    );
+    assert_eq!(
+        Err(SbeErr::NotAscii),
+        OrderMessageBytesDecoder::header(Bytes::copy_from_slice(&frame), 0).map(|_| ())
+    );
+    // the unchecked decode does not fail, it replaces the byte
+    let Ok(Message::Order(order)) = Message::decode(&frame) else {
+        panic!("expected OrderMessage");
+    };
+    assert_eq!("E\u{fffd}R", order.items[0].currency);
+}
+
+#[test]
+fn version_5_entries_need_the_char_arrays() {
+    let mut frame = frame(&order());
+    // items dimension claims the version 4 block length
+    frame[8 + 26] = 14;
+    assert_eq!(
+        Err(DispatchError::Malformed(SbeErr::BlockLengthTooShort)),
+        Message::decode_checked(&frame)
+    );
+}
+
+proptest! {
+    #[test]
+    fn char_arrays_round_trip_or_fail(value in "\\PC{0,20}|[\\x00-\\x7f]{0,20}") {
+        match char_array::<16>(&value) {
+            Ok(array) => prop_assert_eq!(Ok(value.as_str()), char_array_str(&array)),
+            Err(err) => prop_assert!(
+                value.len() > 16 || value.bytes().any(|b| !(b' '..=b'~').contains(&b)),
+                "{:?} failed with {:?}", value, err
+            ),
+        }
+    }
+}
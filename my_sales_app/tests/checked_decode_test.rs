@@ -0,0 +1,192 @@
+use std::{fs, path::Path};
+
+use my_sales_app::{
+    dispatch::DispatchError,
+    model::{Item, Message, OrderMessage},
+};
+use proptest::prelude::*;
+use sales_generated::{
+    ReadBuf, SbeErr,
+    message_header_codec::MessageHeaderDecoder,
+    order_flags::OrderFlags,
+    order_message_codec::{OrderMessageBlockDecoder, OrderMessageDecoder},
+    order_type::OrderType,
+};
+
+fn order_frame() -> Vec<u8> {
+    let order = OrderMessage {
+        order_id: 234,
+        client_id: 135,
+        timestamp: 246,
+        order_type: OrderType::New,
+        order_flags: OrderFlags::default(),
+        items: vec![
+            Item {
+                product_id: 222,
+                quantity: 2,
+                unit_price: 234,
+                ..Item::default()
+            },
+            Item {
+                product_id: 111,
+                quantity: 3,
+                unit_price: 123,
+                ..Item::default()
+            },
+        ],
+        customer_note: "duck is angry".to_string(),
+    };
+    let mut buffer = [0u8; 256];
+    let len = order.encode(&mut buffer);
+    buffer[..len].to_vec()
+}
+
+/// Same property as the fuzz targets: no panic, and accepted frames survive a round trip.
+fn round_trip(frame: &[u8]) -> Result<(), DispatchError> {
+    let message = Message::decode_checked(frame)?;
+    let mut buffer = vec![0u8; 128 * 1024];
+    let len = message.encode(&mut buffer);
+    assert_eq!(
+        Ok(&message),
+        Message::decode_checked(&buffer[..len]).as_ref()
+    );
+    Ok(())
+}
+
+#[test]
+fn checked_matches_unchecked_on_valid_frames() {
+    let frame = order_frame();
+    assert_eq!(Message::decode(&frame), Message::decode_checked(&frame));
+}
+
+#[test]
+fn rejects_malformed_frames() {
+    let frame = order_frame();
+    let items_offset = 8 + 26;
+    let note_offset = items_offset + 4 + 2 * (33 + 4 + 2);
+
+    let mut too_many_items = frame.clone();
+    too_many_items[items_offset + 2] = 200;
+    assert_eq!(
+        Err(DispatchError::Malformed(SbeErr::BufferTooShort)),
+        round_trip(&too_many_items)
+    );
+
+    let mut short_group_block = frame.clone();
+    short_group_block[items_offset] = 13;
+    assert_eq!(
+        Err(DispatchError::Malformed(SbeErr::BlockLengthTooShort)),
+        round_trip(&short_group_block)
+    );
+
+    let mut short_block = frame.clone();
+    short_block[0] = 25;
+    assert_eq!(
+        Err(DispatchError::Malformed(SbeErr::BlockLengthTooShort)),
+        round_trip(&short_block)
+    );
+
+    // version 1 had no order_flags, its block is one byte shorter
+    let mut short_v1_block = frame.clone();
+    short_v1_block[0] = 24;
+    short_v1_block[6] = 1;
+    assert_eq!(
+        Err(DispatchError::Malformed(SbeErr::BlockLengthTooShort)),
+        round_trip(&short_v1_block)
+    );
+
+    let mut bad_utf8 = frame.clone();
+    bad_utf8[note_offset + 2] = 0xff;
+    assert_eq!(Err(DispatchError::InvalidUtf8), round_trip(&bad_utf8));
+
+    assert_eq!(
+        Err(DispatchError::Malformed(SbeErr::BufferTooShort)),
+        round_trip(&frame[..frame.len() - 1])
+    );
+    assert_eq!(Err(DispatchError::Truncated), round_trip(&frame[..7]));
+}
+
+#[test]
+fn block_decoder_matches_flyweight() {
+    let frame = order_frame();
+    let header = || MessageHeaderDecoder::default().wrap(ReadBuf::new(&frame), 0);
+    let block = OrderMessageBlockDecoder::header(header(), 0).unwrap();
+    let order = OrderMessageDecoder::default().header(header(), 0);
+    assert_eq!(order.order_id(), block.order_id());
+    assert_eq!(order.client_id(), block.client_id());
+    assert_eq!(order.timestamp(), block.timestamp());
+    assert_eq!(order.order_type(), block.order_type());
+    assert_eq!(order.order_flags(), block.order_flags());
+    assert_eq!(
+        OrderMessage::from(order),
+        OrderMessage::from(block.decoder())
+    );
+
+    let header = |frame| MessageHeaderDecoder::default().wrap(ReadBuf::new(frame), 0);
+    let mut short_block = frame.clone();
+    short_block[0] = 25;
+    assert_eq!(
+        Err(SbeErr::BlockLengthTooShort),
+        OrderMessageBlockDecoder::header(header(&short_block), 0).map(|_| ())
+    );
+    assert_eq!(
+        Err(SbeErr::BufferTooShort),
+        OrderMessageBlockDecoder::header(header(&frame[..8 + 25]), 0).map(|_| ())
+    );
+}
+
+#[test]
+fn block_decoder_reads_version_1_frames() {
+    let hex = fs::read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden/order_message_two_items_v1.hex"),
+    )
+    .unwrap();
+    let frame: Vec<u8> = (0..hex.trim().len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect();
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(&frame), 0);
+    let block = OrderMessageBlockDecoder::header(header, 0).unwrap();
+    assert_eq!(234, block.order_id());
+    // the byte after a version 1 block is the items group, not flags
+    assert_eq!(OrderFlags::default(), block.order_flags());
+    assert_eq!(2, OrderMessage::from(block.decoder()).items.len());
+}
+
+#[test]
+fn fuzz_seed_corpus_round_trips() {
+    let corpus = Path::new(env!("CARGO_MANIFEST_DIR")).join("../fuzz/corpus");
+    let mut seeds = 0;
+    for target in fs::read_dir(corpus).unwrap() {
+        for seed in fs::read_dir(target.unwrap().path()).unwrap() {
+            let frame = fs::read(seed.unwrap().path()).unwrap();
+            round_trip(&frame).unwrap();
+            seeds += 1;
+        }
+    }
+    assert!(seeds > 0);
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_bytes_never_panic(frame in prop::collection::vec(any::<u8>(), 0..256)) {
+        let _ = round_trip(&frame);
+    }
+
+    #[test]
+    fn mutated_frames_never_panic(
+        flips in prop::collection::vec((any::<prop::sample::Index>(), any::<u8>()), 1..8),
+        cut in any::<prop::sample::Index>(),
+    ) {
+        let mut frame = order_frame();
+        for (index, value) in flips {
+            let i = index.index(frame.len());
+            frame[i] = value;
+        }
+        // keep the header intact often enough to reach the body
+        frame[2] = 1;
+        frame[4] = 100;
+        let len = cut.index(frame.len() + 1);
+        let _ = round_trip(&frame[..len]);
+    }
+}
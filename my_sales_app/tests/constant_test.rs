@@ -0,0 +1,88 @@
+use std::{fs, path::Path};
+
+use bytes::Bytes;
+use my_sales_app::model::{Item, OrderMessage, OrderResponse};
+use sales_generated::{
+    ReadBuf,
+    direction::Direction,
+    message_header_codec::MessageHeaderDecoder,
+    order_message_codec::{self, OrderMessageDecoder, shared::OrderMessageBytesDecoder},
+    order_response_codec::{self, OrderResponseDecoder, shared::OrderResponseBytesDecoder},
+};
+
+fn order_frame() -> Vec<u8> {
+    let order = OrderMessage {
+        order_id: 234,
+        items: vec![Item::default()],
+        ..OrderMessage::default()
+    };
+    let mut buffer = Vec::new();
+    order.encode_to(&mut buffer);
+    buffer
+}
+
+fn response_frame() -> Vec<u8> {
+    let response = OrderResponse {
+        order_id: 234,
+        ..OrderResponse::default()
+    };
+    let mut buffer = Vec::new();
+    response.encode_to(&mut buffer);
+    buffer
+}
+
+#[test]
+fn constants_take_no_space() {
+    // the blocks end with order_flags and fill_price as before
+    assert_eq!(26, order_message_codec::SBE_BLOCK_LENGTH);
+    assert_eq!(29, order_response_codec::SBE_BLOCK_LENGTH);
+    assert_eq!(
+        OrderResponse::default().compute_length(),
+        response_frame().len()
+    );
+}
+
+#[test]
+fn decoders_return_constants() {
+    let frame = order_frame();
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(&frame), 0);
+    let order = OrderMessageDecoder::default().header(header, 0);
+    assert_eq!(b"XSAL", order.venue());
+    assert_eq!("XSAL", order.venue_str());
+    assert_eq!(Direction::Inbound, order.direction());
+
+    let frame = response_frame();
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(&frame), 0);
+    let response = OrderResponseDecoder::default().header(header, 0);
+    assert_eq!("XSAL", response.venue_str());
+    assert_eq!(Direction::Outbound, response.direction());
+}
+
+#[test]
+fn bytes_decoders_return_constants() {
+    let order = OrderMessageBytesDecoder::header(Bytes::from(order_frame()), 0).unwrap();
+    assert_eq!("XSAL", order.venue());
+    assert_eq!(Direction::Inbound, order.direction());
+
+    let response = OrderResponseBytesDecoder::header(Bytes::from(response_frame()), 0).unwrap();
+    assert_eq!("XSAL", response.venue());
+    assert_eq!(Direction::Outbound, response.direction());
+}
+
+/// Constants are not versioned, frames written before they were declared read them too.
+#[test]
+fn older_frames_read_constants() {
+    let path =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden/order_message_two_items_v1.hex");
+    let hex = fs::read_to_string(path).unwrap();
+    let hex = hex.trim();
+    let frame: Vec<u8> = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect();
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(&frame), 0);
+    assert_eq!(1, header.version());
+    let order = OrderMessageDecoder::default().header(header, 0);
+    assert_eq!("XSAL", order.venue_str());
+    assert_eq!(Direction::Inbound, order.direction());
+}
@@ -1,3 +1,6 @@
+// the original example, keeps its `get_limit() as usize`
+#![allow(clippy::unnecessary_cast)]
+
 use sales_generated::{
     Encoder, ReadBuf, WriteBuf,
     message_header_codec::{self, MessageHeaderDecoder},
@@ -76,6 +79,12 @@ fn simple() {
         order.timestamp(),
         order.order_type()
     );
+    // constants come from the schema, they take no space in the buffer
+    println!(
+        "venue = {}, direction = {}",
+        order.venue_str(),
+        order.direction()
+    );
 
     let mut items = order.items_decoder();
 
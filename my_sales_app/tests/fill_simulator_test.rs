@@ -0,0 +1,161 @@
+use my_sales_app::{
+    fill_simulator::{FillModel, FillModelError, FillSimulator},
+    model::{Item, Message, OrderMessage},
+    order_manager::OrderManager,
+};
+use sales_generated::{order_flags::OrderFlags, order_status::OrderStatus, order_type::OrderType};
+
+fn order(order_id: u64, quantities: &[u16]) -> OrderMessage {
+    OrderMessage {
+        order_id,
+        client_id: 135,
+        timestamp: 0,
+        order_type: OrderType::New,
+        order_flags: OrderFlags::default(),
+        items: quantities
+            .iter()
+            .map(|&quantity| Item {
+                product_id: 222,
+                quantity,
+                unit_price: 1_000,
+                ..Item::default()
+            })
+            .collect(),
+        customer_note: String::new(),
+    }
+}
+
+fn model(seed: u64) -> FillModel {
+    FillModel {
+        seed,
+        min_fills: 2,
+        max_fills: 6,
+        max_slippage: 5,
+        fill_interval: 1_000,
+        fill_interval_jitter: 500,
+    }
+}
+
+fn frames(seed: u64) -> Vec<Vec<u8>> {
+    let mut simulator = FillSimulator::new(model(seed)).unwrap();
+    let mut frames = Vec::new();
+    for order_id in 1..=20 {
+        simulator.simulate_encoded(&order(order_id, &[7, 13]), 0, |frame| {
+            frames.push(frame.to_vec())
+        });
+    }
+    frames
+}
+
+#[test]
+fn same_seed_same_frames() {
+    assert_eq!(frames(42), frames(42));
+    assert_ne!(frames(42), frames(43));
+}
+
+#[test]
+fn fills_are_incremental_and_end_filled() {
+    let mut simulator = FillSimulator::new(model(7)).unwrap();
+    for order_id in 1..=50 {
+        let order = order(order_id, &[7, 13]);
+        let fills = simulator.simulate(&order, 1_000_000);
+        assert!((2..=6).contains(&fills.len()), "{} fills", fills.len());
+
+        let (last, partial) = fills.split_last().unwrap();
+        assert_eq!(OrderStatus::Filled, last.status);
+        assert_eq!(20, last.filled_qty);
+        assert!(
+            partial
+                .iter()
+                .all(|f| f.status == OrderStatus::PartiallyFilled)
+        );
+
+        for pair in fills.windows(2) {
+            assert!(pair[0].filled_qty < pair[1].filled_qty);
+            assert!(pair[1].timestamp - pair[0].timestamp >= 1_000);
+            assert!(pair[1].timestamp - pair[0].timestamp <= 1_500);
+        }
+        for fill in &fills {
+            assert_eq!(order_id, fill.order_id);
+            assert!((995..=1_005).contains(&fill.fill_price));
+        }
+    }
+}
+
+#[test]
+fn small_orders_get_fewer_fills() {
+    let mut simulator = FillSimulator::new(model(1)).unwrap();
+    let fills = simulator.simulate(&order(1, &[1]), 0);
+    assert_eq!(1, fills.len());
+    assert_eq!(OrderStatus::Filled, fills[0].status);
+    assert!(simulator.simulate(&order(2, &[]), 0).is_empty());
+}
+
+#[test]
+fn extreme_slippage_saturates() {
+    for seed in 0..50 {
+        let mut simulator = FillSimulator::new(FillModel {
+            max_slippage: i64::MAX,
+            ..model(seed)
+        })
+        .unwrap();
+        for fill in simulator.simulate(&order(1, &[7, 13]), 0) {
+            assert!(fill.fill_price >= 1_000 - i64::MAX, "{}", fill.fill_price);
+        }
+    }
+}
+
+#[test]
+fn extreme_intervals_saturate() {
+    assert_eq!(
+        Some(FillModelError::IntervalOverflow),
+        FillSimulator::new(FillModel {
+            fill_interval: u64::MAX,
+            fill_interval_jitter: 1,
+            ..model(0)
+        })
+        .err()
+    );
+
+    for seed in 0..50 {
+        let mut simulator = FillSimulator::new(FillModel {
+            fill_interval: u64::MAX / 2,
+            fill_interval_jitter: u64::MAX / 2,
+            ..model(seed)
+        })
+        .unwrap();
+        let fills = simulator.simulate(&order(1, &[7, 13]), u64::MAX - 1);
+        assert!(fills.iter().all(|fill| fill.timestamp == u64::MAX));
+        assert_eq!(OrderStatus::Filled, fills.last().unwrap().status);
+    }
+}
+
+#[test]
+fn order_manager_accepts_simulated_fills() {
+    let mut simulator = FillSimulator::new(model(3)).unwrap();
+    let mut manager = OrderManager::new();
+
+    for order_id in 1..=10 {
+        let order = order(order_id, &[4, 4, 4]);
+        let accepted = manager.apply(&order, 0);
+        assert_eq!(OrderStatus::Accepted, accepted.status);
+
+        let mut filled = 0;
+        simulator.simulate_encoded(&order, 0, |frame| {
+            let Message::Response(fill) = Message::decode(frame).unwrap() else {
+                panic!("expected OrderResponse");
+            };
+            let applied = manager
+                .fill(
+                    order_id,
+                    fill.filled_qty - filled,
+                    fill.fill_price,
+                    fill.timestamp,
+                )
+                .unwrap();
+            assert_eq!(fill, applied);
+            filled = fill.filled_qty;
+        });
+        assert_eq!(0, manager.order(order_id).unwrap().open_qty());
+    }
+}
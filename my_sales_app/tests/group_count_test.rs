@@ -0,0 +1,255 @@
+use bytes::Bytes;
+use my_sales_app::{
+    dispatch,
+    model::{Discount, Item, Message, OrderMessage},
+};
+use sales_generated::{
+    ReadBuf, SbeErr, WriteBuf,
+    group_size_encoding_codec::{self, NUM_IN_GROUP_MAX},
+    message_header_codec::{self, MessageHeaderDecoder},
+    order_flags::OrderFlags,
+    order_message_codec::{
+        OrderMessageDecoder, OrderMessageEncoder,
+        encoder::{DiscountsEncoder, ItemsEncoder},
+        shared::OrderMessageBytesDecoder,
+    },
+};
+use sales_generated_v3 as v3;
+
+fn bulk_order(items: usize) -> OrderMessage {
+    OrderMessage {
+        order_id: 234,
+        items: (0..items)
+            .map(|i| Item {
+                product_id: i as u32,
+                quantity: 1,
+                unit_price: 99,
+                ..Item::default()
+            })
+            .collect(),
+        customer_note: "bulk".to_string(),
+        ..OrderMessage::default()
+    }
+}
+
+fn frame(order: &OrderMessage) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    order.encode_to(&mut buffer);
+    buffer
+}
+
+/// Item count and first product id as `sales_generated_v3`, generated from version 3 of
+/// messages.xml, reads them: a uint8 numInGroup and a three byte dimension, whatever version the
+/// header announces.
+fn read_with_version_3(frame: &[u8]) -> (u8, Option<u32>) {
+    let header =
+        v3::message_header_codec::MessageHeaderDecoder::default().wrap(v3::ReadBuf::new(frame), 0);
+    let mut items = v3::order_message_codec::OrderMessageDecoder::default()
+        .header(header, 0)
+        .items_decoder();
+    let first = match items.advance() {
+        Ok(Some(_)) => Some(items.product_id()),
+        _ => None,
+    };
+    (items.count(), first)
+}
+
+/// `order` as a sender with the encoder generated from version 3 of messages.xml writes it,
+/// currency and sku do not exist yet.
+fn write_with_version_3(order: &OrderMessage) -> Vec<u8> {
+    use v3::{
+        Encoder,
+        order_message_codec::{
+            OrderMessageEncoder,
+            encoder::{DiscountsEncoder, ItemsEncoder},
+        },
+    };
+
+    let mut buffer = vec![0u8; 16 * 1024];
+    let mut encoder = OrderMessageEncoder::default().wrap(
+        v3::WriteBuf::new(&mut buffer),
+        v3::message_header_codec::ENCODED_LENGTH,
+    );
+    encoder = encoder.header(0).parent().unwrap();
+    encoder.order_id(order.order_id);
+    encoder.client_id(order.client_id);
+    encoder.timestamp(order.timestamp);
+    encoder.order_type(v3::order_type::OrderType::from(u8::from(order.order_type)));
+    encoder.order_flags(v3::order_flags::OrderFlags::new(order.order_flags.0));
+    let mut items = encoder.items_encoder(order.items.len() as u8, ItemsEncoder::default());
+    for item in &order.items {
+        items.advance().unwrap();
+        items.product_id(item.product_id);
+        items.quantity(item.quantity);
+        let mut price = items.unit_price_encoder();
+        price.mantissa(item.unit_price);
+        items = price.parent().unwrap();
+        let mut discounts =
+            items.discounts_encoder(item.discounts.len() as u8, DiscountsEncoder::default());
+        for discount in &item.discounts {
+            discounts.advance().unwrap();
+            discounts.code(discount.code);
+            let mut amount = discounts.amount_encoder();
+            amount.mantissa(discount.amount);
+            discounts = amount.parent().unwrap();
+        }
+        items = discounts.parent().unwrap();
+        items.item_note(&item.item_note);
+    }
+    encoder = items.parent().unwrap();
+    encoder.customer_note(&order.customer_note);
+    let len = encoder.get_limit();
+    buffer.truncate(len);
+    buffer
+}
+
+#[test]
+fn bulk_order_round_trips() {
+    let order = bulk_order(5_000);
+    let frame = frame(&order);
+    assert_eq!(order.compute_length(), frame.len());
+    assert_eq!(Ok(frame.len()), dispatch::frame_length(&frame));
+
+    let message = Message::Order(order);
+    assert_eq!(Ok(&message), Message::decode(&frame).as_ref());
+    assert_eq!(Ok(&message), Message::decode_checked(&frame).as_ref());
+
+    let decoder = OrderMessageBytesDecoder::header(Bytes::from(frame), 0).unwrap();
+    let mut items = decoder.items_decoder();
+    assert_eq!(5_000, items.count());
+    let mut last = None;
+    while items.advance().is_some() {
+        last = Some(items.product_id());
+    }
+    assert_eq!(Some(4_999), last);
+    assert_eq!(&b"bulk"[..], decoder.customer_note());
+}
+
+#[test]
+fn count_at_the_dimension_limit() {
+    let order = bulk_order(NUM_IN_GROUP_MAX as usize);
+    let frame = frame(&order);
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(&frame), 0);
+    let decoder = OrderMessageDecoder::default()
+        .header_checked(header, 0)
+        .unwrap();
+    let mut items = decoder.items_decoder_checked().unwrap();
+    assert_eq!(NUM_IN_GROUP_MAX, items.count());
+    items.skip_all().unwrap();
+    assert_eq!(Ok(Message::Order(order)), Message::decode_checked(&frame));
+}
+
+#[test]
+fn counts_past_num_in_group_are_rejected() {
+    let too_many = NUM_IN_GROUP_MAX as usize + 1;
+    assert_eq!(Ok(0), group_size_encoding_codec::num_in_group(0));
+    assert_eq!(
+        Ok(NUM_IN_GROUP_MAX),
+        group_size_encoding_codec::num_in_group(too_many - 1)
+    );
+    assert_eq!(
+        Err(SbeErr::CountOutOfRange),
+        group_size_encoding_codec::num_in_group(too_many)
+    );
+    assert_eq!(
+        Err(SbeErr::CountOutOfRange),
+        group_size_encoding_codec::num_in_group(usize::MAX)
+    );
+
+    let mut buffer = [0u8; 256];
+    let order = OrderMessageEncoder::default().wrap(
+        WriteBuf::new(&mut buffer),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    let order = order.header(0).parent().unwrap();
+    assert_eq!(
+        SbeErr::CountOutOfRange,
+        order
+            .items_encoder_checked(too_many, ItemsEncoder::default())
+            .unwrap_err()
+    );
+
+    let order = OrderMessageEncoder::default().wrap(
+        WriteBuf::new(&mut buffer),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    let mut items = order
+        .items_encoder_checked(1, ItemsEncoder::default())
+        .unwrap();
+    let _r = items.advance();
+    assert_eq!(
+        SbeErr::CountOutOfRange,
+        items
+            .discounts_encoder_checked(too_many, DiscountsEncoder::default())
+            .unwrap_err()
+    );
+}
+
+#[test]
+#[should_panic(expected = "too many discounts")]
+fn owned_encode_panics_on_counts_past_num_in_group() {
+    let order = OrderMessage {
+        items: vec![Item {
+            discounts: vec![Discount::default(); NUM_IN_GROUP_MAX as usize + 1],
+            ..Item::default()
+        }],
+        ..OrderMessage::default()
+    };
+    order.encode_to(&mut Vec::new());
+}
+
+/// Readers generated before version 4 do not fail on a version 4 frame, they read the low byte
+/// of the count and every entry one byte early. Only the header version tells them apart, so
+/// senders move to version 4 once every reader is upgraded.
+#[test]
+fn version_3_reader_misreads_version_4_frames() {
+    let mut order = bulk_order(2);
+    order.items[0].product_id = 1_000;
+    let v4 = frame(&order);
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(&v4), 0);
+    assert!(header.version() >= 4);
+    // the high byte of the count becomes the low byte of the product id
+    assert_eq!((2, Some(1_000 << 8)), read_with_version_3(&v4));
+
+    // 256 items read as none at all
+    assert_eq!((0, None), read_with_version_3(&frame(&bulk_order(256))));
+}
+
+/// The other way round works, `sales_generated` reads the three byte dimension of version 3
+/// frames.
+#[test]
+fn version_3_frames_decode() {
+    let hex = include_str!("golden/order_message_two_items_v3.hex").trim();
+    let golden: Vec<u8> = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect();
+    let Ok(Message::Order(order)) = Message::decode_checked(&golden) else {
+        panic!("expected OrderMessage");
+    };
+    assert_eq!(golden, write_with_version_3(&order));
+    assert_eq!((2, Some(222)), read_with_version_3(&golden));
+
+    let mut order = OrderMessage {
+        order_flags: *OrderFlags::default().set_expedited(true),
+        customer_note: "v3".to_string(),
+        ..bulk_order(255)
+    };
+    order.items[7].discounts = vec![Discount {
+        code: 3,
+        amount: -40,
+    }];
+    order.items[7].item_note = "seven".to_string();
+    let v3 = write_with_version_3(&order);
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(&v3), 0);
+    assert_eq!(3, header.version());
+    assert_eq!(
+        Ok(Message::Order(order.clone())),
+        Message::decode_checked(&v3)
+    );
+    assert_eq!(Ok(Message::Order(order.clone())), Message::decode(&v3));
+
+    let decoder = OrderMessageBytesDecoder::header(Bytes::from(v3), 0).unwrap();
+    assert_eq!(255, decoder.items_decoder().count());
+    assert_eq!(&b"v3"[..], decoder.customer_note());
+}
@@ -0,0 +1,589 @@
+//! The interpreter reads messages.xml at runtime, it has to decode what the generated code of
+//! the same schema encodes.
+
+use my_sales_app::{
+    dispatch::DispatchError,
+    interpreter::decode,
+    model::{Discount, Item, OrderMessage},
+};
+use proptest::prelude::*;
+use sales_generated::{
+    ReadBuf, Reader, SbeErr, char_array_str,
+    ir::{self, Bump, ByteOrder, FieldValue, Presence, Schema, SchemaError, Type, Value},
+    message_header_codec::MessageHeaderDecoder,
+    order_flags::OrderFlags,
+    order_message_codec::OrderMessageDecoder,
+    order_type::OrderType,
+};
+
+const MESSAGES_XML: &str = include_str!("../../sales_generated/messages.xml");
+const MESSAGES_BE_XML: &str = include_str!("../../messages_be.xml");
+
+fn golden(name: &str) -> Vec<u8> {
+    let path = format!("{}/tests/golden/{name}.hex", env!("CARGO_MANIFEST_DIR"));
+    let hex = std::fs::read_to_string(path).unwrap();
+    let hex = hex.trim();
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// frames with the group layout of version 4 and later
+fn goldens() -> Vec<Vec<u8>> {
+    [
+        "order_response_partially_filled",
+        "order_message_two_items",
+        "order_message_empty",
+        "order_message_two_items_v4",
+        "order_message_empty_v4",
+    ]
+    .into_iter()
+    .map(golden)
+    .collect()
+}
+
+fn schema(bump: &Bump) -> Schema<'_> {
+    ir::parse(MESSAGES_XML, bump).unwrap()
+}
+
+fn entries<'v, 'a>(fields: &'v [FieldValue<'a>], name: &str) -> &'v [Vec<FieldValue<'a>>] {
+    match fields
+        .iter()
+        .find(|field| field.name == name)
+        .map(|field| &field.value)
+    {
+        Some(Value::Group(entries)) => entries,
+        value => panic!("{name} is {value:?}"),
+    }
+}
+
+fn get<'v, 'a>(fields: &'v [FieldValue<'a>], name: &str) -> &'v Value<'a> {
+    &fields
+        .iter()
+        .find(|field| field.name == name)
+        .unwrap()
+        .value
+}
+
+#[test]
+fn parses_messages_xml() {
+    let bump = Bump::new();
+    let schema = schema(&bump);
+    // build.rs generates ir::SCHEMA with the same parser
+    assert_eq!(ir::SCHEMA, schema);
+    assert_eq!("sales_generated", schema.package);
+    assert_eq!(ByteOrder::LittleEndian, schema.byte_order);
+    assert_eq!(8, schema.header.encoded_length);
+
+    let order = schema.message_by_name("OrderMessage").unwrap();
+    let direction = order
+        .fields
+        .iter()
+        .find(|field| field.name == "direction")
+        .unwrap();
+    assert_eq!(Presence::Constant("Inbound"), direction.presence);
+    let items = &order.groups[0];
+    assert_eq!(
+        ("items", 33, 4),
+        (
+            items.name,
+            items.block_length,
+            items.dimension.encoded_length
+        )
+    );
+    assert_eq!(
+        ("discounts", 3),
+        (items.groups[0].name, items.groups[0].since_version)
+    );
+    assert_eq!(
+        Some(2),
+        order.data[0]
+            .encoding
+            .member("varData")
+            .map(|member| member.offset)
+    );
+    let Type::Composite(price) = items.fields[2].ty else {
+        panic!("unitPrice is not a composite");
+    };
+    assert_eq!(Presence::Constant("-2"), price.fields[1].presence);
+}
+
+#[test]
+fn prints_like_the_compiled_walk() {
+    let bump = Bump::new();
+    let schema = schema(&bump);
+    for frame in goldens() {
+        let decoded = decode(&schema, &frame).unwrap();
+        assert_eq!(ir::walk(&frame).unwrap().to_string(), decoded.to_string());
+        assert_eq!(frame.len(), decoded.encoded_length);
+    }
+}
+
+#[test]
+fn agrees_with_the_generated_decoder() {
+    let frame = golden("order_message_two_items");
+    let bump = Bump::new();
+    let schema = schema(&bump);
+    let decoded = decode(&schema, &frame).unwrap();
+
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(&frame), 0);
+    let decoder = OrderMessageDecoder::default().header(header, 0);
+    assert_eq!(5, decoded.acting_version);
+    assert_eq!(
+        Some(&Value::Uint(decoder.order_id())),
+        decoded.get("orderId")
+    );
+    assert_eq!(
+        Some(&Value::Uint(decoder.client_id())),
+        decoded.get("clientId")
+    );
+    assert_eq!(
+        Some(&Value::Uint(decoder.timestamp())),
+        decoded.get("timestamp")
+    );
+    assert_eq!(
+        Some(&Value::Enum {
+            raw: u8::from(decoder.order_type()) as u64,
+            name: Some("New")
+        }),
+        decoded.get("orderType")
+    );
+    assert_eq!(Some(&Value::Text("XSAL")), decoded.get("venue"));
+
+    let mut items = decoder.items_decoder();
+    let decoded_items = entries(&decoded.fields, "items");
+    assert_eq!(items.count() as usize, decoded_items.len());
+    for item in decoded_items {
+        assert!(items.advance().unwrap().is_some());
+        assert_eq!(
+            &Value::Uint(items.product_id() as u64),
+            get(item, "productId")
+        );
+        assert_eq!(&Value::Uint(items.quantity() as u64), get(item, "quantity"));
+        let mut price = items.unit_price_decoder();
+        assert_eq!(
+            &Value::Composite(vec![
+                FieldValue {
+                    name: "mantissa",
+                    value: Value::Int(price.mantissa())
+                },
+                FieldValue {
+                    name: "exponent",
+                    value: Value::Int(price.exponent() as i64)
+                },
+            ]),
+            get(item, "unitPrice")
+        );
+        items = price.parent().unwrap();
+        assert_eq!(
+            &Value::Text(char_array_str(&items.currency()).unwrap()),
+            get(item, "currency")
+        );
+        let mut discounts = items.discounts_decoder().unwrap();
+        assert_eq!(discounts.count() as usize, entries(item, "discounts").len());
+        items = discounts.parent().unwrap();
+        let (offset, len) = items.item_note_decoder();
+        assert_eq!(
+            &Value::Data(items.get_buf().get_slice_at(offset, len)),
+            get(item, "itemNote")
+        );
+    }
+    let mut decoder = items.parent().unwrap();
+    let (offset, len) = decoder.customer_note_decoder();
+    assert_eq!(
+        Some(&Value::Data(decoder.get_buf().get_slice_at(offset, len))),
+        decoded.get("customerNote")
+    );
+}
+
+#[test]
+fn reads_older_versions_with_their_schema() {
+    // version 3 of messages.xml, before numInGroup was widened and currency and sku were added
+    let xml = MESSAGES_XML
+        .replace(r#"id="100" version="5""#, r#"id="100" version="3""#)
+        .replace(
+            r#"<type name="numInGroup" primitiveType="uint16" />"#,
+            r#"<type name="numInGroup" primitiveType="uint8" />"#,
+        );
+    let bump = Bump::new();
+    let v3 = ir::parse(&xml, &bump).unwrap();
+    assert_eq!(3, v3.version);
+    assert_eq!(3, v3.message(1).unwrap().groups[0].dimension.encoded_length);
+
+    for name in ["order_message_two_items_v3", "order_message_empty_v3"] {
+        let frame = golden(name);
+        let decoded = decode(&v3, &frame).unwrap();
+        assert_eq!(ir::walk(&frame).unwrap().to_string(), decoded.to_string());
+        assert_eq!(None, decoded.get("currency"));
+    }
+}
+
+#[test]
+fn reads_big_endian_schemas() {
+    let bump = Bump::new();
+    let be = ir::parse(MESSAGES_BE_XML, &bump).unwrap();
+    assert_eq!(ByteOrder::BigEndian, be.byte_order);
+
+    let order = OrderMessage {
+        order_id: 234,
+        client_id: 135,
+        timestamp: 1_700_000_000_000_000_042,
+        order_type: OrderType::Cancel,
+        order_flags: *OrderFlags::default().set_post_only(true),
+        items: vec![Item {
+            product_id: 0x0102_0304,
+            quantity: 513,
+            unit_price: -12_345,
+            currency: "EUR".to_string(),
+            sku: "SKU-1".to_string(),
+            discounts: vec![Discount {
+                code: 258,
+                amount: 50,
+            }],
+            item_note: "gift".to_string(),
+        }],
+        customer_note: "duck is angry".to_string(),
+    };
+    let mut le_frame = Vec::new();
+    order.encode_to(&mut le_frame);
+    let be_frame = encode_order_be(&order);
+    assert_ne!(le_frame, be_frame);
+
+    let schema = schema(&bump);
+    assert_eq!(
+        decode(&schema, &le_frame).unwrap().to_string(),
+        decode(&be, &be_frame).unwrap().to_string()
+    );
+    // schema id 100 read in the other byte order
+    assert_eq!(
+        Err(DispatchError::WrongSchema(25_600)),
+        decode(&be, &le_frame)
+    );
+    assert_eq!(
+        Err(DispatchError::WrongSchema(25_600)),
+        decode(&schema, &be_frame)
+    );
+}
+
+fn encode_order_be(order: &OrderMessage) -> Vec<u8> {
+    use sales_generated_be::{
+        Encoder, WriteBuf, message_header_codec,
+        order_message_codec::{
+            OrderMessageEncoder,
+            encoder::{DiscountsEncoder, ItemsEncoder},
+        },
+    };
+
+    let mut buffer = vec![0; order.compute_length()];
+    let mut encoder = OrderMessageEncoder::default().wrap(
+        WriteBuf::new(&mut buffer),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    encoder = encoder.header(0).parent().unwrap();
+    encoder.order_id(order.order_id);
+    encoder.client_id(order.client_id);
+    encoder.timestamp(order.timestamp);
+    encoder.order_type(u8::from(order.order_type).into());
+    encoder.order_flags(sales_generated_be::order_flags::OrderFlags::new(
+        order.order_flags.0,
+    ));
+    let mut items = encoder.items_encoder(order.items.len() as u16, ItemsEncoder::default());
+    for item in &order.items {
+        let _r = items.advance();
+        items.product_id(item.product_id);
+        items.quantity(item.quantity);
+        let mut price = items.unit_price_encoder();
+        price.mantissa(item.unit_price);
+        items = price.parent().unwrap();
+        items.currency_zero_padded(item.currency.as_bytes());
+        items.sku_zero_padded(item.sku.as_bytes());
+        let mut discounts =
+            items.discounts_encoder(item.discounts.len() as u16, DiscountsEncoder::default());
+        for discount in &item.discounts {
+            let _r = discounts.advance();
+            discounts.code(discount.code);
+            let mut amount = discounts.amount_encoder();
+            amount.mantissa(discount.amount);
+            discounts = amount.parent().unwrap();
+        }
+        items = discounts.parent().unwrap();
+        items.item_note(&item.item_note);
+    }
+    encoder = items.parent().unwrap();
+    encoder.customer_note(&order.customer_note);
+    let len = encoder.get_limit();
+    buffer.truncate(len);
+    buffer
+}
+
+/// A schema of another team, with what messages.xml does not use
+const QUOTE_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<sbe:messageSchema xmlns:sbe="http://fixprotocol.io/2016/sbe" package="quotes" id="7" version="2"
+    headerType="header">
+    <types>
+        <composite name="header">
+            <type name="blockLength" primitiveType="uint16" />
+            <type name="templateId" primitiveType="uint16" />
+            <type name="schemaId" primitiveType="uint16" />
+            <type name="version" primitiveType="uint16" />
+        </composite>
+        <composite name="groupSizeEncoding">
+            <type name="blockLength" primitiveType="uint16" />
+            <type name="numInGroup" primitiveType="uint8" />
+        </composite>
+        <type name="Ratio" primitiveType="float" />
+        <type name="Levels" primitiveType="int16" length="3" />
+        <enum name="Side" encodingType="char">
+            <validValue name="Bid">B</validValue>
+            <validValue name="Ask">A</validValue>
+        </enum>
+        <set name="Flags" encodingType="uint16">
+            <choice name="Firm">0</choice>
+            <choice name="Stale">9</choice>
+        </set>
+    </types>
+    <sbe:message name="Quote" id="3">
+        <field name="bid" id="1" type="int32" presence="optional" />
+        <field name="ask" id="2" type="int32" presence="optional" nullValue="0" />
+        <field name="side" id="3" type="Side" />
+        <field name="flags" id="4" type="Flags" />
+        <field name="ratio" id="5" type="Ratio" />
+        <field name="levels" id="6" type="Levels" />
+        <field name="late" id="9" type="uint8" offset="21" sinceVersion="2" />
+        <group name="legs" id="7">
+            <field name="leg" id="8" type="uint64" />
+        </group>
+    </sbe:message>
+</sbe:messageSchema>
+"#;
+
+#[test]
+fn reads_schemas_of_other_teams() {
+    let bump = Bump::new();
+    let schema = ir::parse(QUOTE_XML, &bump).unwrap();
+    assert_eq!(22, schema.message(3).unwrap().block_length);
+
+    let mut frame = vec![];
+    for value in [22u16, 3, 7, 2] {
+        frame.extend(value.to_le_bytes());
+    }
+    frame.extend(i32::MIN.to_le_bytes());
+    frame.extend(0i32.to_le_bytes());
+    frame.push(b'A');
+    frame.extend((1u16 << 9 | 1 << 4).to_le_bytes());
+    frame.extend(0.5f32.to_le_bytes());
+    for level in [-1i16, 0, 300] {
+        frame.extend(level.to_le_bytes());
+    }
+    frame.push(9);
+    frame.extend([8, 0, 2]);
+    frame.extend(1u64.to_le_bytes());
+    frame.extend(u64::MAX.to_le_bytes());
+
+    let decoded = decode(&schema, &frame).unwrap();
+    assert_eq!(frame.len(), decoded.encoded_length);
+    assert_eq!(
+        "Quote { bid: null, ask: null, side: Ask, flags: [Stale], ratio: 0.5, \
+         levels: [-1, 0, 300], late: 9, legs: [{ leg: 1 }, { leg: 18446744073709551615 }] }",
+        decoded.to_string()
+    );
+    assert_eq!(
+        Some(&Value::Set {
+            raw: 1 << 9 | 1 << 4,
+            choices: vec!["Stale"]
+        }),
+        decoded.get("flags")
+    );
+
+    // version 1 senders have no late field and a block of 21 bytes
+    frame[0] = 21;
+    frame[6] = 1;
+    frame.remove(8 + 21);
+    let decoded = decode(&schema, &frame).unwrap();
+    assert_eq!(None, decoded.get("late"));
+    assert_eq!(frame.len(), decoded.encoded_length);
+}
+
+#[test]
+fn rejects_frames_it_cannot_read() {
+    let bump = Bump::new();
+    let schema = schema(&bump);
+    let frame = golden("order_message_two_items");
+    for len in 0..8 {
+        assert_eq!(
+            Err(DispatchError::Truncated),
+            decode(&schema, &frame[..len])
+        );
+    }
+    for len in 8..frame.len() {
+        assert!(
+            matches!(
+                decode(&schema, &frame[..len]),
+                Err(DispatchError::Malformed(_))
+            ),
+            "prefix of {len} bytes"
+        );
+    }
+
+    let mut unknown = frame.clone();
+    unknown[2] = 9;
+    assert_eq!(
+        Err(DispatchError::UnknownTemplate(9)),
+        decode(&schema, &unknown)
+    );
+    let mut short = frame.clone();
+    short[0] = 25;
+    assert_eq!(
+        Err(DispatchError::Malformed(SbeErr::BlockLengthTooShort)),
+        decode(&schema, &short)
+    );
+    let mut not_ascii = frame;
+    // first byte of the currency of the first item
+    not_ascii[8 + 26 + 4 + 14] = 0xc3;
+    assert_eq!(
+        Err(DispatchError::Malformed(SbeErr::NotAscii)),
+        decode(&schema, &not_ascii)
+    );
+}
+
+#[test]
+fn does_not_trust_group_counts() {
+    let bump = Bump::new();
+    let xml = QUOTE_XML.replace(
+        r#"name="numInGroup" primitiveType="uint8""#,
+        r#"name="numInGroup" primitiveType="uint32""#,
+    );
+    let schema = ir::parse(&xml, &bump).unwrap();
+    let mut frame = vec![];
+    for value in [22u16, 3, 7, 2] {
+        frame.extend(value.to_le_bytes());
+    }
+    frame.extend([0; 22]);
+    frame[8 + 8] = b'A';
+    // a block length of 0 fits any count into the frame
+    frame.extend(0u16.to_le_bytes());
+    frame.extend(u32::MAX.to_le_bytes());
+    assert_eq!(
+        Err(DispatchError::Malformed(SbeErr::BlockLengthTooShort)),
+        decode(&schema, &frame)
+    );
+}
+
+#[test]
+fn rejects_broken_schemas() {
+    let bump = Bump::new();
+    assert!(matches!(
+        ir::parse("<messageSchema", &bump),
+        Err(SchemaError::Xml(_))
+    ));
+    assert!(matches!(
+        ir::load("no/such/messages.xml", &bump),
+        Err(SchemaError::Io(_))
+    ));
+
+    let unknown = MESSAGES_XML.replace(r#"type="Sku""#, r#"type="Isin""#);
+    let err = ir::parse(&unknown, &bump).unwrap_err();
+    assert_eq!("unknown type Isin", err.to_string());
+
+    let byte_order = MESSAGES_XML.replace(r#"id="100""#, r#"id="100" byteOrder="middleEndian""#);
+    let err = ir::parse(&byte_order, &bump).unwrap_err();
+    assert!(matches!(err, SchemaError::InvalidValue { ref value, .. } if value == "middleEndian"));
+
+    let no_id = MESSAGES_XML.replace(r#"name="orderId" id="1""#, r#"name="orderId""#);
+    assert_eq!(
+        "field orderId has no attribute id",
+        ir::parse(&no_id, &bump).unwrap_err().to_string()
+    );
+
+    let no_count = MESSAGES_XML.replace(r#"name="numInGroup""#, r#"name="count""#);
+    assert_eq!(
+        "composite groupSizeEncoding has no member numInGroup",
+        ir::parse(&no_count, &bump).unwrap_err().to_string()
+    );
+
+    for (types, name) in [
+        (
+            r#"<composite name="Loop"><ref name="again" type="Loop" /></composite>"#,
+            "Loop",
+        ),
+        (
+            r#"<composite name="A"><ref name="b" type="B" /></composite>
+               <composite name="B"><type name="c" primitiveType="A" /></composite>"#,
+            "A",
+        ),
+        (r#"<type name="Odd" primitiveType="Odd" />"#, "Odd"),
+    ] {
+        let cyclic = QUOTE_XML.replace("    </types>", &format!("{types}\n    </types>"));
+        assert_eq!(
+            format!("type {name} contains itself"),
+            ir::parse(&cyclic, &bump).unwrap_err().to_string()
+        );
+    }
+
+    let out_of_range = QUOTE_XML.replace(
+        "    </types>",
+        r#"<enum name="Tick" encodingType="int8"><validValue name="Far">128</validValue></enum>
+    </types>"#,
+    );
+    assert_eq!(
+        r#"invalid value "128" in validValue Far"#,
+        ir::parse(&out_of_range, &bump).unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn reads_explicit_offsets_and_signed_enums() {
+    let bump = Bump::new();
+    let xml = QUOTE_XML.replace(
+        "    </types>",
+        r#"    <composite name="Pair">
+            <type name="second" primitiveType="uint32" offset="4" />
+            <type name="first" primitiveType="uint16" offset="0" />
+        </composite>
+        <enum name="Tick" encodingType="int16">
+            <validValue name="Down">-1</validValue>
+            <validValue name="Up">1</validValue>
+        </enum>
+    </types>"#,
+    );
+    let schema = ir::parse(&xml, &bump).unwrap();
+    let declared = |name| schema.types.iter().find(|ty| ty.name() == name).unwrap();
+
+    // the length covers the member at offset 4 even though it is declared first
+    assert_eq!(8, declared("Pair").encoded_length());
+    let Type::Enum(tick) = declared("Tick") else {
+        panic!("Tick is not an enum");
+    };
+    assert_eq!(Some("Down"), tick.value(0xffff).map(|value| value.name));
+    assert_eq!(Some("Up"), tick.value(1).map(|value| value.name));
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_bytes_never_panic(frame in prop::collection::vec(any::<u8>(), 0..256)) {
+        let bump = Bump::new();
+        let _ = decode(&schema(&bump), &frame);
+    }
+
+    #[test]
+    fn corrupted_frames_decode_like_the_walk(
+        golden_index in 0..5usize,
+        corruptions in prop::collection::vec((any::<prop::sample::Index>(), any::<u8>()), 1..4),
+    ) {
+        let mut frame = goldens().swap_remove(golden_index);
+        for (index, byte) in corruptions {
+            let len = frame.len();
+            frame[index.index(len)] = byte;
+        }
+        let bump = Bump::new();
+        let schema = schema(&bump);
+        // the walk reads the three byte dimension of frames before version 4
+        if let (Ok(decoded), Ok(walked)) = (decode(&schema, &frame), ir::walk(&frame))
+            && decoded.acting_version >= 4
+        {
+            prop_assert_eq!(walked.to_string(), decoded.to_string());
+            prop_assert_eq!(walked.encoded_length, decoded.encoded_length);
+        }
+    }
+}
@@ -0,0 +1,544 @@
+use my_sales_app::model::{Discount, Item, Message, OrderMessage, OrderResponse};
+use roxmltree::{Document, Node};
+use sales_generated::{
+    SbeErr,
+    ir::{self, Data, Field, FieldValue, Group, Presence, SCHEMA, Type, Value},
+    order_flags::OrderFlags,
+    order_status::OrderStatus,
+    order_type::OrderType,
+};
+
+const MESSAGES_XML: &str = include_str!("../../sales_generated/messages.xml");
+
+fn golden(name: &str) -> Vec<u8> {
+    let path = format!("{}/tests/golden/{name}.hex", env!("CARGO_MANIFEST_DIR"));
+    let hex = std::fs::read_to_string(path).unwrap();
+    let hex = hex.trim();
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn goldens() -> Vec<Vec<u8>> {
+    let mut frames = vec![golden("order_response_partially_filled")];
+    for name in ["order_message_two_items", "order_message_empty"] {
+        frames.push(golden(name));
+        for version in 1..=4 {
+            frames.push(golden(&format!("{name}_v{version}")));
+        }
+    }
+    frames
+}
+
+// ========= IR against messages.xml =========
+
+fn child_elements<'a, 'i>(
+    node: Node<'a, 'i>,
+    tag: &'static str,
+) -> impl Iterator<Item = Node<'a, 'i>> {
+    node.children().filter(move |child| child.has_tag_name(tag))
+}
+
+fn primitive_size(name: &str) -> Option<usize> {
+    Some(match name {
+        "char" | "int8" | "uint8" => 1,
+        "int16" | "uint16" => 2,
+        "int32" | "uint32" | "float" => 4,
+        "int64" | "uint64" | "double" => 8,
+        _ => return None,
+    })
+}
+
+fn type_node<'a, 'i>(doc: &'a Document<'i>, name: &str) -> Node<'a, 'i> {
+    doc.descendants()
+        .find(|node| node.has_tag_name("types"))
+        .unwrap()
+        .children()
+        .find(|node| node.attribute("name") == Some(name))
+        .unwrap_or_else(|| panic!("type {name} not in messages.xml"))
+}
+
+/// encoded length of a type of messages.xml
+fn type_size(doc: &Document, name: &str) -> usize {
+    if let Some(size) = primitive_size(name) {
+        return size;
+    }
+    let node = type_node(doc, name);
+    match node.tag_name().name() {
+        "type" => {
+            let length = node
+                .attribute("length")
+                .map_or(1, |length| length.parse().unwrap());
+            primitive_size(node.attribute("primitiveType").unwrap()).unwrap() * length
+        }
+        "enum" | "set" => primitive_size(node.attribute("encodingType").unwrap()).unwrap(),
+        "composite" => child_elements(node, "type")
+            .filter(|member| member.attribute("presence") != Some("constant"))
+            .map(|member| type_size(doc, member.attribute("primitiveType").unwrap()))
+            .sum(),
+        tag => panic!("unexpected {tag}"),
+    }
+}
+
+fn since_version(node: Node) -> u16 {
+    node.attribute("sinceVersion")
+        .map_or(0, |version| version.parse().unwrap())
+}
+
+fn check_fields(doc: &Document, node: Node, fields: &[Field]) -> usize {
+    let xml_fields: Vec<Node> = child_elements(node, "field").collect();
+    assert_eq!(
+        xml_fields.len(),
+        fields.len(),
+        "fields of {:?}",
+        node.attribute("name")
+    );
+    let mut offset = 0;
+    for (xml, field) in xml_fields.into_iter().zip(fields) {
+        let type_name = xml.attribute("type").unwrap();
+        assert_eq!(xml.attribute("name"), Some(field.name));
+        assert_eq!(
+            xml.attribute("id").unwrap().parse::<u16>().unwrap(),
+            field.id,
+            "{}",
+            field.name
+        );
+        assert_eq!(type_name, field.ty.name(), "{}", field.name);
+        assert_eq!(since_version(xml), field.since_version, "{}", field.name);
+        assert_eq!(
+            xml.attribute("semanticType"),
+            field.semantic_type,
+            "{}",
+            field.name
+        );
+        assert_eq!(
+            xml.attribute("description").unwrap_or_default(),
+            field.description
+        );
+        assert_eq!(offset, field.offset, "{}", field.name);
+        if xml.attribute("presence") == Some("constant") {
+            let constant = match xml.attribute("valueRef") {
+                Some(value_ref) => value_ref.split_once('.').unwrap().1,
+                None => type_node(doc, type_name).text().unwrap().trim(),
+            };
+            let Presence::Constant(ir_constant) = field.presence else {
+                panic!("{} is not a constant", field.name);
+            };
+            assert_eq!(constant, ir_constant, "{}", field.name);
+        } else {
+            assert_eq!(Presence::Required, field.presence, "{}", field.name);
+            let size = type_size(doc, type_name);
+            assert_eq!(size, field.encoded_length(), "{}", field.name);
+            offset += size;
+        }
+    }
+    offset
+}
+
+fn check_groups(doc: &Document, node: Node, groups: &[Group]) {
+    let xml_groups: Vec<Node> = child_elements(node, "group").collect();
+    assert_eq!(xml_groups.len(), groups.len());
+    for (xml, group) in xml_groups.into_iter().zip(groups) {
+        assert_eq!(xml.attribute("name"), Some(group.name));
+        assert_eq!(
+            xml.attribute("id").unwrap().parse::<u16>().unwrap(),
+            group.id
+        );
+        assert_eq!(since_version(xml), group.since_version, "{}", group.name);
+        assert_eq!(
+            xml.attribute("description").unwrap_or_default(),
+            group.description
+        );
+        let block_length = check_fields(doc, xml, group.fields);
+        assert_eq!(block_length, group.block_length as usize, "{}", group.name);
+        check_groups(doc, xml, group.groups);
+        check_data(xml, group.data);
+    }
+}
+
+fn check_data(node: Node, data: &[Data]) {
+    let xml_data: Vec<Node> = child_elements(node, "data").collect();
+    assert_eq!(xml_data.len(), data.len());
+    for (xml, data) in xml_data.into_iter().zip(data) {
+        assert_eq!(xml.attribute("name"), Some(data.name));
+        assert_eq!(
+            xml.attribute("id").unwrap().parse::<u16>().unwrap(),
+            data.id
+        );
+        assert_eq!(xml.attribute("type"), Some(data.encoding.name));
+        assert_eq!(since_version(xml), data.since_version, "{}", data.name);
+        assert_eq!(
+            xml.attribute("description").unwrap_or_default(),
+            data.description
+        );
+    }
+}
+
+#[test]
+fn ir_matches_messages_xml() {
+    let doc = Document::parse(MESSAGES_XML).unwrap();
+    let root = doc.root_element();
+    assert_eq!(root.attribute("package"), Some(SCHEMA.package));
+    assert_eq!(
+        root.attribute("id").unwrap().parse::<u16>().unwrap(),
+        SCHEMA.id
+    );
+    assert_eq!(
+        root.attribute("version").unwrap().parse::<u16>().unwrap(),
+        SCHEMA.version
+    );
+    assert_eq!(
+        root.attribute("semanticVersion"),
+        Some(SCHEMA.semantic_version)
+    );
+
+    let xml_messages: Vec<Node> = child_elements(root, "message").collect();
+    assert_eq!(xml_messages.len(), SCHEMA.messages.len());
+    for (xml, message) in xml_messages.into_iter().zip(SCHEMA.messages) {
+        assert_eq!(xml.attribute("name"), Some(message.name));
+        assert_eq!(
+            xml.attribute("id").unwrap().parse::<u16>().unwrap(),
+            message.id
+        );
+        assert_eq!(
+            xml.attribute("description").unwrap_or_default(),
+            message.description
+        );
+        let block_length = check_fields(&doc, xml, message.fields);
+        assert_eq!(
+            block_length, message.block_length as usize,
+            "{}",
+            message.name
+        );
+        check_groups(&doc, xml, message.groups);
+        check_data(xml, message.data);
+    }
+}
+
+/// The IR is generated from messages.xml, the codecs are ported by hand
+#[test]
+fn ir_matches_the_codecs() {
+    use sales_generated::{
+        SBE_SCHEMA_ID, SBE_SCHEMA_VERSION, SBE_SEMANTIC_VERSION, group_size_encoding_codec,
+        message_header_codec, order_message_codec, order_response_codec,
+    };
+
+    assert_eq!(
+        (SBE_SCHEMA_ID, SBE_SCHEMA_VERSION, SBE_SEMANTIC_VERSION),
+        (SCHEMA.id, SCHEMA.version, SCHEMA.semantic_version)
+    );
+    for (message, template_id, block_length) in [
+        (
+            &ir::ORDER_MESSAGE,
+            order_message_codec::SBE_TEMPLATE_ID,
+            order_message_codec::SBE_BLOCK_LENGTH,
+        ),
+        (
+            &ir::ORDER_RESPONSE,
+            order_response_codec::SBE_TEMPLATE_ID,
+            order_response_codec::SBE_BLOCK_LENGTH,
+        ),
+    ] {
+        assert_eq!(
+            (template_id, block_length),
+            (message.id, message.block_length),
+            "{}",
+            message.name
+        );
+    }
+    assert_eq!(
+        message_header_codec::ENCODED_LENGTH,
+        SCHEMA.header.encoded_length
+    );
+    assert_eq!(
+        group_size_encoding_codec::ENCODED_LENGTH,
+        ir::GROUP_SIZE_ENCODING.encoded_length
+    );
+}
+
+#[test]
+fn ir_types_match_messages_xml() {
+    let doc = Document::parse(MESSAGES_XML).unwrap();
+    for ty in [ir::DIRECTION, ir::ORDER_TYPE, ir::ORDER_STATUS] {
+        let xml = type_node(&doc, ty.name);
+        assert_eq!(xml.attribute("encodingType"), Some(ty.encoding.name()));
+        let values: Vec<(&str, u64)> = child_elements(xml, "validValue")
+            .map(|value| {
+                (
+                    value.attribute("name").unwrap(),
+                    value.text().unwrap().parse().unwrap(),
+                )
+            })
+            .collect();
+        let ir_values: Vec<(&str, u64)> = ty
+            .values
+            .iter()
+            .map(|value| (value.name, value.value))
+            .collect();
+        assert_eq!(values, ir_values, "{}", ty.name);
+    }
+
+    let xml = type_node(&doc, ir::ORDER_FLAGS.name);
+    assert_eq!(
+        xml.attribute("encodingType"),
+        Some(ir::ORDER_FLAGS.encoding.name())
+    );
+    let choices: Vec<(&str, u8)> = child_elements(xml, "choice")
+        .map(|choice| {
+            (
+                choice.attribute("name").unwrap(),
+                choice.text().unwrap().parse().unwrap(),
+            )
+        })
+        .collect();
+    let ir_choices: Vec<(&str, u8)> = ir::ORDER_FLAGS
+        .choices
+        .iter()
+        .map(|choice| (choice.name, choice.bit))
+        .collect();
+    assert_eq!(choices, ir_choices);
+
+    for array in [ir::CURRENCY, ir::SKU, ir::MIC] {
+        let xml = type_node(&doc, array.name);
+        assert_eq!(
+            xml.attribute("length").unwrap().parse::<usize>().unwrap(),
+            array.length
+        );
+        assert_eq!(
+            xml.attribute("characterEncoding"),
+            Some(array.character_encoding)
+        );
+    }
+
+    let xml = type_node(&doc, ir::DECIMAL.name);
+    assert_eq!(
+        type_size(&doc, ir::DECIMAL.name),
+        ir::DECIMAL.encoded_length
+    );
+    let members: Vec<(&str, &str, Option<&str>)> = child_elements(xml, "type")
+        .map(|member| {
+            let name = member.attribute("name").unwrap();
+            let constant = member.text().map(str::trim);
+            (name, member.attribute("primitiveType").unwrap(), constant)
+        })
+        .collect();
+    let ir_members: Vec<(&str, &str, Option<&str>)> = ir::DECIMAL
+        .fields
+        .iter()
+        .map(|member| {
+            let constant = match member.presence {
+                Presence::Constant(constant) => Some(constant),
+                _ => None,
+            };
+            (member.name, member.ty.name(), constant)
+        })
+        .collect();
+    assert_eq!(members, ir_members);
+}
+
+// ========= walk against the compiled decoders =========
+
+fn get<'v, 'a>(fields: &'v [FieldValue<'a>], name: &str) -> Option<&'v Value<'a>> {
+    fields
+        .iter()
+        .find(|field| field.name == name)
+        .map(|field| &field.value)
+}
+
+fn uint(fields: &[FieldValue], name: &str) -> u64 {
+    match get(fields, name) {
+        Some(Value::Uint(value)) => *value,
+        value => panic!("{name} is {value:?}"),
+    }
+}
+
+fn enum_raw(fields: &[FieldValue], name: &str) -> u8 {
+    match get(fields, name) {
+        Some(Value::Enum { raw, .. }) => *raw as u8,
+        value => panic!("{name} is {value:?}"),
+    }
+}
+
+fn mantissa(fields: &[FieldValue], name: &str) -> i64 {
+    let Some(Value::Composite(members)) = get(fields, name) else {
+        panic!("{name} is not a composite");
+    };
+    assert_eq!(Some(&Value::Int(-2)), get(members, "exponent"));
+    match get(members, "mantissa") {
+        Some(Value::Int(value)) => *value,
+        value => panic!("mantissa is {value:?}"),
+    }
+}
+
+/// empty for fields a sender of the acting version does not write
+fn text(fields: &[FieldValue], name: &str) -> String {
+    match get(fields, name) {
+        None => String::new(),
+        Some(Value::Text(text)) => text.to_string(),
+        Some(Value::Data(bytes)) => String::from_utf8(bytes.to_vec()).unwrap(),
+        value => panic!("{name} is {value:?}"),
+    }
+}
+
+fn entries<'v, 'a>(fields: &'v [FieldValue<'a>], name: &str) -> &'v [Vec<FieldValue<'a>>] {
+    match get(fields, name) {
+        None => &[],
+        Some(Value::Group(entries)) => entries,
+        value => panic!("{name} is {value:?}"),
+    }
+}
+
+/// the owned model built from the value tree alone
+fn message_from_tree(frame: &[u8]) -> Message {
+    let tree = ir::walk(frame).unwrap();
+    assert_eq!(frame.len(), tree.encoded_length);
+    let fields = &tree.fields;
+    match tree.message.name {
+        "OrderMessage" => Message::Order(OrderMessage {
+            order_id: uint(fields, "orderId"),
+            client_id: uint(fields, "clientId"),
+            timestamp: uint(fields, "timestamp"),
+            order_type: OrderType::from(enum_raw(fields, "orderType")),
+            order_flags: match get(fields, "orderFlags") {
+                Some(Value::Set { raw, .. }) => OrderFlags::new(*raw as u8),
+                _ => OrderFlags::default(),
+            },
+            items: entries(fields, "items")
+                .iter()
+                .map(|item| Item {
+                    product_id: uint(item, "productId") as u32,
+                    quantity: uint(item, "quantity") as u16,
+                    unit_price: mantissa(item, "unitPrice"),
+                    currency: text(item, "currency"),
+                    sku: text(item, "sku"),
+                    discounts: entries(item, "discounts")
+                        .iter()
+                        .map(|discount| Discount {
+                            code: uint(discount, "code") as u16,
+                            amount: mantissa(discount, "amount"),
+                        })
+                        .collect(),
+                    item_note: text(item, "itemNote"),
+                })
+                .collect(),
+            customer_note: text(fields, "customerNote"),
+        }),
+        "OrderResponse" => Message::Response(OrderResponse {
+            order_id: uint(fields, "orderId"),
+            timestamp: uint(fields, "timestamp"),
+            status: OrderStatus::from(enum_raw(fields, "status")),
+            filled_qty: uint(fields, "filledQty") as u32,
+            fill_price: mantissa(fields, "fillPrice"),
+            server_note: text(fields, "serverNote"),
+        }),
+        name => panic!("unexpected {name}"),
+    }
+}
+
+#[test]
+fn walk_agrees_with_the_generated_decoders() {
+    for frame in goldens() {
+        assert_eq!(
+            Message::decode_checked(&frame).unwrap(),
+            message_from_tree(&frame)
+        );
+    }
+}
+
+#[test]
+fn walk_leaves_out_fields_of_newer_versions() {
+    let frame = golden("order_message_two_items_v1");
+    let v1 = ir::walk(&frame).unwrap();
+    assert_eq!(1, v1.acting_version);
+    assert_eq!(None, v1.get("orderFlags"));
+    let Some(Value::Group(items)) = v1.get("items") else {
+        panic!("no items");
+    };
+    let names: Vec<&str> = items[0].iter().map(|field| field.name).collect();
+    assert_eq!(vec!["productId", "quantity", "unitPrice"], names);
+}
+
+#[test]
+fn walk_prints_any_message() {
+    let frame = golden("order_response_partially_filled");
+    let tree = ir::walk(&frame).unwrap();
+    assert_eq!("OrderResponse", tree.message.name);
+    assert_eq!(
+        Some(&Value::Enum {
+            raw: 1,
+            name: Some("Outbound")
+        }),
+        tree.get("direction")
+    );
+
+    let order = OrderMessage {
+        order_id: 234,
+        client_id: 135,
+        timestamp: 246,
+        order_type: OrderType::New,
+        order_flags: *OrderFlags::default().set_post_only(true),
+        items: vec![Item {
+            product_id: 222,
+            quantity: 2,
+            unit_price: 234,
+            currency: "EUR".to_string(),
+            sku: "SKU-222".to_string(),
+            discounts: vec![Discount {
+                code: 7,
+                amount: 50,
+            }],
+            item_note: "gift".to_string(),
+        }],
+        customer_note: "duck is angry".to_string(),
+    };
+    let mut frame = Vec::new();
+    order.encode_to(&mut frame);
+    assert_eq!(
+        "OrderMessage { orderId: 234, clientId: 135, timestamp: 246, orderType: New, \
+         orderFlags: [PostOnly], venue: \"XSAL\", direction: Inbound, items: [{ productId: 222, \
+         quantity: 2, unitPrice: { mantissa: 234, exponent: -2 }, currency: \"EUR\", \
+         sku: \"SKU-222\", discounts: [{ code: 7, amount: { mantissa: 50, exponent: -2 } }], \
+         itemNote: \"gift\" }], customerNote: \"duck is angry\" }",
+        ir::walk(&frame).unwrap().to_string()
+    );
+}
+
+#[test]
+fn walk_rejects_malformed_frames() {
+    for frame in goldens() {
+        for len in 0..frame.len() {
+            assert!(
+                ir::walk(&frame[..len]).is_err(),
+                "{len} of {} bytes",
+                frame.len()
+            );
+        }
+    }
+
+    let mut frame = golden("order_message_two_items");
+    frame[2] = 99;
+    assert_eq!(Err(SbeErr::WrongTemplateId), ir::walk(&frame).map(|_| ()));
+    frame[2] = 1;
+    frame[4] = 101;
+    assert_eq!(Err(SbeErr::WrongSchemaId), ir::walk(&frame).map(|_| ()));
+    frame[4] = 100;
+    // block length of the message
+    frame[0] = 25;
+    assert_eq!(
+        Err(SbeErr::BlockLengthTooShort),
+        ir::walk(&frame).map(|_| ())
+    );
+}
+
+#[test]
+fn constants_are_not_on_the_wire() {
+    for message in SCHEMA.messages {
+        for field in message.fields {
+            if let Presence::Constant(_) = field.presence {
+                assert_eq!(0, field.encoded_length());
+                assert!(matches!(field.ty, Type::CharArray(_) | Type::Enum(_)));
+            }
+        }
+    }
+}
@@ -0,0 +1,335 @@
+use std::fs::{self, OpenOptions};
+
+use my_sales_app::{
+    dispatch::MessageHandler,
+    journal::{self, Journal, JournalConfig, ProblemKind, Replayer},
+};
+use sales_generated::{
+    Encoder, WriteBuf, message_header_codec,
+    order_message_codec::{OrderMessageDecoder, OrderMessageEncoder, encoder::ItemsEncoder},
+    order_response_codec::{OrderResponseDecoder, OrderResponseEncoder},
+    order_status::OrderStatus,
+    order_type::OrderType,
+};
+
+fn order_frame(order_id: u64) -> Vec<u8> {
+    let mut buffer = [0u8; 128];
+    let mut order = OrderMessageEncoder::default().wrap(
+        WriteBuf::new(&mut buffer),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    order = order.header(0).parent().unwrap();
+    order.order_id(order_id);
+    order.client_id(7);
+    order.timestamp(order_id * 10);
+    order.order_type(OrderType::New);
+    let mut items = order.items_encoder(1, ItemsEncoder::default());
+    let _r = items.advance();
+    items.product_id(1);
+    items.quantity(2);
+    let mut price = items.unit_price_encoder();
+    price.mantissa(300);
+    items = price.parent().unwrap();
+    order = items.parent().unwrap();
+    order.customer_note("note");
+    let len = order.get_limit();
+    buffer[..len].to_vec()
+}
+
+fn response_frame(order_id: u64) -> Vec<u8> {
+    let mut buffer = [0u8; 128];
+    let mut response = OrderResponseEncoder::default().wrap(
+        WriteBuf::new(&mut buffer),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    response = response.header(0).parent().unwrap();
+    response.order_id(order_id);
+    response.timestamp(order_id * 10 + 1);
+    response.status(OrderStatus::Accepted);
+    response.filled_qty(0);
+    let mut price = response.fill_price_encoder();
+    price.mantissa(0);
+    response = price.parent().unwrap();
+    response.server_note("");
+    let len = response.get_limit();
+    buffer[..len].to_vec()
+}
+
+#[derive(Default)]
+struct Collect {
+    orders: Vec<u64>,
+    responses: Vec<u64>,
+}
+
+impl MessageHandler for Collect {
+    fn on_order_message(&mut self, order: OrderMessageDecoder<'_>) {
+        self.orders.push(order.order_id());
+    }
+
+    fn on_order_response(&mut self, response: OrderResponseDecoder<'_>) {
+        self.responses.push(response.order_id());
+    }
+}
+
+/// Appends an order and its response for order ids 1..=count, receive time is 100 * order id.
+fn fill(journal: &mut Journal, count: u64) {
+    for order_id in 1..=count {
+        journal
+            .append(&order_frame(order_id), order_id * 100)
+            .unwrap();
+        journal
+            .append(&response_frame(order_id), order_id * 100 + 1)
+            .unwrap();
+    }
+}
+
+fn small_segments() -> JournalConfig {
+    JournalConfig {
+        segment_size: 512,
+        sync_on_append: false,
+    }
+}
+
+#[test]
+fn append_roll_and_replay() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut journal = Journal::open(dir.path(), small_segments()).unwrap();
+    fill(&mut journal, 20);
+    journal.sync().unwrap();
+    assert_eq!(41, journal.next_sequence());
+
+    let segments = fs::read_dir(dir.path()).unwrap().count();
+    assert!(segments > 1, "expected rolled segments, got {segments}");
+
+    let replayer = Replayer::open(dir.path()).unwrap();
+    let sequences: Vec<u64> = replayer
+        .from_sequence(1)
+        .map(|r| r.unwrap().sequence)
+        .collect();
+    assert_eq!((1..=40).collect::<Vec<_>>(), sequences);
+
+    let mut collect = Collect::default();
+    assert_eq!(40, replayer.replay(1, &mut collect).unwrap());
+    assert_eq!((1..=20).collect::<Vec<_>>(), collect.orders);
+    assert_eq!(collect.orders, collect.responses);
+
+    let report = journal::verify(dir.path()).unwrap();
+    assert!(report.is_clean(), "{report:?}");
+    assert_eq!(40, report.records);
+    assert_eq!(Some(40), report.last_sequence);
+}
+
+#[test]
+fn replay_from_sequence_and_timestamp() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut journal = Journal::open(dir.path(), small_segments()).unwrap();
+    fill(&mut journal, 10);
+
+    let replayer = Replayer::open(dir.path()).unwrap();
+    let first = replayer.from_sequence(17).next().unwrap().unwrap();
+    assert_eq!(17, first.sequence);
+    assert_eq!(order_frame(9), first.frame);
+
+    let mut collect = Collect::default();
+    assert_eq!(
+        5,
+        replayer.replay_from_timestamp(801, &mut collect).unwrap()
+    );
+    assert_eq!(vec![9, 10], collect.orders);
+    assert_eq!(vec![8, 9, 10], collect.responses);
+
+    assert_eq!(0, replayer.from_sequence(21).count());
+}
+
+#[test]
+fn reopen_truncates_torn_tail() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut journal = Journal::open(dir.path(), JournalConfig::default()).unwrap();
+    fill(&mut journal, 3);
+    drop(journal);
+
+    // simulate a crash half way through writing a record
+    let segment = fs::read_dir(dir.path())
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let len = fs::metadata(&segment).unwrap().len();
+    OpenOptions::new()
+        .write(true)
+        .open(&segment)
+        .unwrap()
+        .set_len(len - 5)
+        .unwrap();
+
+    let report = journal::verify(dir.path()).unwrap();
+    assert_eq!(5, report.records);
+    assert_eq!(
+        vec![ProblemKind::TornRecord],
+        report.problems.iter().map(|p| p.kind).collect::<Vec<_>>()
+    );
+
+    let mut journal = Journal::open(dir.path(), JournalConfig::default()).unwrap();
+    assert_eq!(6, journal.next_sequence());
+    journal.append(&response_frame(3), 301).unwrap();
+    assert!(journal::verify(dir.path()).unwrap().is_clean());
+
+    let mut collect = Collect::default();
+    Replayer::open(dir.path())
+        .unwrap()
+        .replay(1, &mut collect)
+        .unwrap();
+    assert_eq!(vec![1, 2, 3], collect.responses);
+}
+
+#[test]
+fn reopen_truncates_zero_filled_tail() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut journal = Journal::open(dir.path(), JournalConfig::default()).unwrap();
+    fill(&mut journal, 3);
+    drop(journal);
+
+    // the file grew but the last records never reached the disk
+    let segment = fs::read_dir(dir.path())
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let len = fs::metadata(&segment).unwrap().len();
+    OpenOptions::new()
+        .write(true)
+        .open(&segment)
+        .unwrap()
+        .set_len(len + 100)
+        .unwrap();
+
+    let report = journal::verify(dir.path()).unwrap();
+    assert_eq!(6, report.records);
+    assert_eq!(ProblemKind::TornRecord, report.problems[0].kind);
+    assert_eq!(len, report.problems[0].offset);
+
+    let mut journal = Journal::open(dir.path(), JournalConfig::default()).unwrap();
+    assert_eq!(7, journal.next_sequence());
+    assert_eq!(len, fs::metadata(&segment).unwrap().len());
+    journal.append(&order_frame(4), 400).unwrap();
+    assert!(journal::verify(dir.path()).unwrap().is_clean());
+}
+
+#[test]
+fn verify_detects_missing_segment() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut journal = Journal::open(dir.path(), small_segments()).unwrap();
+    fill(&mut journal, 20);
+    drop(journal);
+
+    let mut segments: Vec<_> = fs::read_dir(dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    segments.sort();
+    let sequence_of = |path: &std::path::Path| -> u64 {
+        path.file_stem().unwrap().to_str().unwrap().parse().unwrap()
+    };
+    let (missing, next) = (sequence_of(&segments[1]), &segments[2]);
+    fs::remove_file(&segments[1]).unwrap();
+
+    let report = journal::verify(dir.path()).unwrap();
+    assert_eq!(1, report.problems.len());
+    assert_eq!(next, &report.problems[0].segment);
+    assert_eq!(0, report.problems[0].offset);
+    assert_eq!(
+        ProblemKind::SequenceGap {
+            expected: missing,
+            found: sequence_of(next)
+        },
+        report.problems[0].kind
+    );
+}
+
+#[test]
+fn verify_detects_bit_flip() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut journal = Journal::open(dir.path(), JournalConfig::default()).unwrap();
+    fill(&mut journal, 2);
+    drop(journal);
+
+    let segment = fs::read_dir(dir.path())
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let mut bytes = fs::read(&segment).unwrap();
+    let second_record = journal::RECORD_HEADER_LENGTH + order_frame(1).len();
+    bytes[second_record + journal::RECORD_HEADER_LENGTH + 9] ^= 0x01;
+    fs::write(&segment, bytes).unwrap();
+
+    let report = journal::verify(dir.path()).unwrap();
+    assert_eq!(1, report.records);
+    assert_eq!(ProblemKind::CrcMismatch, report.problems[0].kind);
+    assert_eq!(second_record as u64, report.problems[0].offset);
+
+    let results: Vec<_> = Replayer::open(dir.path())
+        .unwrap()
+        .from_sequence(1)
+        .collect();
+    assert_eq!(2, results.len());
+    assert!(results[1].is_err());
+    assert!(Journal::open(dir.path(), JournalConfig::default()).is_err());
+}
+
+#[test]
+fn reopen_keeps_records_after_a_corrupt_length() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut journal = Journal::open(dir.path(), JournalConfig::default()).unwrap();
+    fill(&mut journal, 3);
+    drop(journal);
+
+    // the length of the second record now reaches past the end of the segment
+    let segment = fs::read_dir(dir.path())
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let mut bytes = fs::read(&segment).unwrap();
+    let second_record = journal::RECORD_HEADER_LENGTH + order_frame(1).len();
+    bytes[second_record + 3] ^= 0x10;
+    fs::write(&segment, &bytes).unwrap();
+
+    let report = journal::verify(dir.path()).unwrap();
+    assert_eq!(1, report.records);
+    assert_eq!(ProblemKind::CrcMismatch, report.problems[0].kind);
+    assert_eq!(second_record as u64, report.problems[0].offset);
+
+    assert!(matches!(
+        Journal::open(dir.path(), JournalConfig::default()),
+        Err(journal::JournalError::Corrupt { offset, .. }) if offset == second_record as u64
+    ));
+    assert_eq!(bytes, fs::read(&segment).unwrap());
+}
+
+#[test]
+fn crc_covers_the_length() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut journal = Journal::open(dir.path(), JournalConfig::default()).unwrap();
+    fill(&mut journal, 2);
+    drop(journal);
+
+    // a shorter length still fits the segment, only the crc can tell
+    let segment = fs::read_dir(dir.path())
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let mut bytes = fs::read(&segment).unwrap();
+    bytes[0] ^= 0x01;
+    fs::write(&segment, &bytes).unwrap();
+
+    let report = journal::verify(dir.path()).unwrap();
+    assert_eq!(0, report.records);
+    assert_eq!(ProblemKind::CrcMismatch, report.problems[0].kind);
+}
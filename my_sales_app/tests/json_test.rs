@@ -0,0 +1,370 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use my_sales_app::{
+    json::{self, JsonError},
+    model::{Item, Message, OrderMessage},
+};
+use proptest::prelude::*;
+use sales_generated::order_type::OrderType;
+use serde_json::{Value, json};
+
+fn golden(name: &str) -> Vec<u8> {
+    let path = format!("{}/tests/golden/{name}.hex", env!("CARGO_MANIFEST_DIR"));
+    let hex = std::fs::read_to_string(path).unwrap();
+    let hex = hex.trim();
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn two_items() -> Value {
+    json!({
+        "type": "OrderMessage",
+        "orderId": 234,
+        "clientId": 135,
+        "timestamp": 246,
+        "orderType": "New",
+        "orderFlags": ["GiftWrap"],
+        "items": [
+            {
+                "productId": 222,
+                "quantity": 2,
+                "unitPrice": "2.34",
+                "currency": "EUR",
+                "sku": "SKU-222",
+                "discounts": [{ "code": 7, "amount": "0.50" }, { "code": 9, "amount": "-0.01" }],
+                "itemNote": "gift"
+            },
+            {
+                "productId": 111,
+                "quantity": 3,
+                "unitPrice": "1.23",
+                "currency": "",
+                "sku": "",
+                "discounts": [],
+                "itemNote": ""
+            }
+        ],
+        "customerNote": "duck is angry"
+    })
+}
+
+/// `two_items` with `field` of the first item set to `value`
+fn with_item_field(field: &str, value: Value) -> Value {
+    let mut order = two_items();
+    order["items"][0][field] = value;
+    order
+}
+
+fn error(value: &Value) -> String {
+    json::to_frame(value).unwrap_err().to_string()
+}
+
+#[test]
+fn encodes_golden_frames() {
+    assert_eq!(
+        golden("order_message_two_items"),
+        json::to_frame(&two_items()).unwrap()
+    );
+    assert_eq!(
+        two_items(),
+        json::from_frame(&golden("order_message_two_items")).unwrap()
+    );
+
+    let response = golden("order_response_partially_filled");
+    let value = json::from_frame(&response).unwrap();
+    assert_eq!("PartiallyFilled", value["status"]);
+    assert_eq!("-92233720368547758.07", value["fillPrice"]);
+    assert_eq!(response, json::to_frame(&value).unwrap());
+}
+
+#[test]
+fn optional_fields_default_to_empty() {
+    let value = json!({
+        "type": "OrderMessage",
+        "orderId": 1,
+        "clientId": 2,
+        "timestamp": 3,
+        "orderType": null,
+        "items": [{ "productId": 4, "quantity": 5, "unitPrice": 6.1 }]
+    });
+    let Message::Order(order) = json::to_message(&value).unwrap() else {
+        panic!("not an order");
+    };
+    assert_eq!(
+        OrderMessage {
+            order_id: 1,
+            client_id: 2,
+            timestamp: 3,
+            order_type: OrderType::NullVal,
+            items: vec![Item {
+                product_id: 4,
+                quantity: 5,
+                unit_price: 610,
+                ..Item::default()
+            }],
+            ..OrderMessage::default()
+        },
+        order
+    );
+    let decoded = json::from_frame(&json::to_frame(&value).unwrap()).unwrap();
+    assert_eq!(Value::Null, decoded["orderType"]);
+    assert_eq!("6.10", decoded["items"][0]["unitPrice"]);
+}
+
+#[test]
+fn prices_are_decimals_with_two_fraction_digits() {
+    for (price, mantissa) in [
+        (json!("2.34"), 234),
+        (json!(" 2.3 "), 230),
+        (json!("2.340"), 234),
+        (json!("-0.05"), -5),
+        (json!(7), 700),
+        (json!(0.1), 10),
+        (json!("92233720368547758.07"), i64::MAX),
+    ] {
+        let Message::Order(order) =
+            json::to_message(&with_item_field("unitPrice", price.clone())).unwrap()
+        else {
+            panic!("not an order");
+        };
+        assert_eq!(mantissa, order.items[0].unit_price, "{price}");
+    }
+
+    for price in [
+        json!("2.345"),
+        json!("abc"),
+        json!("1e3"),
+        json!(""),
+        json!(".5"),
+        json!(2.345),
+    ] {
+        let err = json::to_frame(&with_item_field("unitPrice", price.clone())).unwrap_err();
+        assert!(
+            matches!(err, JsonError::NotDecimal { .. }),
+            "{price}: {err}"
+        );
+    }
+    assert_eq!(
+        "items[0].unitPrice: 2.345 is not a decimal with at most 2 fraction digits",
+        error(&with_item_field("unitPrice", json!("2.345")))
+    );
+    assert_eq!(
+        "items[0].unitPrice: 92233720368547758.08 is out of range \
+         -92233720368547758.07..=92233720368547758.07",
+        error(&with_item_field("unitPrice", json!("92233720368547758.08")))
+    );
+    assert_eq!(
+        "items[0].unitPrice: expected a decimal",
+        error(&with_item_field("unitPrice", json!(true)))
+    );
+}
+
+#[test]
+fn numeric_prices_are_read_exactly() {
+    // neither number is exact in f64
+    let price = |text: &str| {
+        let number: Value = serde_json::from_str(text).unwrap();
+        json::to_message(&with_item_field("unitPrice", number))
+    };
+    let Ok(Message::Order(order)) = price("12345678901234567.89") else {
+        panic!("not an order");
+    };
+    assert_eq!(1_234_567_890_123_456_789, order.items[0].unit_price);
+    assert!(matches!(
+        price("100000000000000.001"),
+        Err(JsonError::NotDecimal { .. })
+    ));
+}
+
+#[test]
+fn rejects_integers_out_of_range() {
+    // 65535 is the null value of uint16
+    assert_eq!(
+        "items[0].quantity: 65535 is out of range 0..=65534",
+        error(&with_item_field("quantity", json!(65_535)))
+    );
+    assert_eq!(
+        "items[0].quantity: -1 is out of range 0..=65534",
+        error(&with_item_field("quantity", json!(-1)))
+    );
+    assert_eq!(
+        "items[0].quantity: expected an integer",
+        error(&with_item_field("quantity", json!(1.5)))
+    );
+    assert_eq!(
+        "items[0].quantity: expected an integer",
+        error(&with_item_field("quantity", json!("2")))
+    );
+    assert!(json::to_frame(&with_item_field("quantity", json!(65_534))).is_ok());
+
+    let mut order = two_items();
+    order["orderId"] = json!(u64::MAX);
+    assert_eq!(
+        "orderId: 18446744073709551615 is out of range 0..=18446744073709551614",
+        error(&order)
+    );
+    order["orderId"] = json!(u64::MAX - 1);
+    assert!(json::to_frame(&order).is_ok());
+}
+
+#[test]
+fn rejects_missing_and_unknown_fields() {
+    let mut order = two_items();
+    order["items"][1]
+        .as_object_mut()
+        .unwrap()
+        .remove("productId");
+    assert_eq!("items[1].productId: missing", error(&order));
+
+    let mut order = two_items();
+    order.as_object_mut().unwrap().remove("type");
+    assert_eq!("type: missing", error(&order));
+
+    let mut order = two_items();
+    order["items"][0]["discounts"][1]["percent"] = json!(5);
+    assert_eq!(
+        "items[0].discounts[1].percent: unknown field",
+        error(&order)
+    );
+
+    let mut order = two_items();
+    order["type"] = json!("Quote");
+    assert_eq!(
+        "type: unknown message \"Quote\", expected OrderMessage or OrderResponse",
+        error(&order)
+    );
+    assert_eq!("$: expected an object", error(&json!([])));
+    assert_eq!(
+        "items[0]: expected an object",
+        error(&json!({
+            "type": "OrderMessage", "orderId": 1, "clientId": 2, "timestamp": 3,
+            "orderType": "New", "items": [7]
+        }))
+    );
+}
+
+#[test]
+fn rejects_unknown_names() {
+    let mut order = two_items();
+    order["orderType"] = json!("Amend");
+    assert_eq!(
+        "orderType: unknown name \"Amend\", expected one of New, Update, Cancel",
+        error(&order)
+    );
+    order["orderType"] = json!("new");
+    assert!(matches!(
+        json::to_frame(&order),
+        Err(JsonError::UnknownName { .. })
+    ));
+
+    let mut order = two_items();
+    order["orderFlags"] = json!(["IOC", "Urgent"]);
+    assert_eq!(
+        "orderFlags[1]: unknown name \"Urgent\", expected one of IOC, PostOnly, GiftWrap, Expedited",
+        error(&order)
+    );
+
+    let response = json!({
+        "type": "OrderResponse", "orderId": 1, "timestamp": 2, "status": "Done",
+        "filledQty": 3, "fillPrice": "4.00"
+    });
+    assert_eq!(
+        "status: unknown name \"Done\", expected one of Accepted, Rejected, Filled, PartiallyFilled",
+        error(&response)
+    );
+}
+
+#[test]
+fn rejects_text_the_fields_cannot_hold() {
+    assert_eq!(
+        "items[0].currency: length 4 is longer than 3",
+        error(&with_item_field("currency", json!("EURO")))
+    );
+    assert_eq!(
+        "items[0].sku: not printable US-ASCII",
+        error(&with_item_field("sku", json!("SKU-ä")))
+    );
+    assert_eq!(
+        "items[0].currency: not printable US-ASCII",
+        error(&with_item_field("currency", json!("A\0B")))
+    );
+    assert_eq!(
+        "items[0].itemNote: length 65535 is longer than 65534",
+        error(&with_item_field("itemNote", json!("x".repeat(65_535))))
+    );
+    assert!(json::to_frame(&with_item_field("itemNote", json!("ü".repeat(32_767)))).is_ok());
+}
+
+#[test]
+fn reports_frames_it_cannot_decode() {
+    let frame = golden("order_message_two_items");
+    let err = json::from_frame(&frame[..frame.len() - 1]).unwrap_err();
+    assert!(matches!(err, JsonError::Decode(_)), "{err}");
+}
+
+#[test]
+fn cli_round_trips() {
+    let run = |command: &str, input: &str| {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_sales_json"))
+            .arg(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(input.as_bytes())
+            .unwrap();
+        let output = child.wait_with_output().unwrap();
+        (
+            output.status.code(),
+            String::from_utf8(output.stdout).unwrap(),
+            String::from_utf8(output.stderr).unwrap(),
+        )
+    };
+
+    let input = format!("{}\n[{}]", two_items(), two_items());
+    let (status, hex, _) = run("encode", &input);
+    assert_eq!(Some(0), status);
+    let golden_hex = std::fs::read_to_string(format!(
+        "{}/tests/golden/order_message_two_items.hex",
+        env!("CARGO_MANIFEST_DIR")
+    ))
+    .unwrap();
+    assert_eq!(format!("{0}\n{0}\n", golden_hex.trim()), hex);
+
+    let (status, decoded, _) = run("decode", &hex);
+    assert_eq!(Some(0), status);
+    assert_eq!(format!("{0}\n{0}\n", two_items()), decoded);
+
+    let (status, _, stderr) = run("decode", "00\nzz\n");
+    assert_eq!(Some(1), status);
+    assert_eq!(
+        "line 1: cannot decode frame: frame shorter than message header\nline 2: not hex\n",
+        stderr
+    );
+    let (status, _, _) = run("transcode", "");
+    assert_eq!(Some(2), status);
+}
+
+proptest! {
+    #[test]
+    fn decimals_round_trip(mantissa in (i64::MIN + 1)..=i64::MAX) {
+        let order = Message::Order(OrderMessage {
+            items: vec![Item {
+                unit_price: mantissa,
+                ..Item::default()
+            }],
+            ..OrderMessage::default()
+        });
+        prop_assert_eq!(&order, &json::to_message(&json::from_message(&order)).unwrap());
+    }
+}
@@ -0,0 +1,223 @@
+use bytes::Bytes;
+use my_sales_app::{
+    dispatch::{self, DispatchError},
+    model::{Discount, Item, Message, OrderMessage},
+};
+use sales_generated::{
+    Decoder, Encoder, ReadBuf, Reader, SbeErr, WriteBuf,
+    message_header_codec::{self, MessageHeaderDecoder},
+    order_message_codec::{
+        OrderMessageDecoder, OrderMessageEncoder, encoder::ItemsEncoder,
+        shared::OrderMessageBytesDecoder,
+    },
+};
+
+fn order() -> OrderMessage {
+    OrderMessage {
+        order_id: 234,
+        items: vec![
+            Item {
+                product_id: 1,
+                discounts: vec![
+                    Discount {
+                        code: 10,
+                        amount: 50,
+                    },
+                    Discount {
+                        code: 11,
+                        amount: 25,
+                    },
+                ],
+                item_note: "gift".to_string(),
+                ..Item::default()
+            },
+            Item {
+                product_id: 2,
+                ..Item::default()
+            },
+            Item {
+                product_id: 3,
+                discounts: vec![Discount {
+                    code: 30,
+                    amount: -1,
+                }],
+                item_note: "fragile €".to_string(),
+                ..Item::default()
+            },
+        ],
+        customer_note: "duck is angry".to_string(),
+        ..OrderMessage::default()
+    }
+}
+
+fn frame(order: &OrderMessage) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    let len = order.encode_to(&mut buffer);
+    assert_eq!(len, buffer.len());
+    buffer
+}
+
+fn decoder(frame: &[u8]) -> OrderMessageDecoder<'_> {
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(frame), 0);
+    OrderMessageDecoder::default().header(header, 0)
+}
+
+#[test]
+fn entries_stay_aligned_when_nested_parts_are_skipped() {
+    let frame = frame(&order());
+    let mut items = decoder(&frame).items_decoder();
+    let mut product_ids = Vec::new();
+    while items.advance().unwrap().is_some() {
+        product_ids.push(items.product_id());
+    }
+    assert_eq!(vec![1, 2, 3], product_ids);
+
+    let mut order = items.parent().unwrap();
+    let (offset, len) = order.customer_note_decoder();
+    assert_eq!(b"duck is angry", order.get_buf().get_slice_at(offset, len));
+    assert_eq!(frame.len(), order.get_limit());
+}
+
+#[test]
+fn discounts_read_in_part() {
+    let frame = frame(&order());
+    let mut items = decoder(&frame).items_decoder_checked().unwrap();
+    let (mut codes, mut notes) = (Vec::new(), Vec::new());
+    while items.advance().unwrap().is_some() {
+        // only the first discount of each item
+        let mut discounts = items.discounts_decoder_checked().unwrap();
+        if discounts.advance().unwrap().is_some() {
+            codes.push(discounts.code());
+        }
+        items = discounts.parent().unwrap();
+        let (offset, len) = items.item_note_decoder_checked().unwrap();
+        notes.push(items.get_buf().get_slice_at(offset, len).to_vec());
+    }
+    assert_eq!(vec![10, 30], codes);
+    assert_eq!(
+        vec![
+            b"gift".to_vec(),
+            Vec::new(),
+            "fragile €".as_bytes().to_vec()
+        ],
+        notes
+    );
+
+    let mut order = items.parent().unwrap();
+    let (offset, len) = order.customer_note_decoder_checked().unwrap();
+    assert_eq!(b"duck is angry", order.get_buf().get_slice_at(offset, len));
+}
+
+#[test]
+fn item_note_without_reading_discounts() {
+    let frame = frame(&order());
+    let mut items = decoder(&frame).items_decoder();
+    let mut notes = Vec::new();
+    while items.advance().unwrap().is_some() {
+        let (offset, len) = items.item_note_decoder();
+        notes.push(items.get_buf().get_slice_at(offset, len).to_vec());
+    }
+    assert_eq!(b"gift", notes[0].as_slice());
+    assert!(notes[1].is_empty());
+    assert_eq!("fragile €".as_bytes(), notes[2].as_slice());
+}
+
+#[test]
+fn bytes_decoder_skips_nested_groups() {
+    let decoder = OrderMessageBytesDecoder::header(Bytes::from(frame(&order())), 0).unwrap();
+    let mut items = decoder.items_decoder();
+    let mut product_ids = Vec::new();
+    while items.advance().is_some() {
+        product_ids.push(items.product_id());
+    }
+    assert_eq!(vec![1, 2, 3], product_ids);
+
+    let mut items = decoder.items_decoder();
+    items.advance();
+    items.advance();
+    items.advance();
+    assert_eq!("fragile €".as_bytes(), items.item_note());
+    let mut discounts = items.discounts_decoder();
+    assert_eq!(Some(0), discounts.advance());
+    assert_eq!(-1, discounts.amount_decoder().mantissa());
+    assert_eq!(None, discounts.advance());
+    assert_eq!(&b"duck is angry"[..], decoder.customer_note());
+}
+
+#[test]
+fn encoder_writes_nested_parts_left_out_as_empty() {
+    let mut buffer = [0u8; 256];
+    let mut order = OrderMessageEncoder::default().wrap(
+        WriteBuf::new(&mut buffer),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    order = order.header(0).parent().unwrap();
+    let mut items = order.items_encoder(3, ItemsEncoder::default());
+    // neither discounts nor note
+    let _r = items.advance();
+    items.product_id(1);
+    // note without discounts
+    let _r = items.advance();
+    items.product_id(2);
+    items.item_note("gift");
+    // nothing at all, closed by parent
+    let _r = items.advance();
+    items.product_id(3);
+    order = items.parent().unwrap();
+    order.customer_note("done");
+    let len = order.get_limit();
+
+    assert_eq!(
+        OrderMessageEncoder::compute_length([(0, 0), (0, 4), (0, 0)], 4),
+        len
+    );
+    let Message::Order(decoded) = Message::decode_checked(&buffer[..len]).unwrap() else {
+        panic!("expected OrderMessage");
+    };
+    let ids: Vec<u32> = decoded.items.iter().map(|item| item.product_id).collect();
+    assert_eq!(vec![1, 2, 3], ids);
+    assert!(decoded.items.iter().all(|item| item.discounts.is_empty()));
+    assert_eq!("gift", decoded.items[1].item_note);
+    assert_eq!("done", decoded.customer_note);
+}
+
+#[test]
+fn rejects_malformed_nested_groups() {
+    let order = OrderMessage {
+        items: order().items[..1].to_vec(),
+        ..order()
+    };
+    let frame = frame(&order);
+    // header, block, items dimension, first item block
+    let discounts_offset = 8 + 26 + 4 + 33;
+    let item_note_offset = discounts_offset + 4 + 2 * 10;
+    let check = |frame: &[u8]| {
+        let checked = Message::decode_checked(frame).map(|_| ());
+        assert_eq!(checked, dispatch::frame_length(frame).map(|_| ()));
+        let bytes = OrderMessageBytesDecoder::header(Bytes::copy_from_slice(frame), 0);
+        assert_eq!(checked, bytes.map(|_| ()).map_err(DispatchError::Malformed));
+        checked
+    };
+    assert_eq!(Ok(()), check(&frame));
+
+    let mut too_many_discounts = frame.clone();
+    too_many_discounts[discounts_offset + 2] = 200;
+    assert_eq!(
+        Err(DispatchError::Malformed(SbeErr::BufferTooShort)),
+        check(&too_many_discounts)
+    );
+
+    let mut short_discount_block = frame.clone();
+    short_discount_block[discounts_offset] = 9;
+    assert_eq!(
+        Err(DispatchError::Malformed(SbeErr::BlockLengthTooShort)),
+        Message::decode_checked(&short_discount_block).map(|_| ())
+    );
+
+    let mut long_item_note = frame.clone();
+    long_item_note[item_note_offset + 1] = 0xff;
+    assert_eq!(
+        Err(DispatchError::Malformed(SbeErr::BufferTooShort)),
+        check(&long_item_note)
+    );
+}
@@ -0,0 +1,20 @@
+use sales_generated::order_flags::OrderFlags;
+
+#[test]
+fn displays_the_choices_that_are_set() {
+    assert_eq!("", OrderFlags::default().to_string());
+    assert_eq!("IOC", OrderFlags::default().set_ioc(true).to_string());
+    assert_eq!(
+        "PostOnly|GiftWrap",
+        OrderFlags::default()
+            .set_gift_wrap(true)
+            .set_post_only(true)
+            .to_string()
+    );
+    assert_eq!(
+        "IOC|PostOnly|GiftWrap|Expedited",
+        OrderFlags::new(0xff).to_string()
+    );
+    // bits 4 to 7 are not part of the schema
+    assert_eq!("Expedited", OrderFlags::new(0b1000_1000).to_string());
+}
@@ -0,0 +1,223 @@
+use std::fs::{self, OpenOptions};
+
+use my_sales_app::{
+    journal::{Journal, JournalConfig, JournalError, RecordPosition},
+    model::{Item, Message, OrderMessage, OrderResponse},
+    order_index::{self, OrderIndex},
+};
+use sales_generated::{order_flags::OrderFlags, order_status::OrderStatus, order_type::OrderType};
+
+fn order(order_id: u64, order_type: OrderType) -> OrderMessage {
+    OrderMessage {
+        order_id,
+        client_id: 135,
+        timestamp: order_id,
+        order_type,
+        order_flags: OrderFlags::default(),
+        items: vec![Item {
+            product_id: 222,
+            quantity: 2,
+            unit_price: 234,
+            ..Item::default()
+        }],
+        customer_note: format!("order {order_id}"),
+    }
+}
+
+fn response(order_id: u64, status: OrderStatus) -> OrderResponse {
+    OrderResponse {
+        order_id,
+        timestamp: order_id + 1,
+        status,
+        filled_qty: 0,
+        fill_price: 0,
+        server_note: String::new(),
+    }
+}
+
+fn append(journal: &mut Journal, message: Message) {
+    let mut buffer = [0u8; 256];
+    let len = message.encode(&mut buffer);
+    journal.append(&buffer[..len], 0).unwrap();
+}
+
+/// New and Accepted for every order id in `order_ids`, interleaved across orders.
+fn fill(journal: &mut Journal, order_ids: std::ops::Range<u64>) {
+    for order_id in order_ids.clone() {
+        append(journal, Message::Order(order(order_id, OrderType::New)));
+    }
+    for order_id in order_ids {
+        append(
+            journal,
+            Message::Response(response(order_id, OrderStatus::Accepted)),
+        );
+    }
+}
+
+fn config() -> JournalConfig {
+    JournalConfig {
+        segment_size: 4096,
+        sync_on_append: false,
+    }
+}
+
+/// Cuts the journal back to the record at `position`, like a crash losing the unsynced tail.
+fn lose_tail(dir: &std::path::Path, position: RecordPosition) {
+    let segment = dir.join(format!("{:020}.journal", position.segment));
+    let file = OpenOptions::new().write(true).open(segment).unwrap();
+    file.set_len(position.offset).unwrap();
+}
+
+fn sequences(index: &OrderIndex, order_id: u64) -> Vec<u64> {
+    index
+        .lookup(order_id)
+        .unwrap()
+        .iter()
+        .map(|m| m.sequence)
+        .collect()
+}
+
+#[test]
+fn lookup_returns_order_history() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut journal = Journal::open(dir.path(), config()).unwrap();
+    fill(&mut journal, 200..300);
+    append(&mut journal, Message::Order(order(234, OrderType::Cancel)));
+
+    let mut index = OrderIndex::open(dir.path()).unwrap();
+    assert_eq!(201, index.catch_up().unwrap());
+    assert_eq!(202, index.next_sequence());
+
+    let history = index.lookup(234).unwrap();
+    let messages: Vec<Message> = history.iter().map(|m| m.message.clone()).collect();
+    assert_eq!(
+        vec![
+            Message::Order(order(234, OrderType::New)),
+            Message::Response(response(234, OrderStatus::Accepted)),
+            Message::Order(order(234, OrderType::Cancel)),
+        ],
+        messages
+    );
+    assert_eq!(
+        vec![35, 135, 201],
+        history.iter().map(|m| m.sequence).collect::<Vec<_>>()
+    );
+
+    assert!(index.lookup(1).unwrap().is_empty());
+}
+
+#[test]
+fn catch_up_is_incremental_and_persistent() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut journal = Journal::open(dir.path(), config()).unwrap();
+    fill(&mut journal, 0..10);
+
+    let mut index = OrderIndex::open(dir.path()).unwrap();
+    assert_eq!(20, index.catch_up().unwrap());
+    assert_eq!(0, index.catch_up().unwrap());
+    index.flush().unwrap();
+    drop(index);
+
+    append(
+        &mut journal,
+        Message::Response(response(3, OrderStatus::Filled)),
+    );
+
+    let mut index = OrderIndex::open(dir.path()).unwrap();
+    assert_eq!(20, index.len());
+    assert_eq!(1, index.catch_up().unwrap());
+    let statuses: Vec<_> = index
+        .lookup(3)
+        .unwrap()
+        .into_iter()
+        .filter_map(|m| match m.message {
+            Message::Response(r) => Some(r.status),
+            Message::Order(_) => None,
+        })
+        .collect();
+    assert_eq!(vec![OrderStatus::Accepted, OrderStatus::Filled], statuses);
+}
+
+#[test]
+fn grows_and_rebuilds_heads() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut journal = Journal::open(dir.path(), config()).unwrap();
+    fill(&mut journal, 1..3001);
+
+    let mut index = OrderIndex::open(dir.path()).unwrap();
+    assert_eq!(6000, index.catch_up().unwrap());
+    let expected = index.entries(2999);
+    assert_eq!(2, expected.len());
+    assert_eq!(2999, expected[0].sequence);
+    assert_eq!(5999, expected[1].sequence);
+    drop(index);
+
+    fs::remove_file(dir.path().join(order_index::HEADS_FILE)).unwrap();
+    let index = OrderIndex::open(dir.path()).unwrap();
+    assert_eq!(expected, index.entries(2999));
+    assert_eq!(1, index.lookup(1).unwrap()[0].message.order_id());
+}
+
+#[test]
+fn drops_entries_the_journal_lost() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut journal = Journal::open(dir.path(), config()).unwrap();
+    fill(&mut journal, 0..5);
+    let mut index = OrderIndex::open(dir.path()).unwrap();
+    assert_eq!(10, index.catch_up().unwrap());
+    index.flush().unwrap();
+    let accepted = index.entries(3)[1].position;
+    drop(index);
+    drop(journal);
+
+    // the Accepted of orders 3 and 4 are lost, their sequences go to new orders
+    lose_tail(dir.path(), accepted);
+    let mut journal = Journal::open(dir.path(), config()).unwrap();
+    assert_eq!(9, journal.next_sequence());
+    for order_id in 100..103 {
+        append(
+            &mut journal,
+            Message::Order(order(order_id, OrderType::New)),
+        );
+    }
+
+    let mut index = OrderIndex::open(dir.path()).unwrap();
+    assert_eq!(8, index.len());
+    assert_eq!(3, index.catch_up().unwrap());
+    assert_eq!(vec![4], sequences(&index, 3));
+    assert_eq!(vec![9], sequences(&index, 100));
+    assert_eq!(vec![11], sequences(&index, 102));
+}
+
+#[test]
+fn lookup_rejects_replaced_records() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut journal = Journal::open(dir.path(), config()).unwrap();
+    fill(&mut journal, 0..5);
+    let mut index = OrderIndex::open(dir.path()).unwrap();
+    index.catch_up().unwrap();
+    drop(journal);
+
+    // the journal loses its tail while the index is open
+    lose_tail(dir.path(), index.entries(3)[1].position);
+    let mut journal = Journal::open(dir.path(), config()).unwrap();
+    append(&mut journal, Message::Order(order(100, OrderType::New)));
+    assert!(matches!(
+        index.lookup(3),
+        Err(JournalError::StaleIndex { sequence: 9 })
+    ));
+    // the record of order 4 is gone, its position is past the end of the journal
+    assert!(matches!(index.lookup(4), Err(JournalError::Corrupt { .. })));
+}
+
+#[test]
+fn open_locks_the_index() {
+    let dir = tempfile::tempdir().unwrap();
+    let index = OrderIndex::open(dir.path()).unwrap();
+    assert!(matches!(
+        OrderIndex::open(dir.path()),
+        Err(JournalError::Io(e)) if e.kind() == std::io::ErrorKind::WouldBlock
+    ));
+    drop(index);
+    OrderIndex::open(dir.path()).unwrap();
+}
@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+
+use my_sales_app::{
+    dispatch::DispatchError,
+    model::{Item, OrderMessage},
+    order_manager::{OrderManager, OrderState, Rejection},
+};
+use proptest::prelude::*;
+use sales_generated::{
+    ReadBuf, SbeErr, message_header_codec::MessageHeaderDecoder, order_flags::OrderFlags,
+    order_message_codec::OrderMessageDecoder, order_status::OrderStatus, order_type::OrderType,
+};
+
+fn order(order_id: u64, order_type: OrderType, quantities: &[u16]) -> OrderMessage {
+    OrderMessage {
+        order_id,
+        client_id: 135,
+        timestamp: 1,
+        order_type,
+        order_flags: OrderFlags::default(),
+        items: quantities
+            .iter()
+            .enumerate()
+            .map(|(i, &quantity)| Item {
+                product_id: i as u32,
+                quantity,
+                unit_price: 100,
+                ..Item::default()
+            })
+            .collect(),
+        customer_note: String::new(),
+    }
+}
+
+#[test]
+fn order_lifecycle() {
+    let mut manager = OrderManager::new();
+
+    let accepted = manager.apply(&order(234, OrderType::New, &[2, 3]), 10);
+    assert_eq!(OrderStatus::Accepted, accepted.status);
+    assert_eq!(10, accepted.timestamp);
+
+    let partial = manager.fill(234, 2, 250, 11).unwrap();
+    assert_eq!(OrderStatus::PartiallyFilled, partial.status);
+    assert_eq!((2, 250), (partial.filled_qty, partial.fill_price));
+
+    let updated = manager.apply(&order(234, OrderType::Update, &[4]), 12);
+    assert_eq!(OrderStatus::Accepted, updated.status);
+    assert_eq!(2, updated.filled_qty);
+    assert_eq!(
+        Err(Rejection::InvalidFillQuantity),
+        manager.fill(234, 3, 250, 13)
+    );
+
+    let filled = manager.fill(234, 2, 260, 14).unwrap();
+    assert_eq!(OrderStatus::Filled, filled.status);
+    assert_eq!(4, filled.filled_qty);
+    assert_eq!(OrderState::Filled, manager.order(234).unwrap().state);
+
+    let rejected = manager.apply(&order(234, OrderType::Cancel, &[]), 15);
+    assert_eq!(OrderStatus::Rejected, rejected.status);
+    assert_eq!("order filled", rejected.server_note);
+}
+
+#[test]
+fn rejects_illegal_transitions() {
+    let mut manager = OrderManager::new();
+    let note = |manager: &mut OrderManager, message: OrderMessage| {
+        let response = manager.apply(&message, 0);
+        assert_eq!(OrderStatus::Rejected, response.status);
+        response.server_note
+    };
+
+    assert_eq!(
+        "unknown order",
+        note(&mut manager, order(1, OrderType::Update, &[1]))
+    );
+    assert_eq!(
+        "unknown order",
+        note(&mut manager, order(1, OrderType::Cancel, &[]))
+    );
+    assert_eq!(
+        "order has no quantity",
+        note(&mut manager, order(1, OrderType::New, &[0]))
+    );
+    assert_eq!(
+        "unknown order type",
+        note(&mut manager, order(1, OrderType::NullVal, &[1]))
+    );
+
+    manager.apply(&order(1, OrderType::New, &[1]), 0);
+    assert_eq!(
+        "duplicate order",
+        note(&mut manager, order(1, OrderType::New, &[1]))
+    );
+    manager.apply(&order(1, OrderType::Cancel, &[]), 0);
+    assert_eq!(
+        "order cancelled",
+        note(&mut manager, order(1, OrderType::Update, &[2]))
+    );
+    assert_eq!(Err(Rejection::OrderCancelled), manager.fill(1, 1, 0, 0));
+}
+
+#[test]
+fn applies_wire_messages() {
+    let mut buffer = [0u8; 128];
+    let len = order(7, OrderType::New, &[5]).encode(&mut buffer);
+
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(&buffer[..len]), 0);
+    let decoder = OrderMessageDecoder::default().header(header, 0);
+
+    let mut manager = OrderManager::new();
+    assert_eq!(
+        OrderStatus::Accepted,
+        manager.on_order_message(decoder, 3).unwrap().status
+    );
+    assert_eq!(5, manager.order(7).unwrap().total_qty());
+
+    // the item is cut off, the order is not created
+    let len = order(8, OrderType::New, &[5]).encode(&mut buffer);
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(&buffer[..len - 10]), 0);
+    let decoder = OrderMessageDecoder::default().header(header, 0);
+    assert_eq!(
+        Err(DispatchError::Malformed(SbeErr::BufferTooShort)),
+        manager.on_order_message(decoder, 4)
+    );
+    assert_eq!(None, manager.order(8));
+}
+
+#[derive(Clone, Debug)]
+enum Op {
+    Order(u64, OrderType, Vec<u16>),
+    Fill(u64, u32),
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    let order_type = prop_oneof![
+        4 => Just(OrderType::New),
+        3 => Just(OrderType::Update),
+        1 => Just(OrderType::Cancel),
+        1 => Just(OrderType::NullVal),
+    ];
+    prop_oneof![
+        (0..6u64, order_type, prop::collection::vec(0..4u16, 0..3))
+            .prop_map(|(id, order_type, quantities)| Op::Order(id, order_type, quantities)),
+        (0..6u64, 0..5u32).prop_map(|(id, qty)| Op::Fill(id, qty)),
+    ]
+}
+
+/// Minimal reference of the expected behaviour: total, filled and state per order.
+#[derive(Default)]
+struct Reference(HashMap<u64, (u32, u32, OrderState)>);
+
+impl Reference {
+    fn live(&self, id: u64) -> Result<(u32, u32), Rejection> {
+        match self.0.get(&id) {
+            None => Err(Rejection::UnknownOrder),
+            Some((_, _, OrderState::Filled)) => Err(Rejection::OrderFilled),
+            Some((_, _, OrderState::Cancelled)) => Err(Rejection::OrderCancelled),
+            Some(&(total, filled, _)) => Ok((total, filled)),
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn random_sequences_follow_state_machine(ops in prop::collection::vec(op(), 0..64)) {
+        let mut manager = OrderManager::new();
+        let mut reference = Reference::default();
+
+        for op in ops {
+            match op {
+                Op::Order(id, order_type, quantities) => {
+                    let total: u32 = quantities.iter().map(|&q| q as u32).sum();
+                    let response = manager.apply(&order(id, order_type, &quantities), 0);
+                    prop_assert_eq!(id, response.order_id);
+                    let expected = match order_type {
+                        OrderType::New if reference.0.contains_key(&id) => Err(Rejection::DuplicateOrder),
+                        OrderType::New if total == 0 => Err(Rejection::EmptyOrder),
+                        OrderType::New => {
+                            reference.0.insert(id, (total, 0, OrderState::Open));
+                            Ok(OrderStatus::Accepted)
+                        }
+                        OrderType::Update => reference.live(id).and_then(|(_, filled)| {
+                            if total == 0 {
+                                return Err(Rejection::EmptyOrder);
+                            }
+                            if total < filled {
+                                return Err(Rejection::QuantityBelowFilled);
+                            }
+                            let entry = reference.0.get_mut(&id).unwrap();
+                            entry.0 = total;
+                            if total == filled {
+                                entry.2 = OrderState::Filled;
+                                return Ok(OrderStatus::Filled);
+                            }
+                            Ok(OrderStatus::Accepted)
+                        }),
+                        OrderType::Cancel => reference.live(id).map(|_| {
+                            reference.0.get_mut(&id).unwrap().2 = OrderState::Cancelled;
+                            OrderStatus::Accepted
+                        }),
+                        OrderType::NullVal => Err(Rejection::UnknownOrderType),
+                    };
+                    match expected {
+                        Ok(status) => prop_assert_eq!(status, response.status),
+                        Err(rejection) => {
+                            prop_assert_eq!(OrderStatus::Rejected, response.status);
+                            prop_assert_eq!(rejection.as_str(), response.server_note);
+                        }
+                    }
+                }
+                Op::Fill(id, qty) => {
+                    let result = manager.fill(id, qty, 100, 0);
+                    let expected = reference.live(id).and_then(|(total, filled)| {
+                        if qty == 0 || qty > total - filled {
+                            return Err(Rejection::InvalidFillQuantity);
+                        }
+                        let entry = reference.0.get_mut(&id).unwrap();
+                        entry.1 += qty;
+                        entry.2 = if entry.1 == total { OrderState::Filled } else { OrderState::PartiallyFilled };
+                        Ok(entry.1)
+                    });
+                    match (result, expected) {
+                        (Ok(response), Ok(filled)) => {
+                            prop_assert_eq!(filled, response.filled_qty);
+                            let status = if manager.order(id).unwrap().open_qty() == 0 {
+                                OrderStatus::Filled
+                            } else {
+                                OrderStatus::PartiallyFilled
+                            };
+                            prop_assert_eq!(status, response.status);
+                        }
+                        (result, expected) => prop_assert_eq!(expected.map(|_| ()), result.map(|_| ())),
+                    }
+                }
+            }
+
+            for (id, &(total, filled, state)) in &reference.0 {
+                let order = manager.order(*id).unwrap();
+                prop_assert_eq!((total, filled, state), (order.total_qty(), order.filled_qty, order.state));
+                prop_assert!(order.filled_qty <= order.total_qty());
+            }
+            prop_assert_eq!(reference.0.len(), manager.len());
+        }
+    }
+}
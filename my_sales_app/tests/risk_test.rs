@@ -0,0 +1,188 @@
+use std::collections::{HashMap, HashSet};
+
+use my_sales_app::{
+    model::{Item, Message, OrderMessage},
+    risk::{
+        AllowedProducts, ClientLimits, MaxItems, MaxNotional, MaxQuantity, RiskChain, RiskCheck,
+        RiskConfig,
+    },
+};
+use sales_generated::{
+    ReadBuf, message_header_codec::MessageHeaderDecoder, order_flags::OrderFlags,
+    order_message_codec::OrderMessageDecoder, order_status::OrderStatus, order_type::OrderType,
+};
+
+const CLIENT: u64 = 135;
+
+fn order(client_id: u64, items: &[(u32, u16, i64)]) -> Vec<u8> {
+    let order = OrderMessage {
+        order_id: 234,
+        client_id,
+        timestamp: 0,
+        order_type: OrderType::New,
+        order_flags: OrderFlags::default(),
+        items: items
+            .iter()
+            .map(|&(product_id, quantity, unit_price)| Item {
+                product_id,
+                quantity,
+                unit_price,
+                ..Item::default()
+            })
+            .collect(),
+        customer_note: String::new(),
+    };
+    let mut buffer = [0u8; 256];
+    let len = order.encode(&mut buffer);
+    buffer[..len].to_vec()
+}
+
+fn decoder(frame: &[u8]) -> OrderMessageDecoder<'_> {
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(frame), 0);
+    OrderMessageDecoder::default().header(header, 0)
+}
+
+fn chain() -> RiskChain {
+    let limits = ClientLimits {
+        max_items: 2,
+        max_quantity: 10,
+        max_notional: 10_000,
+        allowed_products: Some(HashSet::from([1, 2, 3])),
+        max_messages_per_second: 3,
+    };
+    RiskChain::standard(RiskConfig {
+        default_limits: ClientLimits::default(),
+        client_limits: HashMap::from([(CLIENT, limits)]),
+    })
+}
+
+fn rule(chain: &mut RiskChain, frame: &[u8], now: u64) -> Option<&'static str> {
+    chain.evaluate(&decoder(frame), now).err().map(|r| r.rule)
+}
+
+#[test]
+fn each_rule_rejects() {
+    let mut chain = chain();
+    let mut now = 0;
+    let mut next = |chain: &mut RiskChain, frame: Vec<u8>| {
+        // one message per second stays under the rate limit
+        now += 1_000_000_000;
+        rule(chain, &frame, now)
+    };
+
+    assert_eq!(
+        None,
+        next(&mut chain, order(CLIENT, &[(1, 10, 500), (2, 10, 500)]))
+    );
+    assert_eq!(
+        Some("max_items"),
+        next(&mut chain, order(CLIENT, &[(1, 1, 1); 3]))
+    );
+    assert_eq!(
+        Some("max_quantity"),
+        next(&mut chain, order(CLIENT, &[(1, 11, 1)]))
+    );
+    assert_eq!(
+        Some("allowed_products"),
+        next(&mut chain, order(CLIENT, &[(1, 1, 1), (9, 1, 1)]))
+    );
+    assert_eq!(
+        Some("max_notional"),
+        next(&mut chain, order(CLIENT, &[(1, 10, 500), (2, 10, 501)]))
+    );
+
+    // other clients fall back to the default limits
+    assert_eq!(None, next(&mut chain, order(7, &[(9, 1000, 1_000_000); 5])));
+}
+
+#[test]
+fn negative_prices_do_not_offset_notional() {
+    let mut chain = chain();
+    assert_eq!(
+        Some("max_notional"),
+        rule(
+            &mut chain,
+            &order(CLIENT, &[(1, 10, 1000), (2, 10, -1000)]),
+            0
+        )
+    );
+}
+
+#[test]
+fn truncated_frames_are_rejected() {
+    let frame = order(CLIENT, &[(1, 1, 1), (2, 1, 1)]);
+    let limits = ClientLimits {
+        allowed_products: Some(HashSet::from([1, 2])),
+        ..ClientLimits::default()
+    };
+    let rejects =
+        |check: &mut dyn RiskCheck, frame: &[u8]| !check.check(&decoder(frame), &limits, 0);
+
+    // the second item is cut short
+    let short_item = &frame[..frame.len() - 10];
+    assert!(rejects(&mut MaxQuantity, short_item));
+    assert!(rejects(&mut AllowedProducts, short_item));
+    assert!(rejects(&mut MaxNotional, short_item));
+
+    // the group dimension is cut short
+    let short_group = &frame[..8 + 26 + 2];
+    assert!(rejects(&mut MaxItems, short_group));
+    assert!(rejects(&mut MaxQuantity, short_group));
+    assert!(rejects(&mut AllowedProducts, short_group));
+    assert!(rejects(&mut MaxNotional, short_group));
+}
+
+#[test]
+fn message_rate_per_client_and_window() {
+    let mut chain = chain();
+    let frame = order(CLIENT, &[(1, 1, 1)]);
+    let other = order(8, &[(1, 1, 1)]);
+
+    for _ in 0..3 {
+        assert_eq!(None, rule(&mut chain, &frame, 5));
+    }
+    assert_eq!(Some("message_rate"), rule(&mut chain, &frame, 999_999_999));
+    assert_eq!(None, rule(&mut chain, &other, 999_999_999));
+    assert_eq!(None, rule(&mut chain, &frame, 1_000_000_000));
+}
+
+#[test]
+fn rejection_response_names_rule() {
+    let mut chain = chain();
+    let frame = order(CLIENT, &[(1, 1, 1); 3]);
+    let rejection = chain.evaluate(&decoder(&frame), 0).unwrap_err();
+
+    let mut buffer = [0u8; 128];
+    let len = rejection.encode_response(234, 42, &mut buffer);
+    let Message::Response(response) = Message::decode(&buffer[..len]).unwrap() else {
+        panic!("expected OrderResponse");
+    };
+    assert_eq!(234, response.order_id);
+    assert_eq!(42, response.timestamp);
+    assert_eq!(OrderStatus::Rejected, response.status);
+    assert_eq!("max_items", response.server_note);
+}
+
+struct NoOddOrders;
+
+impl RiskCheck for NoOddOrders {
+    fn rule(&self) -> &'static str {
+        "no_odd_orders"
+    }
+
+    fn check(&mut self, order: &OrderMessageDecoder<'_>, _: &ClientLimits, _: u64) -> bool {
+        order.order_id().is_multiple_of(2)
+    }
+}
+
+#[test]
+fn custom_chain_runs_in_order() {
+    let mut chain = RiskChain::new(chain().config().clone())
+        .with_check(NoOddOrders)
+        .with_check(MaxItems);
+    assert_eq!(
+        Some("max_items"),
+        rule(&mut chain, &order(CLIENT, &[(1, 1, 1); 3]), 0)
+    );
+    assert_eq!(None, rule(&mut chain, &order(CLIENT, &[(9, 100, 1)]), 0));
+}
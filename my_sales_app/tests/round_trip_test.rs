@@ -0,0 +1,345 @@
+use std::{fs, path::PathBuf};
+
+use my_sales_app::model::{Discount, Item, Message, OrderMessage, OrderResponse};
+use proptest::prelude::*;
+use sales_generated::{
+    order_flags::OrderFlags, order_message_codec::OrderMessageEncoder,
+    order_response_codec::OrderResponseEncoder, order_status::OrderStatus, order_type::OrderType,
+};
+
+/// Large enough for 255 items with their discounts and notes, and the longest generated note.
+const BUFFER_SIZE: usize = 128 * 1024;
+
+fn mantissa() -> impl Strategy<Value = i64> {
+    prop_oneof![
+        Just(i64::MIN + 1),
+        Just(i64::MAX),
+        Just(-1),
+        Just(0),
+        Just(1),
+        any::<i64>(),
+    ]
+}
+
+fn note() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just(String::new()),
+        "[a-z ]{1,32}",
+        // two, three and four byte sequences
+        "[äß€漢😀🦀]{1,16}",
+        "\\PC{0,64}",
+    ]
+}
+
+fn order_type() -> impl Strategy<Value = OrderType> {
+    prop_oneof![
+        Just(OrderType::New),
+        Just(OrderType::Update),
+        Just(OrderType::Cancel),
+        Just(OrderType::NullVal),
+    ]
+}
+
+fn order_flags() -> impl Strategy<Value = OrderFlags> {
+    // bits outside the four choices are kept as well
+    any::<u8>().prop_map(OrderFlags::new)
+}
+
+fn order_status() -> impl Strategy<Value = OrderStatus> {
+    prop_oneof![
+        Just(OrderStatus::Accepted),
+        Just(OrderStatus::Rejected),
+        Just(OrderStatus::Filled),
+        Just(OrderStatus::PartiallyFilled),
+        Just(OrderStatus::NullVal),
+    ]
+}
+
+fn discount() -> impl Strategy<Value = Discount> {
+    (any::<u16>(), mantissa()).prop_map(|(code, amount)| Discount { code, amount })
+}
+
+fn item() -> impl Strategy<Value = Item> {
+    (
+        any::<u32>(),
+        any::<u16>(),
+        mantissa(),
+        "[A-Z]{0,3}",
+        "[ -~]{0,16}",
+        prop::collection::vec(discount(), 0..=3),
+        prop_oneof![Just(String::new()), "[a-z€😀]{1,8}"],
+    )
+        .prop_map(
+            |(product_id, quantity, unit_price, currency, sku, discounts, item_note)| Item {
+                product_id,
+                quantity,
+                unit_price,
+                currency,
+                sku,
+                discounts,
+                item_note,
+            },
+        )
+}
+
+fn items() -> impl Strategy<Value = Vec<Item>> {
+    prop_oneof![
+        prop::collection::vec(item(), 0..=8),
+        prop::collection::vec(item(), 0..=300),
+        prop::collection::vec(item(), 256),
+    ]
+}
+
+fn order_message() -> impl Strategy<Value = OrderMessage> {
+    (
+        any::<u64>(),
+        any::<u64>(),
+        any::<u64>(),
+        order_type(),
+        order_flags(),
+        items(),
+        note(),
+    )
+        .prop_map(
+            |(order_id, client_id, timestamp, order_type, order_flags, items, customer_note)| {
+                OrderMessage {
+                    order_id,
+                    client_id,
+                    timestamp,
+                    order_type,
+                    order_flags,
+                    items,
+                    customer_note,
+                }
+            },
+        )
+}
+
+fn order_response() -> impl Strategy<Value = OrderResponse> {
+    (
+        any::<u64>(),
+        any::<u64>(),
+        order_status(),
+        any::<u32>(),
+        mantissa(),
+        note(),
+    )
+        .prop_map(
+            |(order_id, timestamp, status, filled_qty, fill_price, server_note)| OrderResponse {
+                order_id,
+                timestamp,
+                status,
+                filled_qty,
+                fill_price,
+                server_note,
+            },
+        )
+}
+
+fn encode(message: &Message) -> Vec<u8> {
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+    let len = message.encode(&mut buffer);
+    buffer.truncate(len);
+    buffer
+}
+
+proptest! {
+    #[test]
+    fn order_message_round_trips(order in order_message()) {
+        let message = Message::Order(order);
+        let frame = encode(&message);
+        prop_assert_eq!(message.compute_length(), frame.len());
+        prop_assert_eq!(&message, &Message::decode(&frame).unwrap());
+        prop_assert_eq!(&message, &Message::decode_checked(&frame).unwrap());
+    }
+
+    #[test]
+    fn order_response_round_trips(response in order_response()) {
+        let message = Message::Response(response);
+        let frame = encode(&message);
+        prop_assert_eq!(message.compute_length(), frame.len());
+        prop_assert_eq!(&message, &Message::decode(&frame).unwrap());
+        prop_assert_eq!(&message, &Message::decode_checked(&frame).unwrap());
+    }
+}
+
+fn golden_messages() -> Vec<(&'static str, Message)> {
+    vec![
+        (
+            "order_message_two_items",
+            Message::Order(OrderMessage {
+                order_id: 234,
+                client_id: 135,
+                timestamp: 246,
+                order_type: OrderType::New,
+                order_flags: *OrderFlags::default().set_gift_wrap(true),
+                items: vec![
+                    Item {
+                        product_id: 222,
+                        quantity: 2,
+                        unit_price: 234,
+                        currency: "EUR".to_string(),
+                        sku: "SKU-222".to_string(),
+                        discounts: vec![
+                            Discount {
+                                code: 7,
+                                amount: 50,
+                            },
+                            Discount {
+                                code: 9,
+                                amount: -1,
+                            },
+                        ],
+                        item_note: "gift".to_string(),
+                    },
+                    Item {
+                        product_id: 111,
+                        quantity: 3,
+                        unit_price: 123,
+                        ..Item::default()
+                    },
+                ],
+                customer_note: "duck is angry".to_string(),
+            }),
+        ),
+        (
+            "order_message_empty",
+            Message::Order(OrderMessage {
+                order_id: u64::MAX,
+                client_id: 0,
+                timestamp: 1_700_000_000_000_000_000,
+                order_type: OrderType::Cancel,
+                order_flags: OrderFlags::default(),
+                items: Vec::new(),
+                customer_note: String::new(),
+            }),
+        ),
+        (
+            "order_response_partially_filled",
+            Message::Response(OrderResponse {
+                order_id: 234,
+                timestamp: 1_700_000_000_000_000_001,
+                status: OrderStatus::PartiallyFilled,
+                filled_qty: 3,
+                fill_price: i64::MIN + 1,
+                server_note: "teilweise ausgeführt €".to_string(),
+            }),
+        ),
+    ]
+}
+
+#[test]
+fn compute_length_fits_exactly() {
+    assert_eq!(8 + 26 + 4 + 2, OrderMessageEncoder::compute_length([], 0));
+    assert_eq!(
+        8 + 26 + 4 + 2 * (33 + 4 + 2) + 2 + 13,
+        OrderMessageEncoder::compute_length([(0, 0); 2], 13)
+    );
+    assert_eq!(
+        8 + 26 + 4 + (33 + 4 + 2 * 10 + 2 + 4) + 2,
+        OrderMessageEncoder::compute_length([(2, 4)], 0)
+    );
+    assert_eq!(8 + 29 + 2 + 5, OrderResponseEncoder::compute_length(5));
+
+    for (_, message) in golden_messages() {
+        let mut buffer = vec![0u8; message.compute_length()];
+        assert_eq!(buffer.len(), message.encode(&mut buffer));
+    }
+}
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.hex"))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    let hex: String = hex.split_whitespace().collect();
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// Wire format must not change by accident. After an intended schema change regenerate the
+/// vectors with `UPDATE_GOLDEN=1 cargo test --test round_trip_test` and review the diff.
+#[test]
+fn golden_vectors() {
+    let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+    for (name, message) in golden_messages() {
+        let frame = encode(&message);
+        let path = golden_path(name);
+        if update {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, to_hex(&frame) + "\n").unwrap();
+        }
+        let golden = from_hex(&fs::read_to_string(&path).unwrap());
+        assert_eq!(
+            to_hex(&golden),
+            to_hex(&frame),
+            "encoding of {name} changed"
+        );
+        assert_eq!(message, Message::decode_checked(&golden).unwrap());
+    }
+}
+
+/// Frames of older senders must keep decoding: version 1 has no order_flags, versions before 3
+/// have no discounts or item notes, versions before 4 count group entries in a uint8, versions
+/// before 5 have no currency or sku.
+#[test]
+fn older_version_vectors_decode() {
+    for version in [1u16, 2, 3, 4] {
+        for (name, message) in golden_messages() {
+            let Message::Order(mut order) = message else {
+                continue;
+            };
+            let path = golden_path(&format!("{name}_v{version}"));
+            let golden = from_hex(&fs::read_to_string(path).unwrap());
+            assert_eq!(version.to_le_bytes(), golden[6..8]);
+            if version < 2 {
+                order.order_flags = OrderFlags::default();
+            }
+            for item in &mut order.items {
+                if version < 3 {
+                    item.discounts.clear();
+                    item.item_note.clear();
+                }
+                item.currency.clear();
+                item.sku.clear();
+            }
+            let order = Message::Order(order);
+            assert_eq!(order, Message::decode(&golden).unwrap());
+            assert_eq!(order, Message::decode_checked(&golden).unwrap());
+        }
+    }
+}
+
+#[test]
+fn every_flag_combination_round_trips() {
+    for bits in 0..16u8 {
+        let mut flags = OrderFlags::default();
+        flags
+            .set_ioc(bits & 1 != 0)
+            .set_post_only(bits & 2 != 0)
+            .set_gift_wrap(bits & 4 != 0)
+            .set_expedited(bits & 8 != 0);
+        assert_eq!(bits, flags.0);
+
+        let message = Message::Order(OrderMessage {
+            order_flags: flags,
+            ..OrderMessage::default()
+        });
+        let Message::Order(decoded) = Message::decode_checked(&encode(&message)).unwrap() else {
+            panic!("expected OrderMessage");
+        };
+        assert_eq!(flags, decoded.order_flags);
+        assert_eq!(bits & 1 != 0, decoded.order_flags.get_ioc());
+        assert_eq!(bits & 2 != 0, decoded.order_flags.get_post_only());
+        assert_eq!(bits & 4 != 0, decoded.order_flags.get_gift_wrap());
+        assert_eq!(bits & 8 != 0, decoded.order_flags.get_expedited());
+    }
+}
@@ -0,0 +1,156 @@
+use std::{fs, path::Path};
+
+use my_sales_app::model::{Discount, Item, OrderMessage, OrderResponse};
+use sales_generated::{
+    Decoder, ReadBuf, Reader, SbeErr, message_header_codec::MessageHeaderDecoder,
+    order_message_codec::OrderMessageDecoder, order_response_codec::OrderResponseDecoder,
+};
+
+fn order() -> OrderMessage {
+    OrderMessage {
+        order_id: 234,
+        items: (0..10)
+            .map(|i| Item {
+                product_id: i,
+                discounts: vec![Discount { code: 1, amount: 5 }; i as usize % 3],
+                item_note: "x".repeat(i as usize),
+                ..Item::default()
+            })
+            .collect(),
+        customer_note: "route me".to_string(),
+        ..OrderMessage::default()
+    }
+}
+
+fn frame(order: &OrderMessage) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    order.encode_to(&mut buffer);
+    buffer
+}
+
+/// Frame of an older sender, its items have no nested parts.
+fn golden(name: &str) -> Vec<u8> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.hex"));
+    let hex = fs::read_to_string(path).unwrap();
+    let hex = hex.trim();
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn decoder(frame: &[u8]) -> OrderMessageDecoder<'_> {
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(frame), 0);
+    OrderMessageDecoder::default().header(header, 0)
+}
+
+/// What a router needs: the order id and the trailing customer note.
+fn route(frame: &[u8]) -> Result<(u64, String), SbeErr> {
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(frame), 0);
+    let order = OrderMessageDecoder::default().header_checked(header, 0)?;
+    let order_id = order.order_id();
+    let mut items = order.items_decoder_checked()?;
+    items.skip_all()?;
+    let mut order = items.parent()?;
+    let (offset, len) = order.customer_note_decoder_checked()?;
+    let note = order.get_buf().get_slice_at(offset, len);
+    Ok((order_id, String::from_utf8(note.to_vec()).unwrap()))
+}
+
+#[test]
+fn router_skips_items() {
+    assert_eq!(Ok((234, "route me".to_string())), route(&frame(&order())));
+    for version in ["v1", "v2", "v3", "v4"] {
+        let frame = golden(&format!("order_message_two_items_{version}"));
+        assert_eq!(Ok((234, "duck is angry".to_string())), route(&frame));
+    }
+}
+
+#[test]
+fn skip_all_after_some_entries() {
+    let frames = [
+        (frame(&order()), 0, 8),
+        (golden("order_message_two_items_v2"), 222, 13),
+    ];
+    for (frame, first, note_len) in &frames {
+        let mut items = decoder(frame).items_decoder();
+        assert_eq!(Some(0), items.advance().unwrap());
+        assert_eq!(*first, items.product_id());
+        items.skip_all().unwrap();
+        assert_eq!(None, items.advance().unwrap());
+        // skipping again is a no-op
+        items.skip_all().unwrap();
+
+        let mut order = items.parent().unwrap();
+        assert_eq!(*note_len, order.customer_note_skip());
+        assert_eq!(frame.len(), order.get_limit());
+    }
+}
+
+#[test]
+fn skip_all_on_the_last_entry() {
+    let frame = frame(&order());
+    let mut items = decoder(&frame).items_decoder();
+    while items.advance().unwrap() != Some(9) {}
+    assert_eq!(9, items.product_id());
+    items.skip_all().unwrap();
+
+    let mut order = items.parent().unwrap();
+    let (offset, len) = order.customer_note_decoder_checked().unwrap();
+    assert_eq!(b"route me", order.get_buf().get_slice_at(offset, len));
+    assert_eq!(frame.len(), order.get_limit());
+}
+
+#[test]
+fn skip_all_jumps_fixed_length_entries() {
+    let frame = golden("order_message_two_items_v1");
+    let mut items = decoder(&frame).items_decoder();
+    let start = items.get_limit();
+    items.skip_all().unwrap();
+    assert_eq!(start + 2 * 14, items.get_limit());
+
+    // the jump is checked against the buffer
+    let truncated = &frame[..start + 14];
+    let mut items = decoder(truncated).items_decoder();
+    assert_eq!(Err(SbeErr::BufferTooShort), items.skip_all());
+}
+
+#[test]
+fn skip_all_checks_nested_lengths() {
+    let frame = frame(&order());
+    let mut items = decoder(&frame[..frame.len() - 20]).items_decoder();
+    assert_eq!(Err(SbeErr::BufferTooShort), items.skip_all());
+}
+
+#[test]
+fn var_data_skips() {
+    let frame = frame(&order());
+    let mut items = decoder(&frame).items_decoder();
+    items.skip_all().unwrap();
+    let mut order = items.parent().unwrap();
+    let limit = order.get_limit();
+    assert_eq!(Ok(8), order.clone().customer_note_skip_checked());
+    assert_eq!(8, order.customer_note_skip());
+    assert_eq!(limit + 2 + 8, order.get_limit());
+
+    let mut items = decoder(&frame[..frame.len() - 1]).items_decoder();
+    items.skip_all().unwrap();
+    let mut order = items.parent().unwrap();
+    assert_eq!(
+        Err(SbeErr::BufferTooShort),
+        order.customer_note_skip_checked()
+    );
+
+    let response = OrderResponse {
+        server_note: "done".to_string(),
+        ..OrderResponse::default()
+    };
+    let mut frame = Vec::new();
+    response.encode_to(&mut frame);
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(&frame), 0);
+    let mut response = OrderResponseDecoder::default().header(header, 0);
+    assert_eq!(Ok(4), response.server_note_skip_checked());
+    assert_eq!(frame.len(), response.get_limit());
+}
@@ -0,0 +1,159 @@
+use std::{
+    cell::Cell,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use my_sales_app::model::OrderMessage;
+use sales_generated::{
+    Clock, ReadBuf, SbeErr, Timestamp, WriteBuf,
+    message_header_codec::{self, MessageHeaderDecoder},
+    order_message_codec::{
+        OrderMessageBlockDecoder, OrderMessageDecoder, OrderMessageEncoder,
+        shared::OrderMessageBytesDecoder,
+    },
+    order_response_codec::{OrderResponseDecoder, OrderResponseEncoder},
+    timestamp::{self, SystemClock},
+};
+use time::OffsetDateTime;
+
+// 2023-11-14T22:13:20.000000042Z
+const NANOS: u64 = 1_700_000_000_000_000_042;
+
+#[test]
+fn schema_time_unit() {
+    assert_eq!("nanosecond", timestamp::TIME_UNIT);
+    let ts = Timestamp::from_nanos(NANOS);
+    assert_eq!(NANOS, ts.as_nanos());
+    assert_eq!(Duration::new(1_700_000_000, 42), ts.since_epoch());
+    assert_eq!(Ok(ts), Timestamp::from_since_epoch(ts.since_epoch()));
+    assert_eq!("1700000000.000000042", ts.to_string());
+    assert_eq!(Timestamp::UNIX_EPOCH, Timestamp::default());
+}
+
+#[test]
+fn arithmetic() {
+    let ts = Timestamp::from_nanos(NANOS);
+    let later = ts.checked_add(Duration::from_millis(5)).unwrap();
+    assert_eq!(
+        Some(Duration::from_millis(5)),
+        later.checked_duration_since(ts)
+    );
+    assert_eq!(None, ts.checked_duration_since(later));
+    assert_eq!(None, Timestamp::MAX.checked_add(Duration::from_nanos(1)));
+    assert_eq!(
+        Err(SbeErr::TimestampOutOfRange),
+        Timestamp::from_since_epoch(Duration::MAX)
+    );
+}
+
+#[test]
+fn system_time() {
+    let ts = Timestamp::from_nanos(NANOS);
+    let time = SystemTime::from(ts);
+    assert_eq!(UNIX_EPOCH + Duration::new(1_700_000_000, 42), time);
+    assert_eq!(Ok(ts), Timestamp::try_from(time));
+    assert_eq!(
+        Err(SbeErr::TimestampOutOfRange),
+        Timestamp::try_from(UNIX_EPOCH - Duration::from_nanos(1))
+    );
+    let system = SystemClock.now();
+    assert!(system > ts);
+}
+
+#[test]
+fn chrono_conversions() {
+    let ts = Timestamp::from_nanos(NANOS);
+    let time = DateTime::<Utc>::from(ts);
+    assert_eq!(DateTime::from_timestamp(1_700_000_000, 42), Some(time));
+    assert_eq!(Ok(ts), Timestamp::try_from(time));
+    let max = DateTime::<Utc>::from(Timestamp::MAX);
+    assert_eq!(Ok(Timestamp::MAX), Timestamp::try_from(max));
+    assert_eq!(
+        Err(SbeErr::TimestampOutOfRange),
+        Timestamp::try_from(max + chrono::Duration::nanoseconds(1))
+    );
+    assert_eq!(
+        Err(SbeErr::TimestampOutOfRange),
+        Timestamp::try_from(DateTime::from_timestamp(-1, 0).unwrap())
+    );
+}
+
+#[test]
+fn time_conversions() {
+    let ts = Timestamp::from_nanos(NANOS);
+    let time = OffsetDateTime::from(ts);
+    assert_eq!(1_700_000_000, time.unix_timestamp());
+    assert_eq!(42, time.nanosecond());
+    assert_eq!(Ok(ts), Timestamp::try_from(time));
+    let max = OffsetDateTime::from(Timestamp::MAX);
+    assert_eq!(Ok(Timestamp::MAX), Timestamp::try_from(max));
+    assert_eq!(
+        Err(SbeErr::TimestampOutOfRange),
+        Timestamp::try_from(max + time::Duration::nanoseconds(1))
+    );
+    assert_eq!(
+        Err(SbeErr::TimestampOutOfRange),
+        Timestamp::try_from(OffsetDateTime::UNIX_EPOCH - time::Duration::nanoseconds(1))
+    );
+}
+
+#[test]
+fn encoders_take_the_time_of_a_clock() {
+    // a clock ticking one microsecond per read
+    let ticks = Cell::new(NANOS);
+    let clock = || Timestamp::from_nanos(ticks.replace(ticks.get() + 1_000));
+
+    let mut buffer = [0u8; 128];
+    let mut order = OrderMessageEncoder::default().wrap(
+        WriteBuf::new(&mut buffer),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    order = order.header(0).parent().unwrap();
+    assert_eq!(Timestamp::from_nanos(NANOS), order.timestamp_now(&clock));
+    assert_eq!(
+        Timestamp::from_nanos(NANOS + 1_000),
+        order.timestamp_now(&clock)
+    );
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(&buffer), 0);
+    let decoder = OrderMessageDecoder::default().header(header, 0);
+    assert_eq!(NANOS + 1_000, decoder.timestamp());
+    assert_eq!(
+        Timestamp::from_nanos(NANOS + 1_000),
+        decoder.timestamp_utc()
+    );
+
+    // a Timestamp is a stopped clock
+    let stopped = Timestamp::from_nanos(NANOS);
+    let mut response = OrderResponseEncoder::default().wrap(
+        WriteBuf::new(&mut buffer),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    response = response.header(0).parent().unwrap();
+    response.timestamp_now(&stopped);
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(&buffer), 0);
+    let decoder = OrderResponseDecoder::default().header(header, 0);
+    assert_eq!(stopped, decoder.timestamp_utc());
+    assert_eq!(stopped, stopped.now());
+}
+
+#[test]
+fn all_decoders_read_the_same_timestamp() {
+    let order = OrderMessage {
+        timestamp: NANOS,
+        ..OrderMessage::default()
+    };
+    let mut frame = Vec::new();
+    order.encode_to(&mut frame);
+    let ts = Timestamp::from_nanos(NANOS);
+
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(&frame), 0);
+    let decoder = OrderMessageDecoder::default().header(header, 0);
+    assert_eq!(ts, decoder.timestamp_utc());
+    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(&frame), 0);
+    let block = OrderMessageBlockDecoder::header(header, 0).unwrap();
+    assert_eq!(ts, block.timestamp_utc());
+    let decoder = OrderMessageBytesDecoder::header(Bytes::from(frame), 0).unwrap();
+    assert_eq!(ts, decoder.timestamp_utc());
+}
@@ -0,0 +1,148 @@
+use bytes::BytesMut;
+use my_sales_app::model::{Item, Message, OrderMessage, OrderResponse};
+use sales_generated::{
+    Encoder, SbeErr, WriteBuf, WriteBuffer, message_header_codec,
+    order_flags::OrderFlags,
+    order_message_codec::{OrderMessageEncoder, encoder::ItemsEncoder},
+    order_status::OrderStatus,
+    order_type::OrderType,
+    var_string_encoding_codec,
+};
+
+fn large_order() -> OrderMessage {
+    OrderMessage {
+        order_id: 234,
+        client_id: 135,
+        timestamp: 246,
+        order_type: OrderType::New,
+        order_flags: *OrderFlags::default().set_expedited(true),
+        items: (0..255)
+            .map(|i| Item {
+                product_id: i,
+                quantity: i as u16,
+                unit_price: -(i as i64),
+                ..Item::default()
+            })
+            .collect(),
+        customer_note: "€".repeat(u16::MAX as usize / 3),
+    }
+}
+
+fn fixed(message: &Message) -> Vec<u8> {
+    let mut buffer = vec![0u8; 128 * 1024];
+    let len = message.encode(&mut buffer);
+    buffer.truncate(len);
+    buffer
+}
+
+#[test]
+fn vec_grows_to_frame() {
+    let message = Message::Order(large_order());
+    let mut buffer = Vec::new();
+    let len = message.encode_to(&mut buffer);
+    assert_eq!(len, buffer.len());
+    assert_eq!(fixed(&message), buffer);
+    assert_eq!(message, Message::decode_checked(&buffer).unwrap());
+
+    // a reused buffer is truncated to the frame
+    let response = Message::Response(OrderResponse {
+        order_id: 234,
+        timestamp: 1,
+        status: OrderStatus::Filled,
+        filled_qty: 2,
+        fill_price: 3,
+        server_note: String::new(),
+    });
+    let len = response.encode_to(&mut buffer);
+    assert_eq!(len, buffer.len());
+    assert_eq!(fixed(&response), buffer);
+}
+
+#[test]
+fn bytes_mut_grows_to_frame() {
+    let message = Message::Order(large_order());
+    let mut buffer = BytesMut::new();
+    let len = message.encode_to(&mut buffer);
+    assert_eq!(len, buffer.len());
+    let frame = buffer.freeze();
+    assert_eq!(fixed(&message), frame);
+}
+
+#[test]
+fn slice_through_trait_matches_fixed() {
+    let message = Message::Order(large_order());
+    let mut buffer = vec![0u8; 128 * 1024];
+    let len = message.encode_to(&mut buffer.as_mut_slice());
+    assert_eq!(fixed(&message), buffer[..len]);
+}
+
+#[test]
+#[should_panic]
+fn slice_still_panics_when_too_small() {
+    let mut buffer = [0u8; 64];
+    large_order().encode_to(&mut &mut buffer[..]);
+}
+
+#[test]
+fn generated_encoder_writes_into_vec() {
+    let mut vec = vec![0; 64 * 1024];
+    let mut order = OrderMessageEncoder::default().wrap(
+        WriteBuf::growable(&mut vec),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    order = order.header(0).parent().unwrap();
+    order.order_id(1);
+    let mut items = order.items_encoder(200, ItemsEncoder::default());
+    for i in 0..200 {
+        let _r = items.advance();
+        items.product_id(i);
+    }
+    order = items.parent().unwrap();
+    order.customer_note("done");
+    let len = order.get_limit();
+
+    // the reused buffer still holds its old bytes past the frame
+    assert_eq!(64 * 1024, vec.len());
+    vec.finish(len);
+    assert_eq!(len, vec.len());
+    let Message::Order(decoded) = Message::decode_checked(&vec).unwrap() else {
+        panic!("expected OrderMessage");
+    };
+    assert_eq!(200, decoded.items.len());
+    assert_eq!(199, decoded.items[199].product_id);
+    assert_eq!("done", decoded.customer_note);
+}
+
+#[test]
+fn notes_longer_than_their_length_fail() {
+    let mut vec = Vec::new();
+    let mut order = OrderMessageEncoder::default().wrap(
+        WriteBuf::growable(&mut vec),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    order = order.header(0).parent().unwrap();
+    let mut items = order.items_encoder(1, ItemsEncoder::default());
+    let _r = items.advance();
+    let too_long = "x".repeat(var_string_encoding_codec::LENGTH_MAX as usize + 1);
+    assert_eq!(Err(SbeErr::DataTooLong), items.item_note_checked(&too_long));
+    items.item_note_checked("gift").unwrap();
+    order = items.parent().unwrap();
+    let limit = order.get_limit();
+    assert_eq!(
+        Err(SbeErr::DataTooLong),
+        order.customer_note_checked(&too_long)
+    );
+    // nothing was written
+    assert_eq!(limit, order.get_limit());
+    order.customer_note_checked(&too_long[1..]).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "customer_note too long")]
+fn long_note_panics_instead_of_truncating() {
+    let order = OrderMessage {
+        customer_note: "x".repeat(u16::MAX as usize + 1),
+        ..OrderMessage::default()
+    };
+    order.encode_to(&mut Vec::new());
+}
@@ -0,0 +1,274 @@
+//! Generates the statics of `ir` from messages.xml: a static per type declared in `<types>` and
+//! per message, named after it in SCREAMING_SNAKE_CASE, and `SCHEMA`. The IR is read with the
+//! parser of `ir::parse`, so the statics are what a tool gets from reading the xml at runtime.
+
+use std::{env, fmt::Write as _, fs, path::Path};
+
+use bumpalo::Bump;
+
+#[allow(dead_code)]
+#[path = "src/ir"]
+mod ir {
+    pub mod model;
+    pub mod parse;
+}
+
+use ir::model::*;
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let xml_path = Path::new(&manifest_dir).join("messages.xml");
+    println!("cargo:rerun-if-changed=messages.xml");
+    println!("cargo:rerun-if-changed=src/ir/model.rs");
+    println!("cargo:rerun-if-changed=src/ir/parse.rs");
+
+    let bump = Bump::new();
+    let schema =
+        ir::parse::load(&xml_path, &bump).unwrap_or_else(|e| panic!("{}: {e}", xml_path.display()));
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("ir.rs");
+    fs::write(out, Generator { schema: &schema }.schema()).unwrap();
+}
+
+/// `OrderType` is `ORDER_TYPE`, `groupSizeEncoding` is `GROUP_SIZE_ENCODING`
+fn static_name(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.char_indices() {
+        if c.is_ascii_uppercase() && i > 0 {
+            snake.push('_');
+        }
+        snake.push(c.to_ascii_uppercase());
+    }
+    snake
+}
+
+/// address of the type a `Type` refers to, the parser allocates a declared type once
+fn address(ty: &Type) -> Option<*const ()> {
+    Some(match ty {
+        Type::Primitive(_) => return None,
+        Type::CharArray(ty) => *ty as *const CharArray as *const (),
+        Type::Array(ty) => *ty as *const Array as *const (),
+        Type::Enum(ty) => *ty as *const Enum as *const (),
+        Type::Set(ty) => *ty as *const Set as *const (),
+        Type::Composite(ty) => *ty as *const Composite as *const (),
+    })
+}
+
+struct Generator<'g, 's> {
+    schema: &'g Schema<'s>,
+}
+
+impl Generator<'_, '_> {
+    fn schema(&self) -> String {
+        let schema = self.schema;
+        let mut code = String::from("// Generated by build.rs from messages.xml, do not edit.\n");
+        for ty in schema.types {
+            let (ty_name, literal) = match ty {
+                Type::Primitive(_) => continue,
+                Type::CharArray(ty) => ("CharArray", self.char_array(ty)),
+                Type::Array(ty) => ("Array", self.array(ty)),
+                Type::Enum(ty) => ("Enum", self.enumeration(ty)),
+                Type::Set(ty) => ("Set", self.set(ty)),
+                Type::Composite(ty) => ("Composite", self.composite(ty)),
+            };
+            let name = static_name(ty.name());
+            writeln!(code, "\npub static {name}: {ty_name}<'static> = {literal};").unwrap();
+        }
+        for message in schema.messages {
+            writeln!(
+                code,
+                "\npub static {}: Message<'static> = {};",
+                static_name(message.name),
+                self.message(message)
+            )
+            .unwrap();
+        }
+        let types: Vec<String> = schema.types.iter().map(|ty| self.ty(ty)).collect();
+        let messages: Vec<String> = schema
+            .messages
+            .iter()
+            .map(|message| static_name(message.name))
+            .collect();
+        writeln!(
+            code,
+            "\npub static SCHEMA: Schema<'static> = Schema {{\n    package: {:?},\n    id: {},\n    \
+             version: {},\n    semantic_version: {:?},\n    byte_order: ByteOrder::{:?},\n    \
+             header: {},\n    types: &[{}],\n    messages: &[{}],\n}};",
+            schema.package,
+            schema.id,
+            schema.version,
+            schema.semantic_version,
+            schema.byte_order,
+            self.composite_ref(schema.header),
+            types.join(", "),
+            messages.join(", "),
+        )
+        .unwrap();
+        code
+    }
+
+    /// static of a type declared in `<types>`, `None` for types declared inline
+    fn declared(&self, ty: &Type) -> Option<String> {
+        let target = address(ty)?;
+        self.schema
+            .types
+            .iter()
+            .any(|declared| address(declared) == Some(target))
+            .then(|| static_name(ty.name()))
+    }
+
+    fn ty(&self, ty: &Type) -> String {
+        let (variant, literal) = match ty {
+            Type::Primitive(primitive) => {
+                return format!("Type::Primitive(Primitive::{primitive:?})")
+            }
+            Type::CharArray(ty) => ("CharArray", self.char_array(ty)),
+            Type::Array(ty) => ("Array", self.array(ty)),
+            Type::Enum(ty) => ("Enum", self.enumeration(ty)),
+            Type::Set(ty) => ("Set", self.set(ty)),
+            Type::Composite(ty) => ("Composite", self.composite(ty)),
+        };
+        match self.declared(ty) {
+            Some(name) => format!("Type::{variant}(&{name})"),
+            None => format!("Type::{variant}(&{literal})"),
+        }
+    }
+
+    fn composite_ref(&self, composite: &Composite) -> String {
+        self.declared(&Type::Composite(composite)).map_or_else(
+            || format!("&{}", self.composite(composite)),
+            |name| format!("&{name}"),
+        )
+    }
+
+    fn char_array(&self, ty: &CharArray) -> String {
+        format!(
+            "CharArray {{ name: {:?}, length: {}, character_encoding: {:?} }}",
+            ty.name, ty.length, ty.character_encoding
+        )
+    }
+
+    fn array(&self, ty: &Array) -> String {
+        format!(
+            "Array {{ name: {:?}, primitive: Primitive::{:?}, length: {} }}",
+            ty.name, ty.primitive, ty.length
+        )
+    }
+
+    fn enumeration(&self, ty: &Enum) -> String {
+        let values: Vec<String> = ty
+            .values
+            .iter()
+            .map(|value| {
+                format!(
+                    "ValidValue {{ name: {:?}, value: {} }}",
+                    value.name, value.value
+                )
+            })
+            .collect();
+        format!(
+            "Enum {{ name: {:?}, encoding: Primitive::{:?}, values: &[{}] }}",
+            ty.name,
+            ty.encoding,
+            values.join(", ")
+        )
+    }
+
+    fn set(&self, ty: &Set) -> String {
+        let choices: Vec<String> = ty
+            .choices
+            .iter()
+            .map(|choice| format!("Choice {{ name: {:?}, bit: {} }}", choice.name, choice.bit))
+            .collect();
+        format!(
+            "Set {{ name: {:?}, encoding: Primitive::{:?}, choices: &[{}] }}",
+            ty.name,
+            ty.encoding,
+            choices.join(", ")
+        )
+    }
+
+    fn composite(&self, ty: &Composite) -> String {
+        format!(
+            "Composite {{ name: {:?}, encoded_length: {}, fields: {} }}",
+            ty.name,
+            ty.encoded_length,
+            self.fields(ty.fields)
+        )
+    }
+
+    fn fields(&self, fields: &[Field]) -> String {
+        let fields: Vec<String> = fields
+            .iter()
+            .map(|field| {
+                format!(
+                    "\n    Field {{ name: {:?}, id: {}, offset: {}, ty: {}, presence: Presence::{:?}, \
+                     since_version: {}, semantic_type: {:?}, description: {:?} }}",
+                    field.name,
+                    field.id,
+                    field.offset,
+                    self.ty(&field.ty),
+                    field.presence,
+                    field.since_version,
+                    field.semantic_type,
+                    field.description
+                )
+            })
+            .collect();
+        format!("&[{}]", fields.join(","))
+    }
+
+    fn groups(&self, groups: &[Group]) -> String {
+        let groups: Vec<String> = groups
+            .iter()
+            .map(|group| {
+                format!(
+                    "\nGroup {{ name: {:?}, id: {}, block_length: {}, since_version: {}, \
+                     dimension: {}, fields: {}, groups: {}, data: {}, description: {:?} }}",
+                    group.name,
+                    group.id,
+                    group.block_length,
+                    group.since_version,
+                    self.composite_ref(group.dimension),
+                    self.fields(group.fields),
+                    self.groups(group.groups),
+                    self.data(group.data),
+                    group.description
+                )
+            })
+            .collect();
+        format!("&[{}]", groups.join(","))
+    }
+
+    fn data(&self, data: &[Data]) -> String {
+        let data: Vec<String> = data
+            .iter()
+            .map(|data| {
+                format!(
+                    "\nData {{ name: {:?}, id: {}, since_version: {}, encoding: {}, \
+                     character_encoding: {:?}, description: {:?} }}",
+                    data.name,
+                    data.id,
+                    data.since_version,
+                    self.composite_ref(data.encoding),
+                    data.character_encoding,
+                    data.description
+                )
+            })
+            .collect();
+        format!("&[{}]", data.join(","))
+    }
+
+    fn message(&self, message: &Message) -> String {
+        format!(
+            "Message {{\n    name: {:?},\n    id: {},\n    block_length: {},\n    fields: {},\n    \
+             groups: {},\n    data: {},\n    description: {:?},\n}}",
+            message.name,
+            message.id,
+            message.block_length,
+            self.fields(message.fields),
+            self.groups(message.groups),
+            self.data(message.data),
+            message.description
+        )
+    }
+}
@@ -0,0 +1,23 @@
+//! `Display` of the set types, which the generator only gives a `Debug` listing every choice.
+
+use core::fmt;
+
+use crate::{ir, order_flags::OrderFlags};
+
+/// Choices that are set, separated by `|` and named as in messages.xml, e.g. `PostOnly|GiftWrap`.
+/// An empty set prints nothing, bits the schema does not name are left out.
+impl fmt::Display for OrderFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let set = ir::ORDER_FLAGS
+            .choices
+            .iter()
+            .filter(|choice| self.0 & (1 << choice.bit) != 0);
+        for (i, choice) in set.enumerate() {
+            if i > 0 {
+                f.write_str("|")?;
+            }
+            f.write_str(choice.name)?;
+        }
+        Ok(())
+    }
+}
@@ -3,7 +3,40 @@ use crate::*;
 pub use encoder::GroupSizeEncodingEncoder;
 pub use decoder::GroupSizeEncodingDecoder;
 
-pub const ENCODED_LENGTH: usize = 3;
+pub const ENCODED_LENGTH: usize = 4;
+
+/// max value of 'numInGroup', the most entries one group can carry
+pub const NUM_IN_GROUP_MAX: u16 = 65534;
+
+/// `count` as 'numInGroup', `SbeErr::CountOutOfRange` when it exceeds `NUM_IN_GROUP_MAX`
+#[inline]
+pub fn num_in_group(count: usize) -> SbeResult<u16> {
+    match u16::try_from(count) {
+        Ok(count) if count <= NUM_IN_GROUP_MAX => Ok(count),
+        _ => Err(SbeErr::CountOutOfRange),
+    }
+}
+
+/// encoded length of a dimension in frames of `acting_version`, 'numInGroup' was a uint8 before
+/// version 4
+#[inline]
+pub fn acting_encoded_length(acting_version: u16) -> usize {
+    if acting_version < 4 {
+        3
+    } else {
+        ENCODED_LENGTH
+    }
+}
+
+/// 'numInGroup' of the dimension at `offset` in frames of `acting_version`
+#[inline]
+pub fn acting_num_in_group(buf: &ReadBuf<'_>, offset: usize, acting_version: u16) -> u16 {
+    if acting_version < 4 {
+        buf.get_u8_at(offset + 2) as u16
+    } else {
+        buf.get_u16_at(offset + 2)
+    }
+}
 
 pub mod encoder {
     use super::*;
@@ -54,17 +87,17 @@ pub mod encoder {
 
         /// primitive field 'numInGroup'
         /// - min value: 0
-        /// - max value: 254
-        /// - null value: 0xff_u8
+        /// - max value: 65534
+        /// - null value: 0xffff_u16
         /// - characterEncoding: null
         /// - semanticType: null
         /// - encodedOffset: 2
-        /// - encodedLength: 1
+        /// - encodedLength: 2
         /// - version: 0
         #[inline]
-        pub fn num_in_group(&mut self, value: u8) {
+        pub fn num_in_group(&mut self, value: u16) {
             let offset = self.offset + 2;
-            self.get_buf_mut().put_u8_at(offset, value);
+            self.get_buf_mut().put_u16_at(offset, value);
         }
 
     }
@@ -113,8 +146,8 @@ pub mod decoder {
 
         /// primitive field - 'REQUIRED'
         #[inline]
-        pub fn num_in_group(&self) -> u8 {
-            self.get_buf().get_u8_at(self.offset + 2)
+        pub fn num_in_group(&self) -> u16 {
+            self.get_buf().get_u16_at(self.offset + 2)
         }
 
     }
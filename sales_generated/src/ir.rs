@@ -0,0 +1,488 @@
+//! Description of messages.xml for tools that work on any message of the schema, e.g. printers
+//! or bridges, instead of one hand-written decoder per message. `walk` decodes a frame into a
+//! tree of named values with it.
+//!
+//! build.rs generates the statics from messages.xml, a static per declared type and message,
+//! e.g. `ORDER_TYPE` and `ORDER_MESSAGE`, and `SCHEMA`. A schema change needs no edit here, only
+//! the codecs are ported by hand, `my_sales_app/tests/ir_test.rs` checks they agree.
+//!
+//! With the `xml` feature `parse` reads any SBE schema at runtime into the same types, which
+//! `walk_schema` decodes frames with.
+
+mod model;
+#[cfg(feature = "xml")]
+mod parse;
+
+#[cfg(feature = "xml")]
+pub use bumpalo::Bump;
+pub use model::*;
+#[cfg(feature = "xml")]
+pub use parse::{load, parse, SchemaError};
+
+include!(concat!(env!("OUT_DIR"), "/ir.rs"));
+
+#[cfg(feature = "alloc")]
+pub use walk::{header, walk, walk_schema, FieldValue, Header, MessageValue, Value};
+
+#[cfg(feature = "alloc")]
+mod walk {
+    use super::*;
+    use crate::*;
+    use alloc::vec::Vec;
+    use core::fmt;
+
+    /// Decoded value of a field, group or var data
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Value<'a> {
+        Int(i64),
+        Uint(u64),
+        Float(f64),
+        /// optional field holding its null value
+        Null,
+        /// `char` array up to its first NUL, or a constant `char` array
+        Text(&'a str),
+        /// `name` is `None` for the null value or values unknown to this schema
+        Enum {
+            raw: u64,
+            name: Option<&'a str>,
+        },
+        /// names of the choices set in `raw`
+        Set {
+            raw: u64,
+            choices: Vec<&'a str>,
+        },
+        /// fixed length array of a primitive other than `char`
+        Array(Vec<Value<'a>>),
+        Composite(Vec<FieldValue<'a>>),
+        /// one list of values per entry
+        Group(Vec<Vec<FieldValue<'a>>>),
+        Data(&'a [u8]),
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct FieldValue<'a> {
+        pub name: &'a str,
+        pub value: Value<'a>,
+    }
+
+    /// Decoded frame, `Display` prints it on one line
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct MessageValue<'a> {
+        pub message: &'a Message<'a>,
+        pub acting_version: u16,
+        /// fields, then groups, then var data, in schema order. Fields, groups and var data
+        /// newer than `acting_version` are left out.
+        pub fields: Vec<FieldValue<'a>>,
+        /// bytes of the frame used, header included
+        pub encoded_length: usize,
+    }
+
+    impl<'a> MessageValue<'a> {
+        #[inline]
+        pub fn get(&self, name: &str) -> Option<&Value<'a>> {
+            self.fields
+                .iter()
+                .find(|field| field.name == name)
+                .map(|field| &field.value)
+        }
+    }
+
+    /// Message header of a frame
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Header {
+        pub block_length: usize,
+        pub template_id: u16,
+        pub schema_id: u16,
+        pub version: u16,
+    }
+
+    /// Reads the `headerType` of `schema` at the start of `frame`, fails with `BufferTooShort`
+    /// for a frame shorter than it.
+    pub fn header(schema: &Schema, frame: &[u8]) -> SbeResult<Header> {
+        let reader = Reader::new(schema, frame);
+        reader.buf.check_bounds(0, schema.header.encoded_length)?;
+        let member = |name| reader.member(schema.header, name, 0);
+        Ok(Header {
+            block_length: member("blockLength") as usize,
+            template_id: member("templateId") as u16,
+            schema_id: member("schemaId") as u16,
+            version: member("version") as u16,
+        })
+    }
+
+    /// Decodes the frame at the start of `frame` with `SCHEMA`, any message and version. The
+    /// group dimensions of frames before version 4 are read as `group_size_encoding_codec` does.
+    /// Fails with `WrongSchemaId`, `WrongTemplateId` for a template missing from the schema,
+    /// `BufferTooShort`, `BlockLengthTooShort` or `NotAscii`, never panics.
+    pub fn walk(frame: &[u8]) -> SbeResult<MessageValue<'_>> {
+        walk_with(&SCHEMA, frame, true)
+    }
+
+    /// Decodes the frame at the start of `frame` with any schema, e.g. one read at runtime, and
+    /// fails like `walk`. The frame is read with the layout of `schema`, so frames of
+    /// messages.xml before version 4 need the messages.xml of their version.
+    pub fn walk_schema<'a>(schema: &'a Schema<'a>, frame: &'a [u8]) -> SbeResult<MessageValue<'a>> {
+        walk_with(schema, frame, false)
+    }
+
+    fn walk_with<'a>(
+        schema: &'a Schema<'a>,
+        frame: &'a [u8],
+        compiled: bool,
+    ) -> SbeResult<MessageValue<'a>> {
+        let header = header(schema, frame)?;
+        if header.schema_id != schema.id {
+            return Err(SbeErr::WrongSchemaId);
+        }
+        let message = schema
+            .message(header.template_id)
+            .ok_or(SbeErr::WrongTemplateId)?;
+
+        let mut walker = Walker {
+            reader: Reader::new(schema, frame),
+            acting_version: header.version,
+            limit: schema.header.encoded_length,
+            compiled,
+        };
+        let fields = walker.body(
+            message.fields,
+            message.groups,
+            message.data,
+            header.block_length,
+        )?;
+        Ok(MessageValue {
+            message,
+            acting_version: header.version,
+            fields,
+            encoded_length: walker.limit,
+        })
+    }
+
+    /// `ReadBuf` of either byte order
+    #[derive(Clone, Copy)]
+    struct Reader<'a> {
+        buf: ReadBuf<'a>,
+        big_endian: bool,
+    }
+
+    impl<'a> Reader<'a> {
+        fn new(schema: &Schema, frame: &'a [u8]) -> Self {
+            Self {
+                buf: ReadBuf::new(frame),
+                big_endian: schema.byte_order == ByteOrder::BigEndian,
+            }
+        }
+
+        /// value of an unsigned primitive, the bits of the others
+        fn raw(&self, primitive: Primitive, offset: usize) -> u64 {
+            let (value, size) = match primitive.size() {
+                1 => (self.buf.get_u8_at(offset) as u64, 1),
+                2 => (self.buf.get_u16_at(offset) as u64, 2),
+                4 => (self.buf.get_u32_at(offset) as u64, 4),
+                _ => (self.buf.get_u64_at(offset), 8),
+            };
+            if self.big_endian && size > 1 {
+                value.swap_bytes() >> (64 - 8 * size)
+            } else {
+                value
+            }
+        }
+
+        fn primitive(&self, primitive: Primitive, offset: usize) -> Value<'a> {
+            let raw = self.raw(primitive, offset);
+            match primitive {
+                Primitive::Int8 => Value::Int(raw as u8 as i8 as i64),
+                Primitive::Int16 => Value::Int(raw as u16 as i16 as i64),
+                Primitive::Int32 => Value::Int(raw as u32 as i32 as i64),
+                Primitive::Int64 => Value::Int(raw as i64),
+                Primitive::Float => Value::Float(f32::from_bits(raw as u32) as f64),
+                Primitive::Double => Value::Float(f64::from_bits(raw)),
+                _ => Value::Uint(raw),
+            }
+        }
+
+        /// primitive member `name` of the `composite` at `offset`. `parse` checks the header,
+        /// dimension and var data composites have theirs.
+        fn member(&self, composite: &Composite, name: &str, offset: usize) -> u64 {
+            match composite.member(name) {
+                Some(Field {
+                    ty: Type::Primitive(primitive),
+                    offset: member_offset,
+                    ..
+                }) => self.raw(*primitive, offset + member_offset),
+                _ => 0,
+            }
+        }
+    }
+
+    struct Walker<'a> {
+        reader: Reader<'a>,
+        acting_version: u16,
+        limit: usize,
+        /// frame of `SCHEMA`, its group dimensions change layout at version 4
+        compiled: bool,
+    }
+
+    impl<'a> Walker<'a> {
+        /// block at `limit`, then its groups and var data
+        fn body(
+            &mut self,
+            fields: &'a [Field<'a>],
+            groups: &'a [Group<'a>],
+            data: &'a [Data<'a>],
+            block_length: usize,
+        ) -> SbeResult<Vec<FieldValue<'a>>> {
+            if block_length < min_block_length(fields, self.acting_version) {
+                return Err(SbeErr::BlockLengthTooShort);
+            }
+            self.reader.buf.check_bounds(self.limit, block_length)?;
+            let offset = self.limit;
+            self.limit += block_length;
+
+            let acting_version = self.acting_version;
+            let mut values = Vec::with_capacity(fields.len() + groups.len() + data.len());
+            for field in fields
+                .iter()
+                .filter(|field| field.since_version <= acting_version)
+            {
+                values.push(FieldValue {
+                    name: field.name,
+                    value: self.field(field, offset + field.offset)?,
+                });
+            }
+            for group in groups
+                .iter()
+                .filter(|group| group.since_version <= acting_version)
+            {
+                values.push(FieldValue {
+                    name: group.name,
+                    value: self.group(group)?,
+                });
+            }
+            for data in data
+                .iter()
+                .filter(|data| data.since_version <= acting_version)
+            {
+                values.push(FieldValue {
+                    name: data.name,
+                    value: self.data(data)?,
+                });
+            }
+            Ok(values)
+        }
+
+        fn group(&mut self, group: &'a Group<'a>) -> SbeResult<Value<'a>> {
+            let buf = self.reader.buf;
+            let (dimension_length, block_length, count) = if self.compiled {
+                // messages.xml only declares the dimension of version 4 and later
+                let length = group_size_encoding_codec::acting_encoded_length(self.acting_version);
+                buf.check_bounds(self.limit, length)?;
+                let count = group_size_encoding_codec::acting_num_in_group(
+                    &buf,
+                    self.limit,
+                    self.acting_version,
+                );
+                (length, buf.get_u16_at(self.limit) as u64, count as u64)
+            } else {
+                let dimension = group.dimension;
+                buf.check_bounds(self.limit, dimension.encoded_length)?;
+                (
+                    dimension.encoded_length,
+                    self.reader.member(dimension, "blockLength", self.limit),
+                    self.reader.member(dimension, "numInGroup", self.limit),
+                )
+            };
+            self.limit += dimension_length;
+            // every entry needs its block, checked before allocating for `count` of them
+            let blocks = usize::try_from(count.saturating_mul(block_length))
+                .map_err(|_| SbeErr::BufferTooShort)?;
+            buf.check_bounds(self.limit, blocks)?;
+
+            // a block length of 0 passes the check above for any count, so do not trust it
+            // further than the bytes left
+            let block_length = block_length as usize;
+            let capacity = (buf.len() - self.limit) / block_length.max(1);
+            let mut entries = Vec::with_capacity(
+                usize::try_from(count).map_or(capacity, |count| count.min(capacity)),
+            );
+            for _ in 0..count {
+                entries.push(self.body(group.fields, group.groups, group.data, block_length)?);
+            }
+            Ok(Value::Group(entries))
+        }
+
+        fn data(&mut self, data: &Data) -> SbeResult<Value<'a>> {
+            let encoding = data.encoding;
+            let data_offset = encoding
+                .member("varData")
+                .map_or(encoding.encoded_length, |member| member.offset);
+            self.reader.buf.check_bounds(self.limit, data_offset)?;
+            let len = self.reader.member(encoding, "length", self.limit);
+            let len = usize::try_from(len).map_err(|_| SbeErr::BufferTooShort)?;
+            let start = self.limit + data_offset;
+            self.reader.buf.check_bounds(start, len)?;
+            self.limit = start + len;
+            Ok(Value::Data(self.reader.buf.get_slice_at(start, len)))
+        }
+
+        fn field(&self, field: &'a Field<'a>, offset: usize) -> SbeResult<Value<'a>> {
+            let reader = &self.reader;
+            match (field.presence, field.ty) {
+                (Presence::Constant(constant), ty) => return Ok(constant_value(ty, constant)),
+                (Presence::Optional(null), Type::Primitive(primitive))
+                    if reader.raw(primitive, offset) == null =>
+                {
+                    return Ok(Value::Null)
+                }
+                _ => {}
+            }
+            Ok(match field.ty {
+                Type::Primitive(primitive) => reader.primitive(primitive, offset),
+                Type::CharArray(array) => {
+                    Value::Text(reader.buf.get_char_array_at(offset, array.length)?)
+                }
+                Type::Array(array) => Value::Array(
+                    (0..array.length)
+                        .map(|i| {
+                            reader.primitive(array.primitive, offset + i * array.primitive.size())
+                        })
+                        .collect(),
+                ),
+                Type::Enum(ty) => {
+                    let raw = reader.raw(ty.encoding, offset);
+                    Value::Enum {
+                        raw,
+                        name: ty.value(raw).map(|value| value.name),
+                    }
+                }
+                Type::Set(ty) => {
+                    let raw = reader.raw(ty.encoding, offset);
+                    Value::Set {
+                        raw,
+                        choices: ty
+                            .choices
+                            .iter()
+                            .filter(|choice| choice.bit < 64 && raw & (1 << choice.bit) != 0)
+                            .map(|choice| choice.name)
+                            .collect(),
+                    }
+                }
+                Type::Composite(ty) => {
+                    let mut members = Vec::with_capacity(ty.fields.len());
+                    for member in ty
+                        .fields
+                        .iter()
+                        .filter(|member| member.since_version <= self.acting_version)
+                    {
+                        members.push(FieldValue {
+                            name: member.name,
+                            value: self.field(member, offset + member.offset)?,
+                        });
+                    }
+                    Value::Composite(members)
+                }
+            })
+        }
+    }
+
+    fn constant_value<'a>(ty: Type<'a>, constant: &'a str) -> Value<'a> {
+        match ty {
+            Type::Enum(ty) => match ty.value_by_name(constant) {
+                Some(value) => Value::Enum {
+                    raw: value.value,
+                    name: Some(value.name),
+                },
+                None => Value::Text(constant),
+            },
+            Type::Primitive(Primitive::Float | Primitive::Double) => {
+                constant.parse().map_or(Value::Text(constant), Value::Float)
+            }
+            Type::Primitive(primitive) if primitive.name().starts_with("int") => {
+                constant.parse().map_or(Value::Text(constant), Value::Int)
+            }
+            Type::Primitive(Primitive::Char) | Type::CharArray(_) => Value::Text(constant),
+            Type::Primitive(_) => constant.parse().map_or(Value::Text(constant), Value::Uint),
+            Type::Array(_) | Type::Set(_) | Type::Composite(_) => Value::Text(constant),
+        }
+    }
+
+    impl fmt::Display for Value<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Self::Int(value) => write!(f, "{value}"),
+                Self::Uint(value) => write!(f, "{value}"),
+                Self::Float(value) => write!(f, "{value}"),
+                Self::Null => f.write_str("null"),
+                Self::Text(text) => write!(f, "{text:?}"),
+                Self::Enum {
+                    name: Some(name), ..
+                } => f.write_str(name),
+                Self::Enum { raw, name: None } => write!(f, "{raw}"),
+                Self::Set { choices, .. } => {
+                    write!(f, "[{}]", joined(choices, |f, name| f.write_str(name)))
+                }
+                Self::Array(values) => {
+                    write!(f, "[{}]", joined(values, |f, value| write!(f, "{value}")))
+                }
+                Self::Composite(members) => write!(
+                    f,
+                    "{{ {} }}",
+                    joined(members, |f, member| write!(f, "{member}"))
+                ),
+                Self::Group(entries) => write!(
+                    f,
+                    "[{}]",
+                    joined(entries, |f, entry| write!(
+                        f,
+                        "{{ {} }}",
+                        joined(entry, |f, field| write!(f, "{field}"))
+                    ))
+                ),
+                Self::Data(bytes) => match core::str::from_utf8(bytes) {
+                    Ok(text) => write!(f, "{text:?}"),
+                    Err(_) => write!(f, "{bytes:?}"),
+                },
+            }
+        }
+    }
+
+    impl fmt::Display for FieldValue<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}: {}", self.name, self.value)
+        }
+    }
+
+    /// `OrderMessage { orderId: 234, ..., items: [{ productId: 222, ... }], customerNote: "..." }`
+    impl fmt::Display for MessageValue<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "{} {{ {} }}",
+                self.message.name,
+                joined(&self.fields, |f, field| write!(f, "{field}"))
+            )
+        }
+    }
+
+    /// items written by `write`, comma separated
+    struct Joined<'s, T, W>(&'s [T], W);
+
+    fn joined<T, W: Fn(&mut fmt::Formatter<'_>, &T) -> fmt::Result>(
+        items: &[T],
+        write: W,
+    ) -> Joined<'_, T, W> {
+        Joined(items, write)
+    }
+
+    impl<T, W: Fn(&mut fmt::Formatter<'_>, &T) -> fmt::Result> fmt::Display for Joined<'_, T, W> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            for (i, item) in self.0.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                (self.1)(f, item)?;
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,303 @@
+//! Types of the IR. They only borrow, the statics of messages.xml are `'static` and a schema
+//! read at runtime borrows from the `Bump` it was parsed into. build.rs compiles this file too.
+
+/// SBE primitive type
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Primitive {
+    Char,
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    Uint8,
+    Uint16,
+    Uint32,
+    Uint64,
+    Float,
+    Double,
+}
+
+impl Primitive {
+    /// encoded length in bytes
+    #[inline]
+    pub const fn size(self) -> usize {
+        match self {
+            Self::Char | Self::Int8 | Self::Uint8 => 1,
+            Self::Int16 | Self::Uint16 => 2,
+            Self::Int32 | Self::Uint32 | Self::Float => 4,
+            Self::Int64 | Self::Uint64 | Self::Double => 8,
+        }
+    }
+
+    /// name of the primitiveType in the schema
+    #[inline]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Char => "char",
+            Self::Int8 => "int8",
+            Self::Int16 => "int16",
+            Self::Int32 => "int32",
+            Self::Int64 => "int64",
+            Self::Uint8 => "uint8",
+            Self::Uint16 => "uint16",
+            Self::Uint32 => "uint32",
+            Self::Uint64 => "uint64",
+            Self::Float => "float",
+            Self::Double => "double",
+        }
+    }
+}
+
+/// Parses the name of a primitiveType, e.g. `uint16`
+impl core::str::FromStr for Primitive {
+    type Err = ();
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Ok(match name {
+            "char" => Self::Char,
+            "int8" => Self::Int8,
+            "int16" => Self::Int16,
+            "int32" => Self::Int32,
+            "int64" => Self::Int64,
+            "uint8" => Self::Uint8,
+            "uint16" => Self::Uint16,
+            "uint32" => Self::Uint32,
+            "uint64" => Self::Uint64,
+            "float" => Self::Float,
+            "double" => Self::Double,
+            _ => return Err(()),
+        })
+    }
+}
+
+/// `byteOrder` of the schema, every value of a frame is in it
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ByteOrder {
+    LittleEndian,
+    BigEndian,
+}
+
+/// Type of a field or composite member
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Type<'s> {
+    Primitive(Primitive),
+    /// fixed length `char` array, NUL padded
+    CharArray(&'s CharArray<'s>),
+    /// fixed length array of any other primitive
+    Array(&'s Array<'s>),
+    Enum(&'s Enum<'s>),
+    Set(&'s Set<'s>),
+    Composite(&'s Composite<'s>),
+}
+
+impl<'s> Type<'s> {
+    /// encoded length in bytes of a non constant field of this type
+    #[inline]
+    pub const fn encoded_length(&self) -> usize {
+        match self {
+            Self::Primitive(primitive) => primitive.size(),
+            Self::CharArray(array) => array.length,
+            Self::Array(array) => array.primitive.size() * array.length,
+            Self::Enum(ty) => ty.encoding.size(),
+            Self::Set(ty) => ty.encoding.size(),
+            Self::Composite(ty) => ty.encoded_length,
+        }
+    }
+
+    /// name of the type in the schema, a primitiveType for primitives
+    #[inline]
+    pub const fn name(&self) -> &'s str {
+        match self {
+            Self::Primitive(primitive) => primitive.name(),
+            Self::CharArray(array) => array.name,
+            Self::Array(array) => array.name,
+            Self::Enum(ty) => ty.name,
+            Self::Set(ty) => ty.name,
+            Self::Composite(ty) => ty.name,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CharArray<'s> {
+    pub name: &'s str,
+    pub length: usize,
+    pub character_encoding: &'s str,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Array<'s> {
+    pub name: &'s str,
+    pub primitive: Primitive,
+    pub length: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Enum<'s> {
+    pub name: &'s str,
+    pub encoding: Primitive,
+    pub values: &'s [ValidValue<'s>],
+}
+
+impl<'s> Enum<'s> {
+    /// valid value encoded as `raw`, `None` for the null value or values of a newer schema
+    #[inline]
+    pub fn value(&self, raw: u64) -> Option<&'s ValidValue<'s>> {
+        self.values.iter().find(|value| value.value == raw)
+    }
+
+    #[inline]
+    pub fn value_by_name(&self, name: &str) -> Option<&'s ValidValue<'s>> {
+        self.values.iter().find(|value| value.name == name)
+    }
+}
+
+/// `value` of a `char` enum is the character code
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValidValue<'s> {
+    pub name: &'s str,
+    pub value: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Set<'s> {
+    pub name: &'s str,
+    pub encoding: Primitive,
+    pub choices: &'s [Choice<'s>],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Choice<'s> {
+    pub name: &'s str,
+    pub bit: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Composite<'s> {
+    pub name: &'s str,
+    pub encoded_length: usize,
+    /// members, their `id` is 0 and `offset` is from the start of the composite
+    pub fields: &'s [Field<'s>],
+}
+
+impl<'s> Composite<'s> {
+    #[inline]
+    pub fn member(&self, name: &str) -> Option<&'s Field<'s>> {
+        self.fields.iter().find(|member| member.name == name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Presence<'s> {
+    Required,
+    /// primitive holding this raw value is null, SBE's null value of the primitive unless the
+    /// schema sets `nullValue`
+    Optional(u64),
+    /// takes no space on the wire, the value as written in the schema, a `valueRef` of an enum
+    /// holds the name of the valid value
+    Constant(&'s str),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Field<'s> {
+    pub name: &'s str,
+    pub id: u16,
+    /// offset from the start of the block, constants take the offset of the next field
+    pub offset: usize,
+    pub ty: Type<'s>,
+    pub presence: Presence<'s>,
+    pub since_version: u16,
+    pub semantic_type: Option<&'s str>,
+    pub description: &'s str,
+}
+
+impl Field<'_> {
+    /// encoded length in bytes, 0 for constants
+    #[inline]
+    pub const fn encoded_length(&self) -> usize {
+        match self.presence {
+            Presence::Constant(_) => 0,
+            _ => self.ty.encoded_length(),
+        }
+    }
+}
+
+/// Repeating group, on the wire a `dimension` with the `blockLength` and `numInGroup` of the
+/// entries, then the entries, each a block followed by its nested groups and var data
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Group<'s> {
+    pub name: &'s str,
+    pub id: u16,
+    pub block_length: u16,
+    pub since_version: u16,
+    /// the `dimensionType` composite
+    pub dimension: &'s Composite<'s>,
+    pub fields: &'s [Field<'s>],
+    pub groups: &'s [Group<'s>],
+    pub data: &'s [Data<'s>],
+    pub description: &'s str,
+}
+
+/// Var data field, on the wire an `encoding` composite: the `length` then `length` bytes of
+/// `varData`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Data<'s> {
+    pub name: &'s str,
+    pub id: u16,
+    pub since_version: u16,
+    pub encoding: &'s Composite<'s>,
+    pub character_encoding: &'s str,
+    pub description: &'s str,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Message<'s> {
+    pub name: &'s str,
+    /// template id
+    pub id: u16,
+    pub block_length: u16,
+    pub fields: &'s [Field<'s>],
+    pub groups: &'s [Group<'s>],
+    pub data: &'s [Data<'s>],
+    pub description: &'s str,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Schema<'s> {
+    pub package: &'s str,
+    pub id: u16,
+    pub version: u16,
+    pub semantic_version: &'s str,
+    pub byte_order: ByteOrder,
+    /// the `headerType` composite, with `blockLength`, `templateId`, `schemaId` and `version`
+    pub header: &'s Composite<'s>,
+    /// types declared in `<types>`, in schema order. A `<type>` of length 1 is its primitive.
+    pub types: &'s [Type<'s>],
+    pub messages: &'s [Message<'s>],
+}
+
+impl<'s> Schema<'s> {
+    #[inline]
+    pub fn message(&self, template_id: u16) -> Option<&'s Message<'s>> {
+        self.messages
+            .iter()
+            .find(|message| message.id == template_id)
+    }
+
+    #[inline]
+    pub fn message_by_name(&self, name: &str) -> Option<&'s Message<'s>> {
+        self.messages.iter().find(|message| message.name == name)
+    }
+}
+
+/// Block length holding every field a sender at `acting_version` writes
+#[inline]
+pub fn min_block_length(fields: &[Field], acting_version: u16) -> usize {
+    fields
+        .iter()
+        .filter(|field| field.since_version <= acting_version)
+        .filter(|field| !matches!(field.presence, Presence::Constant(_)))
+        .map(|field| field.offset + field.encoded_length())
+        .max()
+        .unwrap_or_default()
+}
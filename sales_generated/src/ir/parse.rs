@@ -0,0 +1,516 @@
+//! Reads an SBE xml schema into the IR. build.rs generates the statics of messages.xml with it,
+//! with the `xml` feature tools read schemas they were not compiled against at runtime, e.g.
+//! another team's schema or an older messages.xml.
+
+use std::{collections::HashMap, fmt, fs, io, path::Path, str::FromStr};
+
+use bumpalo::Bump;
+use roxmltree::{Document, Node};
+
+use super::model::*;
+
+#[derive(Debug)]
+pub enum SchemaError {
+    Io(io::Error),
+    Xml(roxmltree::Error),
+    /// required attribute is missing, `element` is e.g. `field quantity`
+    MissingAttribute {
+        element: String,
+        attribute: &'static str,
+    },
+    /// attribute or text does not parse, e.g. an id that is not a number
+    InvalidValue {
+        element: String,
+        value: String,
+    },
+    /// type is neither a primitive nor declared in `<types>`
+    UnknownType(String),
+    /// type contains itself, directly or through other declared types
+    RecursiveType(String),
+    /// composite used as message header, group dimension or var data lacks a member
+    MissingMember {
+        composite: String,
+        member: &'static str,
+    },
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "cannot read schema: {e}"),
+            Self::Xml(e) => write!(f, "schema is not valid xml: {e}"),
+            Self::MissingAttribute { element, attribute } => {
+                write!(f, "{element} has no attribute {attribute}")
+            }
+            Self::InvalidValue { element, value } => {
+                write!(f, "invalid value {value:?} in {element}")
+            }
+            Self::UnknownType(name) => write!(f, "unknown type {name}"),
+            Self::RecursiveType(name) => write!(f, "type {name} contains itself"),
+            Self::MissingMember { composite, member } => {
+                write!(f, "composite {composite} has no member {member}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Xml(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SchemaError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<roxmltree::Error> for SchemaError {
+    fn from(e: roxmltree::Error) -> Self {
+        Self::Xml(e)
+    }
+}
+
+/// Reads the schema file at `path`, see `parse`
+pub fn load(path: impl AsRef<Path>, bump: &Bump) -> Result<Schema<'_>, SchemaError> {
+    parse(&fs::read_to_string(path)?, bump)
+}
+
+/// Reads `xml` into IR allocated in `bump`. Offsets and block lengths the schema leaves out are
+/// laid out like the SBE tool does, the header, group dimension and var data composites are
+/// checked for the members a decoder reads.
+pub fn parse<'s>(xml: &str, bump: &'s Bump) -> Result<Schema<'s>, SchemaError> {
+    let doc = Document::parse(xml)?;
+    let root = doc.root_element();
+    let declared: Vec<Node> = doc
+        .descendants()
+        .filter(|node| node.has_tag_name("types"))
+        .flat_map(|types| types.children().filter(Node::is_element))
+        .collect();
+    let mut parser = Parser {
+        bump,
+        nodes: declared
+            .iter()
+            .filter_map(|node| Some((node.attribute("name")?, *node)))
+            .collect(),
+        types: HashMap::new(),
+        resolving: Vec::new(),
+    };
+
+    let byte_order = match root.attribute("byteOrder").unwrap_or("littleEndian") {
+        "littleEndian" => ByteOrder::LittleEndian,
+        "bigEndian" => ByteOrder::BigEndian,
+        value => return Err(invalid(root, value)),
+    };
+    let header = parser.composite(root.attribute("headerType").unwrap_or("messageHeader"))?;
+    for name in ["blockLength", "templateId", "schemaId", "version"] {
+        primitive_member(header, name)?;
+    }
+    let mut types = Vec::with_capacity(declared.len());
+    for node in &declared {
+        types.push(parser.named(attribute(*node, "name")?)?.0);
+    }
+    let mut messages = Vec::new();
+    for node in root
+        .children()
+        .filter(|node| node.tag_name().name() == "message")
+    {
+        messages.push(parser.message(node)?);
+    }
+
+    Ok(Schema {
+        package: bump.alloc_str(root.attribute("package").unwrap_or_default()),
+        id: parse_attribute(root, "id")?,
+        version: parse_optional(root, "version")?.unwrap_or(0),
+        semantic_version: bump.alloc_str(root.attribute("semanticVersion").unwrap_or_default()),
+        byte_order,
+        header,
+        types: bump.alloc_slice_copy(&types),
+        messages: bump.alloc_slice_copy(&messages),
+    })
+}
+
+fn describe(node: Node) -> String {
+    match node.attribute("name") {
+        Some(name) => format!("{} {name}", node.tag_name().name()),
+        None => node.tag_name().name().to_string(),
+    }
+}
+
+fn invalid(node: Node, value: &str) -> SchemaError {
+    SchemaError::InvalidValue {
+        element: describe(node),
+        value: value.to_string(),
+    }
+}
+
+fn attribute<'a>(node: Node<'a, '_>, attribute: &'static str) -> Result<&'a str, SchemaError> {
+    node.attribute(attribute)
+        .ok_or_else(|| SchemaError::MissingAttribute {
+            element: describe(node),
+            attribute,
+        })
+}
+
+fn parse_optional<T: FromStr>(node: Node, name: &'static str) -> Result<Option<T>, SchemaError> {
+    node.attribute(name)
+        .map(|value| value.trim().parse().map_err(|_| invalid(node, value)))
+        .transpose()
+}
+
+fn parse_attribute<T: FromStr>(node: Node, name: &'static str) -> Result<T, SchemaError> {
+    let value = attribute(node, name)?;
+    value.trim().parse().map_err(|_| invalid(node, value))
+}
+
+fn since_version(node: Node) -> Result<u16, SchemaError> {
+    Ok(parse_optional(node, "sinceVersion")?.unwrap_or(0))
+}
+
+/// `blockLength` of a message or group, else the length its fields take
+fn block_length(node: Node, fields_length: usize) -> Result<u16, SchemaError> {
+    match parse_optional(node, "blockLength")? {
+        Some(block_length) => Ok(block_length),
+        None => u16::try_from(fields_length).map_err(|_| invalid(node, &fields_length.to_string())),
+    }
+}
+
+/// primitive member `name` of a composite
+fn primitive_member<'s>(
+    composite: &Composite<'s>,
+    name: &'static str,
+) -> Result<&'s Field<'s>, SchemaError> {
+    composite
+        .member(name)
+        .filter(|member| matches!(member.ty, Type::Primitive(_)))
+        .ok_or_else(|| SchemaError::MissingMember {
+            composite: composite.name.to_string(),
+            member: name,
+        })
+}
+
+/// null value of an optional primitive, SBE's default unless the schema sets `nullValue`
+fn null_value(node: Node, primitive: Primitive) -> Result<u64, SchemaError> {
+    let bits = 8 * primitive.size() as u32;
+    let mask = u64::MAX >> (64 - bits);
+    let Some(value) = node.attribute("nullValue") else {
+        return Ok(match primitive {
+            Primitive::Char => 0,
+            Primitive::Int8 | Primitive::Int16 | Primitive::Int32 | Primitive::Int64 => {
+                1 << (bits - 1)
+            }
+            Primitive::Float => f32::NAN.to_bits() as u64,
+            Primitive::Double => f64::NAN.to_bits(),
+            _ => mask,
+        });
+    };
+    let value = value.trim();
+    let raw = match primitive {
+        Primitive::Float => value
+            .parse::<f32>()
+            .map(|value| value.to_bits() as u64)
+            .ok(),
+        Primitive::Double => value.parse::<f64>().map(f64::to_bits).ok(),
+        _ => value
+            .parse::<u64>()
+            .ok()
+            .or_else(|| value.parse::<i64>().ok().map(|value| value as u64 & mask)),
+    };
+    raw.ok_or_else(|| invalid(node, value))
+}
+
+/// `text` as the bits of an integer `primitive`, negative for the signed ones
+fn integer(primitive: Primitive, text: &str) -> Option<u64> {
+    let bits = 8 * primitive.size() as u32;
+    let mask = u64::MAX >> (64 - bits);
+    match primitive {
+        Primitive::Int8 | Primitive::Int16 | Primitive::Int32 | Primitive::Int64 => {
+            let value: i64 = text.parse().ok()?;
+            // fits if it survives sign extension from the encoding's width
+            (value << (64 - bits) >> (64 - bits) == value).then_some(value as u64 & mask)
+        }
+        Primitive::Float | Primitive::Double => None,
+        _ => text.parse().ok().filter(|value| value & !mask == 0),
+    }
+}
+
+struct Parser<'s, 'a, 'input> {
+    bump: &'s Bump,
+    /// declarations of `<types>` by name
+    nodes: HashMap<&'a str, Node<'a, 'input>>,
+    /// declared types read so far, each is allocated once however many fields use it
+    types: HashMap<&'a str, (Type<'s>, Presence<'s>)>,
+    /// declared types being read, one of them used again is a cycle
+    resolving: Vec<&'a str>,
+}
+
+impl<'s, 'a> Parser<'s, 'a, '_> {
+    fn text(&self, node: Node) -> &'s str {
+        self.bump.alloc_str(node.text().unwrap_or_default().trim())
+    }
+
+    fn optional_str(&self, node: Node, name: &str) -> Option<&'s str> {
+        node.attribute(name)
+            .map(|value| &*self.bump.alloc_str(value))
+    }
+
+    fn description(&self, node: Node) -> &'s str {
+        self.optional_str(node, "description").unwrap_or_default()
+    }
+
+    /// type `name` and the presence it declares, a primitive name or a type of `<types>`
+    fn named(&mut self, name: &str) -> Result<(Type<'s>, Presence<'s>), SchemaError> {
+        if let Ok(primitive) = name.parse() {
+            return Ok((Type::Primitive(primitive), Presence::Required));
+        }
+        let (&name, &node) = self
+            .nodes
+            .get_key_value(name)
+            .ok_or_else(|| SchemaError::UnknownType(name.to_string()))?;
+        if let Some(declared) = self.types.get(name) {
+            return Ok(*declared);
+        }
+        if self.resolving.contains(&name) {
+            return Err(SchemaError::RecursiveType(name.to_string()));
+        }
+        self.resolving.push(name);
+        let declared = self.declared(node);
+        self.resolving.pop();
+        let declared = declared?;
+        self.types.insert(name, declared);
+        Ok(declared)
+    }
+
+    fn composite(&mut self, name: &str) -> Result<&'s Composite<'s>, SchemaError> {
+        match self.named(name)? {
+            (Type::Composite(composite), _) => Ok(composite),
+            _ => Err(SchemaError::UnknownType(name.to_string())),
+        }
+    }
+
+    fn primitive(&mut self, node: Node, name: &str) -> Result<Primitive, SchemaError> {
+        match self.named(name)? {
+            (Type::Primitive(primitive), _) => Ok(primitive),
+            _ => Err(invalid(node, name)),
+        }
+    }
+
+    /// `<type>`, `<composite>`, `<enum>`, `<set>` or `<ref>` element
+    fn declared(&mut self, node: Node<'a, '_>) -> Result<(Type<'s>, Presence<'s>), SchemaError> {
+        let name = &*self.bump.alloc_str(attribute(node, "name")?);
+        match node.tag_name().name() {
+            "type" => {
+                let primitive = self.primitive(node, attribute(node, "primitiveType")?)?;
+                let presence = match node.attribute("presence") {
+                    Some("constant") => Presence::Constant(self.text(node)),
+                    Some("optional") => Presence::Optional(null_value(node, primitive)?),
+                    _ => Presence::Required,
+                };
+                let ty = match (primitive, parse_optional(node, "length")?) {
+                    (Primitive::Char, Some(length)) => Type::CharArray(
+                        self.bump.alloc(CharArray {
+                            name,
+                            length,
+                            character_encoding: self
+                                .optional_str(node, "characterEncoding")
+                                .unwrap_or("US-ASCII"),
+                        }),
+                    ),
+                    (_, None | Some(1)) => Type::Primitive(primitive),
+                    (primitive, Some(length)) => Type::Array(self.bump.alloc(Array {
+                        name,
+                        primitive,
+                        length,
+                    })),
+                };
+                Ok((ty, presence))
+            }
+            "composite" => {
+                let mut members = Vec::new();
+                let mut offset = 0;
+                let mut encoded_length = 0;
+                for member in node.children().filter(Node::is_element) {
+                    let (ty, presence) = self.declared(member)?;
+                    let member = Field {
+                        name: self.bump.alloc_str(attribute(member, "name")?),
+                        id: 0,
+                        offset: parse_optional(member, "offset")?.unwrap_or(offset),
+                        ty,
+                        presence,
+                        since_version: since_version(member)?,
+                        semantic_type: self.optional_str(member, "semanticType"),
+                        description: self.description(member),
+                    };
+                    offset = member.offset + member.encoded_length();
+                    // explicit offsets may put the last member before others
+                    encoded_length = encoded_length.max(offset);
+                    members.push(member);
+                }
+                let composite = self.bump.alloc(Composite {
+                    name,
+                    encoded_length,
+                    fields: self.bump.alloc_slice_copy(&members),
+                });
+                Ok((Type::Composite(composite), Presence::Required))
+            }
+            "enum" => {
+                let encoding = self.primitive(node, attribute(node, "encodingType")?)?;
+                let mut values = Vec::new();
+                for value in node
+                    .children()
+                    .filter(|value| value.has_tag_name("validValue"))
+                {
+                    let text = value.text().unwrap_or_default().trim();
+                    let raw = match encoding {
+                        Primitive::Char if text.len() == 1 => Some(text.as_bytes()[0] as u64),
+                        _ => integer(encoding, text),
+                    };
+                    values.push(ValidValue {
+                        name: self.bump.alloc_str(attribute(value, "name")?),
+                        value: raw.ok_or_else(|| invalid(value, text))?,
+                    });
+                }
+                let ty = self.bump.alloc(Enum {
+                    name,
+                    encoding,
+                    values: self.bump.alloc_slice_copy(&values),
+                });
+                Ok((Type::Enum(ty), Presence::Required))
+            }
+            "set" => {
+                let encoding = self.primitive(node, attribute(node, "encodingType")?)?;
+                let mut choices = Vec::new();
+                for choice in node
+                    .children()
+                    .filter(|choice| choice.has_tag_name("choice"))
+                {
+                    let text = choice.text().unwrap_or_default().trim();
+                    choices.push(Choice {
+                        name: self.bump.alloc_str(attribute(choice, "name")?),
+                        bit: text.parse().map_err(|_| invalid(choice, text))?,
+                    });
+                }
+                let ty = self.bump.alloc(Set {
+                    name,
+                    encoding,
+                    choices: self.bump.alloc_slice_copy(&choices),
+                });
+                Ok((Type::Set(ty), Presence::Required))
+            }
+            "ref" => self.named(attribute(node, "type")?),
+            _ => Err(invalid(node, node.tag_name().name())),
+        }
+    }
+
+    fn message(&mut self, node: Node<'a, '_>) -> Result<Message<'s>, SchemaError> {
+        let (fields, fields_length) = self.fields(node)?;
+        Ok(Message {
+            name: self.bump.alloc_str(attribute(node, "name")?),
+            id: parse_attribute(node, "id")?,
+            block_length: block_length(node, fields_length)?,
+            fields,
+            groups: self.groups(node)?,
+            data: self.data(node)?,
+            description: self.description(node),
+        })
+    }
+
+    /// `<field>` children of `node` and the block length they take
+    fn fields(&mut self, node: Node<'a, '_>) -> Result<(&'s [Field<'s>], usize), SchemaError> {
+        let mut fields = Vec::new();
+        let mut offset = 0;
+        for node in node.children().filter(|child| child.has_tag_name("field")) {
+            let (ty, declared) = self.named(attribute(node, "type")?)?;
+            let presence = match node.attribute("presence") {
+                Some("constant") => match node.attribute("valueRef") {
+                    Some(value_ref) => {
+                        let (_, name) = value_ref
+                            .split_once('.')
+                            .ok_or_else(|| invalid(node, value_ref))?;
+                        Presence::Constant(self.bump.alloc_str(name))
+                    }
+                    None => match declared {
+                        Presence::Constant(value) => Presence::Constant(value),
+                        _ => return Err(invalid(node, "constant")),
+                    },
+                },
+                Some("optional") => match ty {
+                    Type::Primitive(primitive) => Presence::Optional(null_value(node, primitive)?),
+                    _ => declared,
+                },
+                _ => declared,
+            };
+            let field = Field {
+                name: self.bump.alloc_str(attribute(node, "name")?),
+                id: parse_attribute(node, "id")?,
+                offset: parse_optional(node, "offset")?.unwrap_or(offset),
+                ty,
+                presence,
+                since_version: since_version(node)?,
+                semantic_type: self.optional_str(node, "semanticType"),
+                description: self.description(node),
+            };
+            offset = offset.max(field.offset + field.encoded_length());
+            fields.push(field);
+        }
+        Ok((self.bump.alloc_slice_copy(&fields), offset))
+    }
+
+    fn groups(&mut self, node: Node<'a, '_>) -> Result<&'s [Group<'s>], SchemaError> {
+        let mut groups = Vec::new();
+        for node in node.children().filter(|child| child.has_tag_name("group")) {
+            let dimension = self.composite(
+                node.attribute("dimensionType")
+                    .unwrap_or("groupSizeEncoding"),
+            )?;
+            primitive_member(dimension, "blockLength")?;
+            primitive_member(dimension, "numInGroup")?;
+            let (fields, fields_length) = self.fields(node)?;
+            groups.push(Group {
+                name: self.bump.alloc_str(attribute(node, "name")?),
+                id: parse_attribute(node, "id")?,
+                block_length: block_length(node, fields_length)?,
+                since_version: since_version(node)?,
+                dimension,
+                fields,
+                groups: self.groups(node)?,
+                data: self.data(node)?,
+                description: self.description(node),
+            });
+        }
+        Ok(self.bump.alloc_slice_copy(&groups))
+    }
+
+    fn data(&mut self, node: Node<'a, '_>) -> Result<&'s [Data<'s>], SchemaError> {
+        let mut data = Vec::new();
+        for node in node.children().filter(|child| child.has_tag_name("data")) {
+            let encoding_name = attribute(node, "type")?;
+            let encoding = self.composite(encoding_name)?;
+            primitive_member(encoding, "length")?;
+            encoding
+                .member("varData")
+                .ok_or_else(|| SchemaError::MissingMember {
+                    composite: encoding.name.to_string(),
+                    member: "varData",
+                })?;
+            let character_encoding = self.nodes[encoding_name]
+                .children()
+                .find(|member| member.attribute("name") == Some("varData"))
+                .and_then(|member| self.optional_str(member, "characterEncoding"));
+            data.push(Data {
+                name: self.bump.alloc_str(attribute(node, "name")?),
+                id: parse_attribute(node, "id")?,
+                since_version: since_version(node)?,
+                encoding,
+                character_encoding: character_encoding.unwrap_or_default(),
+                description: self.description(node),
+            });
+        }
+        Ok(self.bump.alloc_slice_copy(&data))
+    }
+}
@@ -1,22 +1,36 @@
+//! Codecs of messages.xml. Generated once with sbe-all 1.37.0 and maintained by hand since, the
+//! README lists which files are still unchanged generator output and how to port a schema change.
+
+#![cfg_attr(not(feature = "std"), no_std)]
 #![forbid(unsafe_code)]
 #![allow(clippy::all)]
 #![allow(non_camel_case_types)]
 
 #![allow(ambiguous_glob_reexports)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 use ::core::{convert::TryInto};
 
 pub mod decimal_codec;
+pub mod direction;
+mod display;
 pub mod group_size_encoding_codec;
+pub mod ir;
 pub mod message_header_codec;
+pub mod order_flags;
 pub mod order_message_codec;
 pub mod order_response_codec;
 pub mod order_status;
 pub mod order_type;
+pub mod timestamp;
 pub mod var_string_encoding_codec;
 
+pub use timestamp::{Clock, Timestamp};
+
 pub const SBE_SCHEMA_ID: u16 = 100;
-pub const SBE_SCHEMA_VERSION: u16 = 1;
+pub const SBE_SCHEMA_VERSION: u16 = 5;
 pub const SBE_SEMANTIC_VERSION: &str = "1.0.0";
 
 pub type SbeResult<T> = core::result::Result<T, SbeErr>;
@@ -24,6 +38,28 @@ pub type SbeResult<T> = core::result::Result<T, SbeErr>;
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum SbeErr {
     ParentNotSet,
+    /// field, block, group or var data extends past the end of the buffer
+    BufferTooShort,
+    /// acting block length of a message or group is smaller than its schema block length
+    BlockLengthTooShort,
+    /// message header names another template than the decoder expects
+    WrongTemplateId,
+    /// message header names another schema, see `ir::walk`
+    WrongSchemaId,
+    /// group count does not fit the 'numInGroup' of the group dimension, see
+    /// `group_size_encoding_codec::NUM_IN_GROUP_MAX`
+    CountOutOfRange,
+    /// var data is longer than its 'length' can count, see
+    /// `var_string_encoding_codec::LENGTH_MAX`
+    DataTooLong,
+    /// value is longer than the fixed length of a `char` array field
+    CharArrayTooLong,
+    /// `char` array field declared as US-ASCII holds a byte other than printable US-ASCII before
+    /// its NUL padding, or a value to encode holds one
+    NotAscii,
+    /// time is before the unix epoch or too late for a `u64` of nanoseconds, see
+    /// `timestamp::Timestamp`
+    TimestampOutOfRange,
 }
 impl core::fmt::Display for SbeErr {
     #[inline]
@@ -31,7 +67,7 @@ impl core::fmt::Display for SbeErr {
         write!(f, "{self:?}")
     }
 }
-impl std::error::Error for SbeErr {}
+impl core::error::Error for SbeErr {}
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Either<L, R> {
@@ -78,6 +114,35 @@ impl<'a> ReadBuf<'a> {
         Self { data }
     }
 
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Ok if `len` bytes starting at `index` are inside the buffer
+    #[inline]
+    pub fn check_bounds(&self, index: usize, len: usize) -> SbeResult<()> {
+        match index.checked_add(len) {
+            Some(end) if end <= self.data.len() => Ok(()),
+            _ => Err(SbeErr::BufferTooShort),
+        }
+    }
+
+    /// The `N` bytes at `index` as a fixed size array, checked once so that reads from the
+    /// array need no further bounds checks
+    #[inline]
+    pub fn get_array_at<const N: usize>(&self, index: usize) -> SbeResult<&'a [u8; N]> {
+        self.data
+            .get(index..)
+            .and_then(|rest| rest.first_chunk::<N>())
+            .ok_or(SbeErr::BufferTooShort)
+    }
+
     #[inline]
     pub(crate) fn get_bytes_at<const N: usize>(slice: &[u8], index: usize) -> [u8; N] {
         slice[index..index+N].try_into().expect("slice with incorrect length")
@@ -134,30 +199,185 @@ impl<'a> ReadBuf<'a> {
     }
 
     #[inline]
-    pub fn get_slice_at(&self, index: usize, len: usize) -> &[u8] {
+    pub fn get_slice_at(&self, index: usize, len: usize) -> &'a [u8] {
         &self.data[index..index+len]
     }
 
+    /// `char` array of `len` bytes at `index` up to its first NUL, fails with `NotAscii`
+    #[inline]
+    pub fn get_char_array_at(&self, index: usize, len: usize) -> SbeResult<&'a str> {
+        char_array_str(&self.data[index..index+len])
+    }
+
+}
+
+/// Text of a NUL padded `char` array up to its first NUL, fails with `NotAscii` if it holds bytes
+/// outside printable US-ASCII
+#[inline]
+pub fn char_array_str(bytes: &[u8]) -> SbeResult<&str> {
+    let text = bytes.split(|&b| b == 0).next().unwrap_or_default();
+    if !is_printable(text) {
+        return Err(SbeErr::NotAscii);
+    }
+    core::str::from_utf8(text).map_err(|_| SbeErr::NotAscii)
+}
+
+/// `value` as a `char` array of `N` bytes padded with NUL, fails with `CharArrayTooLong` past `N`
+/// bytes or `NotAscii` outside printable US-ASCII. A NUL or control character would not read
+/// back, the decoder stops at the first NUL.
+#[inline]
+pub fn char_array<const N: usize>(value: &str) -> SbeResult<[u8; N]> {
+    if !is_printable(value.as_bytes()) {
+        return Err(SbeErr::NotAscii);
+    }
+    let mut array = [0u8; N];
+    array
+        .get_mut(..value.len())
+        .ok_or(SbeErr::CharArrayTooLong)?
+        .copy_from_slice(value.as_bytes());
+    Ok(array)
+}
+
+/// US-ASCII from space to `~`, what a `char` array holds before its padding
+#[inline]
+fn is_printable(bytes: &[u8]) -> bool {
+    bytes.iter().all(|b| matches!(b, b' '..=b'~'))
+}
+
+/// `N` bytes at the constant offset `AT` of a fixed size block, an offset outside the block fails
+/// to compile
+#[inline]
+pub(crate) fn get_block_bytes<const AT: usize, const N: usize, const LEN: usize>(block: &[u8; LEN]) -> [u8; N] {
+    const { assert!(AT + N <= LEN) };
+    core::array::from_fn(|i| block[AT + i])
+}
+
+/// Destination of a `WriteBuf` that can grow while encoding. Fixed slices are written directly.
+///
+/// Encoders only ever grow the buffer, the frame ends at their `get_limit()`. A reused buffer
+/// keeps what lies past that until `finish` is called with it.
+pub trait WriteBuffer {
+    /// The whole buffer, grown to at least `len` bytes if it can grow. A buffer that cannot
+    /// returns what it has and a write past its end panics, as for a plain slice.
+    fn writable(&mut self, len: usize) -> &mut [u8];
+
+    /// Ends encoding at `len`, the `get_limit()` of the encoder. Growable buffers drop the bytes
+    /// past it, fixed ones are left as they are.
+    #[inline]
+    fn finish(&mut self, len: usize) {
+        let _ = len;
+    }
+}
+
+impl WriteBuffer for [u8] {
+    #[inline]
+    fn writable(&mut self, _len: usize) -> &mut [u8] {
+        self
+    }
+}
+
+impl WriteBuffer for &mut [u8] {
+    #[inline]
+    fn writable(&mut self, _len: usize) -> &mut [u8] {
+        self
+    }
+}
+
+/// Grows with zeroes up to the last byte written, `finish` truncates a reused `Vec` to the frame.
+#[cfg(feature = "alloc")]
+impl WriteBuffer for alloc::vec::Vec<u8> {
+    #[inline]
+    fn writable(&mut self, len: usize) -> &mut [u8] {
+        if self.len() < len {
+            self.resize(len, 0);
+        }
+        self
+    }
+
+    #[inline]
+    fn finish(&mut self, len: usize) {
+        self.truncate(len);
+    }
+}
+
+/// Grows with zeroes up to the last byte written, `finish` truncates a reused `BytesMut` to the
+/// frame.
+#[cfg(feature = "bytes")]
+impl WriteBuffer for bytes::BytesMut {
+    #[inline]
+    fn writable(&mut self, len: usize) -> &mut [u8] {
+        if self.len() < len {
+            self.resize(len, 0);
+        }
+        self
+    }
+
+    #[inline]
+    fn finish(&mut self, len: usize) {
+        self.truncate(len);
+    }
+}
+
+enum WriteData<'a> {
+    Fixed(&'a mut [u8]),
+    Growable(&'a mut dyn WriteBuffer),
+}
+
+impl WriteData<'_> {
+    /// buffer with at least `len` bytes if it can grow
+    #[inline]
+    fn writable(&mut self, len: usize) -> &mut [u8] {
+        match self {
+            Self::Fixed(data) => data,
+            Self::Growable(buffer) => Self::grow(&mut **buffer, len),
+        }
+    }
+
+    /// kept out of line so the fixed slice path stays as small as before
+    #[cold]
+    #[inline(never)]
+    fn grow(buffer: &mut dyn WriteBuffer, len: usize) -> &mut [u8] {
+        buffer.writable(len)
+    }
 }
 
-#[derive(Debug, Default)]
 pub struct WriteBuf<'a> {
-    data: &'a mut [u8],
+    data: WriteData<'a>,
+}
+impl Default for WriteBuf<'_> {
+    fn default() -> Self {
+        Self::new(&mut [])
+    }
+}
+impl core::fmt::Debug for WriteBuf<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.data {
+            WriteData::Fixed(data) => f.debug_struct("WriteBuf").field("data", data).finish(),
+            WriteData::Growable(_) => f.debug_struct("WriteBuf").finish_non_exhaustive(),
+        }
+    }
 }
 impl<'a> WriteBuf<'a> {
     pub fn new(data: &'a mut [u8]) -> Self {
-        Self { data }
+        Self { data: WriteData::Fixed(data) }
+    }
+
+    /// Writes into a buffer that grows as needed, e.g. `Vec<u8>` or `BytesMut`, so the encoded
+    /// size need not be known up front: `WriteBuf::growable(&mut vec)`. The frame is the first
+    /// `get_limit()` bytes of the buffer, see `WriteBuffer::finish`.
+    pub fn growable(buffer: &'a mut dyn WriteBuffer) -> Self {
+        Self { data: WriteData::Growable(buffer) }
     }
 
     #[inline]
     pub fn put_bytes_at<const COUNT: usize>(&mut self, index: usize, bytes: &[u8; COUNT]) -> usize {
-        self.data[index..index + COUNT].copy_from_slice(bytes);
+        self.data.writable(index + COUNT)[index..index + COUNT].copy_from_slice(bytes);
         COUNT
     }
 
     #[inline]
     pub fn put_u8_at(&mut self, index: usize, value: u8) {
-        self.data[index] = value;
+        self.data.writable(index + 1)[index] = value;
     }
 
     #[inline]
@@ -208,7 +428,7 @@ impl<'a> WriteBuf<'a> {
     #[inline]
     pub fn put_slice_at(&mut self, index: usize, src: &[u8]) -> usize {
         let len = src.len();
-        let dest = self.data.split_at_mut(index).1.split_at_mut(len).0;
+        let dest = self.data.writable(index + len).split_at_mut(index).1.split_at_mut(len).0;
         dest.clone_from_slice(src);
         len
     }
@@ -216,7 +436,7 @@ impl<'a> WriteBuf<'a> {
 impl<'a> From<&'a mut WriteBuf<'a>> for &'a mut [u8] {
     #[inline]
     fn from(buf: &'a mut WriteBuf<'a>) -> &'a mut [u8] {
-        buf.data
+        buf.data.writable(0)
     }
 }
 
@@ -1,5 +1,6 @@
 use crate::*;
 
+pub use decoder::OrderMessageBlockDecoder;
 pub use decoder::OrderMessageDecoder;
 pub use encoder::OrderMessageEncoder;
 
@@ -7,7 +8,7 @@ pub use crate::SBE_SCHEMA_ID;
 pub use crate::SBE_SCHEMA_VERSION;
 pub use crate::SBE_SEMANTIC_VERSION;
 
-pub const SBE_BLOCK_LENGTH: u16 = 25;
+pub const SBE_BLOCK_LENGTH: u16 = 26;
 pub const SBE_TEMPLATE_ID: u16 = 1;
 
 pub mod encoder {
@@ -56,6 +57,20 @@ pub mod encoder {
             self.limit - self.offset
         }
 
+        /// exact frame length including the message header, for one `(num_discounts, item_note_len)`
+        /// per item and a customer note of `customer_note_len` bytes
+        #[inline]
+        pub fn compute_length(items: impl IntoIterator<Item = (usize, usize)>, customer_note_len: usize) -> usize {
+            message_header_codec::ENCODED_LENGTH
+                + SBE_BLOCK_LENGTH as usize
+                + group_size_encoding_codec::ENCODED_LENGTH
+                + items
+                    .into_iter()
+                    .map(|(num_discounts, item_note_len)| ItemsEncoder::<Self>::compute_length(num_discounts, item_note_len))
+                    .sum::<usize>()
+                + 2 + customer_note_len
+        }
+
         pub fn header(self, offset: usize) -> MessageHeaderEncoder<Self> {
             let mut header = MessageHeaderEncoder::default().wrap(self, offset);
             header.block_length(SBE_BLOCK_LENGTH);
@@ -100,7 +115,7 @@ pub mod encoder {
         /// - max value: -2
         /// - null value: 0xffffffffffffffff_u64
         /// - characterEncoding: null
-        /// - semanticType: null
+        /// - semanticType: UTCTimestamp
         /// - encodedOffset: 16
         /// - encodedLength: 8
         /// - version: 0
@@ -110,6 +125,20 @@ pub mod encoder {
             self.get_buf_mut().put_u64_at(offset, value);
         }
 
+        /// primitive field 'timestamp' from a `Timestamp`
+        #[inline]
+        pub fn timestamp_utc(&mut self, value: Timestamp) {
+            self.timestamp(value.as_nanos());
+        }
+
+        /// primitive field 'timestamp' set to the time of `clock`, which is returned
+        #[inline]
+        pub fn timestamp_now(&mut self, clock: &impl Clock) -> Timestamp {
+            let now = clock.now();
+            self.timestamp_utc(now);
+            now
+        }
+
         /// REQUIRED enum
         #[inline]
         pub fn order_type(&mut self, value: order_type::OrderType) {
@@ -117,31 +146,68 @@ pub mod encoder {
             self.get_buf_mut().put_u8_at(offset, value as u8)
         }
 
+        #[inline]
+        pub fn order_flags(&mut self, value: order_flags::OrderFlags) {
+            let offset = self.offset + 25;
+            self.get_buf_mut().put_u8_at(offset, value.0)
+        }
+
+        // skipping CONSTANT venue
+
+        // skipping CONSTANT enum 'direction'
+
         /// GROUP ENCODER (id=20, description='List of items in the order.')
         #[inline]
-        pub fn items_encoder(self, count: u8, items_encoder: ItemsEncoder<Self>) -> ItemsEncoder<Self> {
+        pub fn items_encoder(self, count: u16, items_encoder: ItemsEncoder<Self>) -> ItemsEncoder<Self> {
             items_encoder.wrap(self, count)
         }
 
-        /// VAR_DATA ENCODER - character encoding: 'UTF-8'
+        /// `items_encoder` for a count of any size, `SbeErr::CountOutOfRange` when it does not
+        /// fit the group dimension
+        #[inline]
+        pub fn items_encoder_checked(self, count: usize, items_encoder: ItemsEncoder<Self>) -> SbeResult<ItemsEncoder<Self>> {
+            let count = group_size_encoding_codec::num_in_group(count)?;
+            Ok(self.items_encoder(count, items_encoder))
+        }
+
+        /// VAR_DATA ENCODER - character encoding: 'UTF-8'. Panics if `value` is longer than
+        /// `var_string_encoding_codec::LENGTH_MAX`
         #[inline]
         pub fn customer_note(&mut self, value: &str) {
+            self.customer_note_checked(value).expect("customer_note too long");
+        }
+
+        /// same as customer_note but fails with `SbeErr::DataTooLong` instead of writing a truncated
+        /// length
+        #[inline]
+        pub fn customer_note_checked(&mut self, value: &str) -> SbeResult<()> {
+            let data_length = var_string_encoding_codec::length(value.len())?;
             let limit = self.get_limit();
-            let data_length = value.len();
-            self.set_limit(limit + 2 + data_length);
-            self.get_buf_mut().put_u16_at(limit, data_length as u16);
+            self.set_limit(limit + 2 + value.len());
+            self.get_buf_mut().put_u16_at(limit, data_length);
             self.get_buf_mut().put_slice_at(limit + 2, value.as_bytes());
+            Ok(())
         }
 
     }
 
+    /// nested parts of the current items entry written so far, they have to follow in this order
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+    enum ItemsEntry {
+        #[default]
+        Block,
+        Discounts,
+        ItemNote,
+    }
+
     #[derive(Debug, Default)]
     pub struct ItemsEncoder<P> {
         parent: Option<P>,
-        count: u8,
+        count: u16,
         index: usize,
         offset: usize,
         initial_limit: usize,
+        entry: ItemsEntry,
     }
 
     impl<'a, P> Writer<'a> for ItemsEncoder<P> where P: Writer<'a> + Default {
@@ -172,33 +238,48 @@ pub mod encoder {
         pub fn wrap(
             mut self,
             mut parent: P,
-            count: u8,
+            count: u16,
         ) -> Self {
             let initial_limit = parent.get_limit();
-            parent.set_limit(initial_limit + 3);
+            parent.set_limit(initial_limit + 4);
             parent.get_buf_mut().put_u16_at(initial_limit, Self::block_length());
-            parent.get_buf_mut().put_u8_at(initial_limit + 2, count);
+            parent.get_buf_mut().put_u16_at(initial_limit + 2, count);
             self.parent = Some(parent);
             self.count = count;
             self.index = usize::MAX;
             self.offset = usize::MAX;
             self.initial_limit = initial_limit;
+            self.entry = ItemsEntry::ItemNote;
             self
         }
 
         #[inline]
         pub fn block_length() -> u16 {
-            14
+            33
+        }
+
+        /// encoded length of one entry with `num_discounts` discounts and an item note of
+        /// `item_note_len` bytes
+        #[inline]
+        pub fn compute_length(num_discounts: usize, item_note_len: usize) -> usize {
+            Self::block_length() as usize
+                + group_size_encoding_codec::ENCODED_LENGTH
+                + num_discounts * DiscountsEncoder::<Self>::block_length() as usize
+                + 2 + item_note_len
         }
 
+        /// closes the current entry after `advance` ran out of entries
         #[inline]
         pub fn parent(&mut self) -> SbeResult<P> {
+            self.finish_entry();
             self.parent.take().ok_or(SbeErr::ParentNotSet)
         }
 
-        /// will return Some(current index) when successful otherwise None
+        /// will return Some(current index) when successful otherwise None. The nested parts the
+        /// previous entry left out are written empty first
         #[inline]
         pub fn advance(&mut self) -> SbeResult<Option<usize>> {
+            self.finish_entry();
             let index = self.index.wrapping_add(1);
             if index >= self.count as usize {
                 return Ok(None);
@@ -207,12 +288,33 @@ pub mod encoder {
                 self.offset = parent.get_limit();
                 parent.set_limit(self.offset + Self::block_length() as usize);
                 self.index = index;
+                self.entry = ItemsEntry::Block;
                 Ok(Some(index))
             } else {
                 Err(SbeErr::ParentNotSet)
             }
         }
 
+        /// writes an empty discounts group and item note unless the entry has them
+        #[inline]
+        fn finish_entry(&mut self) {
+            if self.entry < ItemsEntry::Discounts {
+                self.empty_discounts();
+            }
+            if self.entry < ItemsEntry::ItemNote {
+                self.item_note("");
+            }
+        }
+
+        #[inline]
+        fn empty_discounts(&mut self) {
+            let limit = self.get_limit();
+            self.set_limit(limit + group_size_encoding_codec::ENCODED_LENGTH);
+            self.get_buf_mut().put_u16_at(limit, DiscountsEncoder::<Self>::block_length());
+            self.get_buf_mut().put_u16_at(limit + 2, 0);
+            self.entry = ItemsEntry::Discounts;
+        }
+
         /// primitive field 'productId'
         /// - min value: 0
         /// - max value: 4294967294
@@ -250,6 +352,279 @@ pub mod encoder {
             decimal_codec::DecimalEncoder::default().wrap(self, offset)
         }
 
+        #[inline]
+        pub fn currency_at(&mut self, index: usize, value: u8) {
+            let offset = self.offset + 14;
+            let buf = self.get_buf_mut();
+            buf.put_u8_at(offset + index, value);
+        }
+
+        /// primitive array field 'currency'
+        /// - min value: 32
+        /// - max value: 126
+        /// - null value: 0_u8
+        /// - characterEncoding: US-ASCII
+        /// - semanticType: null
+        /// - encodedOffset: 14
+        /// - encodedLength: 3
+        /// - version: 5
+        #[inline]
+        pub fn currency(&mut self, value: &[u8]) {
+            debug_assert_eq!(3, value.len());
+            let offset = self.offset + 14;
+            let buf = self.get_buf_mut();
+            buf.put_slice_at(offset, value);
+        }
+
+        /// primitive array field 'currency' from an Iterator
+        /// - min value: 32
+        /// - max value: 126
+        /// - null value: 0_u8
+        /// - characterEncoding: US-ASCII
+        /// - semanticType: null
+        /// - encodedOffset: 14
+        /// - encodedLength: 3
+        /// - version: 5
+        #[inline]
+        pub fn currency_from_iter(&mut self, iter: impl Iterator<Item = u8>) {
+            let offset = self.offset + 14;
+            let buf = self.get_buf_mut();
+            for (i, v) in iter.enumerate() {
+                buf.put_u8_at(offset + i, v);
+            }
+        }
+
+        /// primitive array field 'currency' with zero padding
+        /// - min value: 32
+        /// - max value: 126
+        /// - null value: 0_u8
+        /// - characterEncoding: US-ASCII
+        /// - semanticType: null
+        /// - encodedOffset: 14
+        /// - encodedLength: 3
+        /// - version: 5
+        #[inline]
+        pub fn currency_zero_padded(&mut self, value: &[u8]) {
+            let iter = value.iter().copied().chain(core::iter::repeat(0_u8)).take(3);
+            self.currency_from_iter(iter);
+        }
+
+        /// primitive array field 'currency' from text, padded with NUL. Fails with
+        /// `SbeErr::CharArrayTooLong` past 3 bytes or `SbeErr::NotAscii`
+        #[inline]
+        pub fn currency_str(&mut self, value: &str) -> SbeResult<()> {
+            let value = char_array::<3>(value)?;
+            self.currency(&value);
+            Ok(())
+        }
+
+        #[inline]
+        pub fn sku_at(&mut self, index: usize, value: u8) {
+            let offset = self.offset + 17;
+            let buf = self.get_buf_mut();
+            buf.put_u8_at(offset + index, value);
+        }
+
+        /// primitive array field 'sku'
+        /// - min value: 32
+        /// - max value: 126
+        /// - null value: 0_u8
+        /// - characterEncoding: US-ASCII
+        /// - semanticType: null
+        /// - encodedOffset: 17
+        /// - encodedLength: 16
+        /// - version: 5
+        #[inline]
+        pub fn sku(&mut self, value: &[u8]) {
+            debug_assert_eq!(16, value.len());
+            let offset = self.offset + 17;
+            let buf = self.get_buf_mut();
+            buf.put_slice_at(offset, value);
+        }
+
+        /// primitive array field 'sku' from an Iterator
+        /// - min value: 32
+        /// - max value: 126
+        /// - null value: 0_u8
+        /// - characterEncoding: US-ASCII
+        /// - semanticType: null
+        /// - encodedOffset: 17
+        /// - encodedLength: 16
+        /// - version: 5
+        #[inline]
+        pub fn sku_from_iter(&mut self, iter: impl Iterator<Item = u8>) {
+            let offset = self.offset + 17;
+            let buf = self.get_buf_mut();
+            for (i, v) in iter.enumerate() {
+                buf.put_u8_at(offset + i, v);
+            }
+        }
+
+        /// primitive array field 'sku' with zero padding
+        /// - min value: 32
+        /// - max value: 126
+        /// - null value: 0_u8
+        /// - characterEncoding: US-ASCII
+        /// - semanticType: null
+        /// - encodedOffset: 17
+        /// - encodedLength: 16
+        /// - version: 5
+        #[inline]
+        pub fn sku_zero_padded(&mut self, value: &[u8]) {
+            let iter = value.iter().copied().chain(core::iter::repeat(0_u8)).take(16);
+            self.sku_from_iter(iter);
+        }
+
+        /// primitive array field 'sku' from text, padded with NUL. Fails with
+        /// `SbeErr::CharArrayTooLong` past 16 bytes or `SbeErr::NotAscii`
+        #[inline]
+        pub fn sku_str(&mut self, value: &str) -> SbeResult<()> {
+            let value = char_array::<16>(value)?;
+            self.sku(&value);
+            Ok(())
+        }
+
+        /// GROUP ENCODER (id=24, description='Discounts applied to the item.')
+        #[inline]
+        pub fn discounts_encoder(mut self, count: u16, discounts_encoder: DiscountsEncoder<Self>) -> DiscountsEncoder<Self> {
+            debug_assert!(self.entry == ItemsEntry::Block, "discounts after item note");
+            self.entry = ItemsEntry::Discounts;
+            discounts_encoder.wrap(self, count)
+        }
+
+        /// `discounts_encoder` for a count of any size, `SbeErr::CountOutOfRange` when it does
+        /// not fit the group dimension
+        #[inline]
+        pub fn discounts_encoder_checked(self, count: usize, discounts_encoder: DiscountsEncoder<Self>) -> SbeResult<DiscountsEncoder<Self>> {
+            let count = group_size_encoding_codec::num_in_group(count)?;
+            Ok(self.discounts_encoder(count, discounts_encoder))
+        }
+
+        /// VAR_DATA ENCODER - character encoding: 'UTF-8'. Panics if `value` is longer than
+        /// `var_string_encoding_codec::LENGTH_MAX`
+        #[inline]
+        pub fn item_note(&mut self, value: &str) {
+            self.item_note_checked(value).expect("item_note too long");
+        }
+
+        /// same as item_note but fails with `SbeErr::DataTooLong` instead of writing a truncated
+        /// length
+        #[inline]
+        pub fn item_note_checked(&mut self, value: &str) -> SbeResult<()> {
+            let data_length = var_string_encoding_codec::length(value.len())?;
+            if self.entry < ItemsEntry::Discounts {
+                self.empty_discounts();
+            }
+            self.entry = ItemsEntry::ItemNote;
+            let limit = self.get_limit();
+            self.set_limit(limit + 2 + value.len());
+            self.get_buf_mut().put_u16_at(limit, data_length);
+            self.get_buf_mut().put_slice_at(limit + 2, value.as_bytes());
+            Ok(())
+        }
+
+    }
+
+    #[derive(Debug, Default)]
+    pub struct DiscountsEncoder<P> {
+        parent: Option<P>,
+        count: u16,
+        index: usize,
+        offset: usize,
+        initial_limit: usize,
+    }
+
+    impl<'a, P> Writer<'a> for DiscountsEncoder<P> where P: Writer<'a> + Default {
+        #[inline]
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a> {
+            if let Some(parent) = self.parent.as_mut() {
+                parent.get_buf_mut()
+            } else {
+                panic!("parent was None")
+            }
+        }
+    }
+
+    impl<'a, P> Encoder<'a> for DiscountsEncoder<P> where P: Encoder<'a> + Default {
+        #[inline]
+        fn get_limit(&self) -> usize {
+            self.parent.as_ref().expect("parent missing").get_limit()
+        }
+
+        #[inline]
+        fn set_limit(&mut self, limit: usize) {
+            self.parent.as_mut().expect("parent missing").set_limit(limit);
+        }
+    }
+
+    impl<'a, P> DiscountsEncoder<P> where P: Encoder<'a> + Default {
+        #[inline]
+        pub fn wrap(
+            mut self,
+            mut parent: P,
+            count: u16,
+        ) -> Self {
+            let initial_limit = parent.get_limit();
+            parent.set_limit(initial_limit + 4);
+            parent.get_buf_mut().put_u16_at(initial_limit, Self::block_length());
+            parent.get_buf_mut().put_u16_at(initial_limit + 2, count);
+            self.parent = Some(parent);
+            self.count = count;
+            self.index = usize::MAX;
+            self.offset = usize::MAX;
+            self.initial_limit = initial_limit;
+            self
+        }
+
+        #[inline]
+        pub fn block_length() -> u16 {
+            10
+        }
+
+        #[inline]
+        pub fn parent(&mut self) -> SbeResult<P> {
+            self.parent.take().ok_or(SbeErr::ParentNotSet)
+        }
+
+        /// will return Some(current index) when successful otherwise None
+        #[inline]
+        pub fn advance(&mut self) -> SbeResult<Option<usize>> {
+            let index = self.index.wrapping_add(1);
+            if index >= self.count as usize {
+                return Ok(None);
+            }
+            if let Some(parent) = self.parent.as_mut() {
+                self.offset = parent.get_limit();
+                parent.set_limit(self.offset + Self::block_length() as usize);
+                self.index = index;
+                Ok(Some(index))
+            } else {
+                Err(SbeErr::ParentNotSet)
+            }
+        }
+
+        /// primitive field 'code'
+        /// - min value: 0
+        /// - max value: 65534
+        /// - null value: 0xffff_u16
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 0
+        /// - encodedLength: 2
+        /// - version: 0
+        #[inline]
+        pub fn code(&mut self, value: u16) {
+            let offset = self.offset;
+            self.get_buf_mut().put_u16_at(offset, value);
+        }
+
+        /// COMPOSITE ENCODER
+        #[inline]
+        pub fn amount_encoder(self) -> decimal_codec::DecimalEncoder<Self> {
+            let offset = self.offset + 2;
+            decimal_codec::DecimalEncoder::default().wrap(self, offset)
+        }
+
     }
 
 } // end encoder
@@ -312,6 +687,32 @@ pub mod decoder {
             self
         }
 
+        /// same as wrap but fails instead of panicking later if the block does not fit the buffer
+        pub fn wrap_checked(
+            self,
+            buf: ReadBuf<'a>,
+            offset: usize,
+            acting_block_length: u16,
+            acting_version: u16,
+        ) -> SbeResult<Self> {
+            if acting_block_length < Self::min_block_length(acting_version) {
+                return Err(SbeErr::BlockLengthTooShort);
+            }
+            buf.check_bounds(offset, acting_block_length as usize)?;
+            Ok(self.wrap(buf, offset, acting_block_length, acting_version))
+        }
+
+        /// block length holding every field a sender at `acting_version` writes, version 1 has no
+        /// order_flags
+        #[inline]
+        pub fn min_block_length(acting_version: u16) -> u16 {
+            if acting_version < 2 {
+                25
+            } else {
+                SBE_BLOCK_LENGTH
+            }
+        }
+
         #[inline]
         pub fn encoded_length(&self) -> usize {
             self.limit - self.offset
@@ -330,6 +731,23 @@ pub mod decoder {
             )
         }
 
+        /// same as header but validates the header and block against the buffer
+        pub fn header_checked(self, mut header: MessageHeaderDecoder<ReadBuf<'a>>, offset: usize) -> SbeResult<Self> {
+            let buf = header.parent()?;
+            buf.check_bounds(offset, message_header_codec::ENCODED_LENGTH)?;
+            let header = MessageHeaderDecoder::default().wrap(buf, offset);
+            if header.template_id() != SBE_TEMPLATE_ID {
+                return Err(SbeErr::WrongTemplateId);
+            }
+
+            self.wrap_checked(
+                buf,
+                offset + message_header_codec::ENCODED_LENGTH,
+                header.block_length(),
+                header.version(),
+            )
+        }
+
         /// primitive field - 'REQUIRED'
         #[inline]
         pub fn order_id(&self) -> u64 {
@@ -348,18 +766,59 @@ pub mod decoder {
             self.get_buf().get_u64_at(self.offset + 16)
         }
 
+        /// primitive field 'timestamp' as a `Timestamp`
+        #[inline]
+        pub fn timestamp_utc(&self) -> Timestamp {
+            Timestamp::from_nanos(self.timestamp())
+        }
+
         /// REQUIRED enum
         #[inline]
         pub fn order_type(&self) -> order_type::OrderType {
             self.get_buf().get_u8_at(self.offset + 24).into()
         }
 
+        /// BIT SET DECODER
+        #[inline]
+        pub fn order_flags(&self) -> order_flags::OrderFlags {
+            if self.acting_version() < 2 {
+                return order_flags::OrderFlags::default();
+            }
+
+            order_flags::OrderFlags::new(self.get_buf().get_u8_at(self.offset + 25))
+        }
+
+        /// CONSTANT 
+        /// characterEncoding: 'US-ASCII'
+        #[inline]
+        pub fn venue(&self) -> &'static [u8] {
+            b"XSAL"
+        }
+
+        /// CONSTANT 'venue' as text
+        #[inline]
+        pub fn venue_str(&self) -> &'static str {
+            "XSAL"
+        }
+
+        /// CONSTANT enum
+        #[inline]
+        pub fn direction(&self) -> direction::Direction {
+            direction::Direction::Inbound
+        }
+
         /// GROUP DECODER (id=20, description='List of items in the order.')
         #[inline]
         pub fn items_decoder(self) -> ItemsDecoder<Self> {
             ItemsDecoder::default().wrap(self)
         }
 
+        /// same as items_decoder but fails if the group does not fit the buffer
+        #[inline]
+        pub fn items_decoder_checked(self) -> SbeResult<ItemsDecoder<Self>> {
+            ItemsDecoder::default().wrap_checked(self)
+        }
+
         /// VAR_DATA DECODER - character encoding: 'UTF-8'
         #[inline]
         pub fn customer_note_decoder(&mut self) -> (usize, usize) {
@@ -369,6 +828,37 @@ pub mod decoder {
             (offset + 2, data_length)
         }
 
+        /// same as customer_note_decoder but fails if length or data do not fit the buffer
+        #[inline]
+        pub fn customer_note_decoder_checked(&mut self) -> SbeResult<(usize, usize)> {
+            let offset = self.get_limit();
+            self.get_buf().check_bounds(offset, 2)?;
+            let data_length = self.get_buf().get_u16_at(offset) as usize;
+            self.get_buf().check_bounds(offset + 2, data_length)?;
+            self.set_limit(offset + 2 + data_length);
+            Ok((offset + 2, data_length))
+        }
+
+        /// moves the limit past customer_note without reading the data, returns its length
+        #[inline]
+        pub fn customer_note_skip(&mut self) -> usize {
+            let offset = self.get_limit();
+            let data_length = self.get_buf().get_u16_at(offset) as usize;
+            self.set_limit(offset + 2 + data_length);
+            data_length
+        }
+
+        /// same as customer_note_skip but fails if length or data do not fit the buffer
+        #[inline]
+        pub fn customer_note_skip_checked(&mut self) -> SbeResult<usize> {
+            let offset = self.get_limit();
+            self.get_buf().check_bounds(offset, 2)?;
+            let data_length = self.get_buf().get_u16_at(offset) as usize;
+            self.get_buf().check_bounds(offset + 2, data_length)?;
+            self.set_limit(offset + 2 + data_length);
+            Ok(data_length)
+        }
+
         #[inline]
         pub fn customer_note_slice(&'a self, coordinates: (usize, usize)) -> &'a [u8] {
             debug_assert!(self.get_limit() >= coordinates.0 + coordinates.1);
@@ -377,30 +867,119 @@ pub mod decoder {
 
     }
 
-    #[derive(Debug, Default)]
-    pub struct ItemsDecoder<P> {
-        parent: Option<P>,
-        block_length: u16,
-        count: u8,
-        index: usize,
-        offset: usize,
+    /// Fixed block of the message behind a single pre-checked array. Block length and bounds are
+    /// validated once at wrap, field reads after that have no bounds checks or panic branches.
+    /// For a version 1 sender the last byte of the array is the start of the items group, it is
+    /// never read as order_flags.
+    #[derive(Clone, Copy, Debug)]
+    pub struct OrderMessageBlockDecoder<'a> {
+        block: &'a [u8; SBE_BLOCK_LENGTH as usize],
+        decoder: OrderMessageDecoder<'a>,
     }
 
-    impl<'a, P> ActingVersion for ItemsDecoder<P> where P: Reader<'a> + ActingVersion + Default {
-        #[inline]
-        fn acting_version(&self) -> u16 {
-            self.parent.as_ref().unwrap().acting_version()
+    impl<'a> OrderMessageBlockDecoder<'a> {
+        pub fn wrap(
+            buf: ReadBuf<'a>,
+            offset: usize,
+            acting_block_length: u16,
+            acting_version: u16,
+        ) -> SbeResult<Self> {
+            let decoder = OrderMessageDecoder::default().wrap_checked(buf, offset, acting_block_length, acting_version)?;
+            Ok(Self { block: buf.get_array_at(offset)?, decoder })
         }
-    }
 
-    impl<'a, P> Reader<'a> for ItemsDecoder<P> where P: Reader<'a> + Default {
-        #[inline]
-        fn get_buf(&self) -> &ReadBuf<'a> {
-            self.parent.as_ref().expect("parent missing").get_buf()
+        pub fn header(header: MessageHeaderDecoder<ReadBuf<'a>>, offset: usize) -> SbeResult<Self> {
+            let decoder = OrderMessageDecoder::default().header_checked(header, offset)?;
+            let block = decoder.get_buf().get_array_at(decoder.offset)?;
+            Ok(Self { block, decoder })
         }
-    }
 
-    impl<'a, P> Decoder<'a> for ItemsDecoder<P> where P: Decoder<'a> + ActingVersion + Default {
+        /// flyweight positioned after the block, to go on with the items group and var data
+        #[inline]
+        pub fn decoder(self) -> OrderMessageDecoder<'a> {
+            self.decoder
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn order_id(&self) -> u64 {
+            u64::from_le_bytes(get_block_bytes::<0, 8, _>(self.block))
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn client_id(&self) -> u64 {
+            u64::from_le_bytes(get_block_bytes::<8, 8, _>(self.block))
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn timestamp(&self) -> u64 {
+            u64::from_le_bytes(get_block_bytes::<16, 8, _>(self.block))
+        }
+
+        /// primitive field 'timestamp' as a `Timestamp`
+        #[inline]
+        pub fn timestamp_utc(&self) -> Timestamp {
+            Timestamp::from_nanos(self.timestamp())
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn order_type(&self) -> order_type::OrderType {
+            self.block[24].into()
+        }
+
+        /// BIT SET DECODER
+        #[inline]
+        pub fn order_flags(&self) -> order_flags::OrderFlags {
+            if self.decoder.acting_version < 2 {
+                return order_flags::OrderFlags::default();
+            }
+
+            order_flags::OrderFlags::new(self.block[25])
+        }
+    }
+
+    /// Offsets of the item note and of the end of an items entry whose discounts group starts at
+    /// `offset`, both checked against the buffer.
+    #[inline]
+    pub(crate) fn items_entry_tail(buf: &ReadBuf<'_>, offset: usize, acting_version: u16) -> SbeResult<(usize, usize)> {
+        let dimension_length = group_size_encoding_codec::acting_encoded_length(acting_version);
+        buf.check_bounds(offset, dimension_length)?;
+        let block_length = buf.get_u16_at(offset) as usize;
+        let count = group_size_encoding_codec::acting_num_in_group(buf, offset, acting_version) as usize;
+        let item_note = offset + dimension_length + count * block_length;
+        buf.check_bounds(item_note, 2)?;
+        let data_length = buf.get_u16_at(item_note) as usize;
+        buf.check_bounds(item_note + 2, data_length)?;
+        Ok((item_note, item_note + 2 + data_length))
+    }
+
+    #[derive(Debug, Default)]
+    pub struct ItemsDecoder<P> {
+        parent: Option<P>,
+        block_length: u16,
+        count: u16,
+        index: usize,
+        offset: usize,
+    }
+
+    impl<'a, P> ActingVersion for ItemsDecoder<P> where P: Reader<'a> + ActingVersion + Default {
+        #[inline]
+        fn acting_version(&self) -> u16 {
+            self.parent.as_ref().unwrap().acting_version()
+        }
+    }
+
+    impl<'a, P> Reader<'a> for ItemsDecoder<P> where P: Reader<'a> + Default {
+        #[inline]
+        fn get_buf(&self) -> &ReadBuf<'a> {
+            self.parent.as_ref().expect("parent missing").get_buf()
+        }
+    }
+
+    impl<'a, P> Decoder<'a> for ItemsDecoder<P> where P: Decoder<'a> + ActingVersion + Default {
         #[inline]
         fn get_limit(&self) -> usize {
             self.parent.as_ref().expect("parent missing").get_limit()
@@ -418,9 +997,10 @@ pub mod decoder {
             mut parent: P,
         ) -> Self {
             let initial_offset = parent.get_limit();
+            let acting_version = parent.acting_version();
             let block_length = parent.get_buf().get_u16_at(initial_offset);
-            let count = parent.get_buf().get_u8_at(initial_offset + 2);
-            parent.set_limit(initial_offset + 3);
+            let count = group_size_encoding_codec::acting_num_in_group(parent.get_buf(), initial_offset, acting_version);
+            parent.set_limit(initial_offset + group_size_encoding_codec::acting_encoded_length(acting_version));
             self.parent = Some(parent);
             self.block_length = block_length;
             self.count = count;
@@ -429,7 +1009,36 @@ pub mod decoder {
             self
         }
 
-        /// group token - Token{signal=BEGIN_GROUP, name='items', referencedName='null', description='List of items in the order.', packageName='null', id=20, version=0, deprecated=0, encodedLength=14, offset=25, componentTokenCount=18, encoding=Encoding{presence=REQUIRED, primitiveType=null, byteOrder=LITTLE_ENDIAN, minValue=null, maxValue=null, nullValue=null, constValue=null, characterEncoding='null', epoch='null', timeUnit=null, semanticType='null'}}
+        /// same as wrap but checks that the dimensions and all entries fit the buffer
+        pub fn wrap_checked(
+            self,
+            parent: P,
+        ) -> SbeResult<Self> {
+            let initial_offset = parent.get_limit();
+            let acting_version = parent.acting_version();
+            let dimension_length = group_size_encoding_codec::acting_encoded_length(acting_version);
+            parent.get_buf().check_bounds(initial_offset, dimension_length)?;
+            let block_length = parent.get_buf().get_u16_at(initial_offset);
+            let count = group_size_encoding_codec::acting_num_in_group(parent.get_buf(), initial_offset, acting_version);
+            if block_length < Self::min_block_length(acting_version) {
+                return Err(SbeErr::BlockLengthTooShort);
+            }
+            parent.get_buf().check_bounds(initial_offset + dimension_length, count as usize * block_length as usize)?;
+            Ok(self.wrap(parent))
+        }
+
+        /// block length holding every field a sender at `acting_version` writes, 'currency' and
+        /// 'sku' came with version 5
+        #[inline]
+        pub fn min_block_length(acting_version: u16) -> u16 {
+            if acting_version < 5 {
+                14
+            } else {
+                33
+            }
+        }
+
+        /// group token - Token{signal=BEGIN_GROUP, name='items', referencedName='null', description='List of items in the order.', packageName='null', id=20, version=0, deprecated=0, encodedLength=33, offset=26, componentTokenCount=45, encoding=Encoding{presence=REQUIRED, primitiveType=null, byteOrder=LITTLE_ENDIAN, minValue=null, maxValue=null, nullValue=null, constValue=null, characterEncoding='null', epoch='null', timeUnit=null, semanticType='null'}}
         #[inline]
         pub fn parent(&mut self) -> SbeResult<P> {
             self.parent.take().ok_or(SbeErr::ParentNotSet)
@@ -441,18 +1050,25 @@ pub mod decoder {
         }
 
         #[inline]
-        pub fn count(&self) -> u8 {
+        pub fn count(&self) -> u16 {
             self.count
         }
 
-        /// will return Some(current index) when successful otherwise None
+        /// will return Some(current index) when successful otherwise None. Moves past the
+        /// discounts and item note of the previous entry whether or not they were read, and
+        /// fails if the entry does not fit the buffer
         pub fn advance(&mut self) -> SbeResult<Option<usize>> {
+            if self.index != usize::MAX && self.parent.is_some() {
+                let end = self.entry_end()?;
+                self.set_limit(end);
+            }
             let index = self.index.wrapping_add(1);
             if index >= self.count as usize {
-                 return Ok(None);
+                return Ok(None);
             }
             if let Some(parent) = self.parent.as_mut() {
                 self.offset = parent.get_limit();
+                parent.get_buf().check_bounds(self.offset, self.block_length as usize)?;
                 parent.set_limit(self.offset + self.block_length as usize);
                 self.index = index;
                 Ok(Some(index))
@@ -461,6 +1077,55 @@ pub mod decoder {
             }
         }
 
+        /// Moves the parent limit past the current and the remaining entries without reading
+        /// them, `advance` returns None afterwards. Entries of senders before version 3 have a
+        /// fixed length and are skipped with a single jump, later ones are walked by their nested
+        /// lengths only.
+        pub fn skip_all(&mut self) -> SbeResult<()> {
+            let mut end = if self.index == usize::MAX { self.get_limit() } else { self.entry_end()? };
+            let remaining = self.count as usize - self.index.wrapping_add(1);
+            if remaining == 0 {
+                // past the nested parts of the current entry
+                self.set_limit(end);
+                return Ok(());
+            }
+            let block_length = self.block_length as usize;
+
+            let acting_version = self.acting_version();
+            if acting_version >= 3 {
+                let buf = self.get_buf();
+                let mut offset = end;
+                for _ in 0..remaining {
+                    offset = end;
+                    buf.check_bounds(offset, block_length)?;
+                    end = items_entry_tail(buf, offset + block_length, acting_version)?.1;
+                }
+                self.offset = offset;
+            } else {
+                let length = remaining * block_length;
+                self.get_buf().check_bounds(end, length)?;
+                end += length;
+                self.offset = end - block_length;
+            }
+            self.set_limit(end);
+            self.index = self.count as usize - 1;
+            Ok(())
+        }
+
+        /// start of the discounts group of the current entry
+        #[inline]
+        fn nested_offset(&self) -> usize {
+            self.offset + self.block_length as usize
+        }
+
+        #[inline]
+        fn entry_end(&self) -> SbeResult<usize> {
+            if self.acting_version() < 3 {
+                return Ok(self.nested_offset());
+            }
+            Ok(items_entry_tail(self.get_buf(), self.nested_offset(), self.acting_version())?.1)
+        }
+
         /// primitive field - 'REQUIRED'
         #[inline]
         pub fn product_id(&self) -> u32 {
@@ -480,7 +1145,555 @@ pub mod decoder {
             decimal_codec::DecimalDecoder::default().wrap(self, offset)
         }
 
+        #[inline]
+        pub fn currency(&self) -> [u8; 3] {
+            if self.acting_version() < 5 {
+                return [0_u8; 3];
+            }
+
+            let buf = self.get_buf();
+            ReadBuf::get_bytes_at(buf.data, self.offset + 14)
+        }
+
+        #[inline]
+        pub fn sku(&self) -> [u8; 16] {
+            if self.acting_version() < 5 {
+                return [0_u8; 16];
+            }
+
+            let buf = self.get_buf();
+            ReadBuf::get_bytes_at(buf.data, self.offset + 17)
+        }
+
+        /// 'currency' up to its first NUL, empty for senders before version 5. Fails with
+        /// `SbeErr::NotAscii`
+        #[inline]
+        pub fn currency_str(&self) -> SbeResult<&'a str> {
+            if self.acting_version() < 5 {
+                return Ok("");
+            }
+
+            self.get_buf().get_char_array_at(self.offset + 14, 3)
+        }
+
+        /// 'sku' up to its first NUL, empty for senders before version 5. Fails with
+        /// `SbeErr::NotAscii`
+        #[inline]
+        pub fn sku_str(&self) -> SbeResult<&'a str> {
+            if self.acting_version() < 5 {
+                return Ok("");
+            }
+
+            self.get_buf().get_char_array_at(self.offset + 17, 16)
+        }
+
+        /// GROUP DECODER (id=24, description='Discounts applied to the item.')
+        #[inline]
+        pub fn discounts_decoder(mut self) -> Option<DiscountsDecoder<Self>> {
+            if self.acting_version() < 3 {
+                return None;
+            }
+
+            let offset = self.nested_offset();
+            self.set_limit(offset);
+            Some(DiscountsDecoder::default().wrap(self))
+        }
+
+        /// same as discounts_decoder but fails if the group does not fit the buffer, an empty
+        /// group for senders before version 3
+        #[inline]
+        pub fn discounts_decoder_checked(mut self) -> SbeResult<DiscountsDecoder<Self>> {
+            if self.acting_version() < 3 {
+                return Ok(DiscountsDecoder::empty(self));
+            }
+
+            let offset = self.nested_offset();
+            self.set_limit(offset);
+            DiscountsDecoder::default().wrap_checked(self)
+        }
+
+        /// VAR_DATA DECODER - character encoding: 'UTF-8'. Found behind the discounts group even
+        /// if that was not read
+        #[inline]
+        pub fn item_note_decoder(&mut self) -> (usize, usize) {
+            if self.acting_version() < 3 {
+                return (self.parent.as_ref().unwrap().get_limit(), 0);
+            }
+
+            let (nested, acting_version) = (self.nested_offset(), self.acting_version());
+            let count = group_size_encoding_codec::acting_num_in_group(self.get_buf(), nested, acting_version);
+            let discounts_length = count as usize * self.get_buf().get_u16_at(nested) as usize;
+            let offset = nested + group_size_encoding_codec::acting_encoded_length(acting_version) + discounts_length;
+            let data_length = self.get_buf().get_u16_at(offset) as usize;
+            self.parent.as_mut().unwrap().set_limit(offset + 2 + data_length);
+            (offset + 2, data_length)
+        }
+
+        /// same as item_note_decoder but fails if length or data do not fit the buffer
+        #[inline]
+        pub fn item_note_decoder_checked(&mut self) -> SbeResult<(usize, usize)> {
+            if self.acting_version() < 3 {
+                return Ok((self.get_limit(), 0));
+            }
+
+            let acting_version = self.acting_version();
+            let (offset, end) = items_entry_tail(self.get_buf(), self.nested_offset(), acting_version)?;
+            self.set_limit(end);
+            Ok((offset + 2, end - offset - 2))
+        }
+
+        #[inline]
+        pub fn item_note_slice(&'a self, coordinates: (usize, usize)) -> &'a [u8] {
+            if self.acting_version() < 3 {
+                return &[] as &[u8];
+            }
+
+            debug_assert!(self.get_limit() >= coordinates.0 + coordinates.1);
+            self.get_buf().get_slice_at(coordinates.0, coordinates.1)
+        }
+
+    }
+
+    #[derive(Debug, Default)]
+    pub struct DiscountsDecoder<P> {
+        parent: Option<P>,
+        block_length: u16,
+        count: u16,
+        index: usize,
+        offset: usize,
+    }
+
+    impl<'a, P> ActingVersion for DiscountsDecoder<P> where P: Reader<'a> + ActingVersion + Default {
+        #[inline]
+        fn acting_version(&self) -> u16 {
+            self.parent.as_ref().unwrap().acting_version()
+        }
+    }
+
+    impl<'a, P> Reader<'a> for DiscountsDecoder<P> where P: Reader<'a> + ActingVersion + Default {
+        #[inline]
+        fn get_buf(&self) -> &ReadBuf<'a> {
+            self.parent.as_ref().expect("parent missing").get_buf()
+        }
+    }
+
+    impl<'a, P> Decoder<'a> for DiscountsDecoder<P> where P: Decoder<'a> + ActingVersion + Default {
+        #[inline]
+        fn get_limit(&self) -> usize {
+            self.parent.as_ref().expect("parent missing").get_limit()
+        }
+
+        #[inline]
+        fn set_limit(&mut self, limit: usize) {
+            self.parent.as_mut().expect("parent missing").set_limit(limit);
+        }
+    }
+
+    impl<'a, P> DiscountsDecoder<P> where P: Decoder<'a> + ActingVersion + Default {
+        pub fn wrap(
+            mut self,
+            mut parent: P,
+        ) -> Self {
+            let initial_offset = parent.get_limit();
+            let acting_version = parent.acting_version();
+            let block_length = parent.get_buf().get_u16_at(initial_offset);
+            let count = group_size_encoding_codec::acting_num_in_group(parent.get_buf(), initial_offset, acting_version);
+            parent.set_limit(initial_offset + group_size_encoding_codec::acting_encoded_length(acting_version));
+            self.parent = Some(parent);
+            self.block_length = block_length;
+            self.count = count;
+            self.index = usize::MAX;
+            self.offset = 0;
+            self
+        }
+
+        /// same as wrap but checks that the dimensions and all entries fit the buffer
+        pub fn wrap_checked(
+            self,
+            parent: P,
+        ) -> SbeResult<Self> {
+            let initial_offset = parent.get_limit();
+            let acting_version = parent.acting_version();
+            let dimension_length = group_size_encoding_codec::acting_encoded_length(acting_version);
+            parent.get_buf().check_bounds(initial_offset, dimension_length)?;
+            let block_length = parent.get_buf().get_u16_at(initial_offset);
+            let count = group_size_encoding_codec::acting_num_in_group(parent.get_buf(), initial_offset, acting_version);
+            if block_length < 10 {
+                return Err(SbeErr::BlockLengthTooShort);
+            }
+            parent.get_buf().check_bounds(initial_offset + dimension_length, count as usize * block_length as usize)?;
+            Ok(self.wrap(parent))
+        }
+
+        /// no entries, for a parent whose sender predates the group
+        fn empty(parent: P) -> Self {
+            Self {
+                parent: Some(parent),
+                index: usize::MAX,
+                ..Self::default()
+            }
+        }
+
+        /// group token - Token{signal=BEGIN_GROUP, name='discounts', referencedName='null', description='Discounts applied to the item.', packageName='null', id=24, version=3, deprecated=0, encodedLength=10, offset=33, componentTokenCount=15, encoding=Encoding{presence=REQUIRED, primitiveType=null, byteOrder=LITTLE_ENDIAN, minValue=null, maxValue=null, nullValue=null, constValue=null, characterEncoding='null', epoch='null', timeUnit=null, semanticType='null'}}
+        #[inline]
+        pub fn parent(&mut self) -> SbeResult<P> {
+            self.parent.take().ok_or(SbeErr::ParentNotSet)
+        }
+
+        #[inline]
+        pub fn acting_version(&mut self) -> u16 {
+            self.parent.as_ref().unwrap().acting_version()
+        }
+
+        #[inline]
+        pub fn count(&self) -> u16 {
+            self.count
+        }
+
+        /// will return Some(current index) when successful otherwise None
+        pub fn advance(&mut self) -> SbeResult<Option<usize>> {
+            let index = self.index.wrapping_add(1);
+            if index >= self.count as usize {
+                return Ok(None);
+            }
+            if let Some(parent) = self.parent.as_mut() {
+                self.offset = parent.get_limit();
+                parent.set_limit(self.offset + self.block_length as usize);
+                self.index = index;
+                Ok(Some(index))
+            } else {
+                Err(SbeErr::ParentNotSet)
+            }
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn code(&self) -> u16 {
+            self.get_buf().get_u16_at(self.offset)
+        }
+
+        /// COMPOSITE DECODER
+        #[inline]
+        pub fn amount_decoder(self) -> decimal_codec::DecimalDecoder<Self> {
+            let offset = self.offset + 2;
+            decimal_codec::DecimalDecoder::default().wrap(self, offset)
+        }
+
     }
 
 } // end decoder
 
+
+#[cfg(feature = "bytes")]
+pub mod shared {
+    use super::*;
+    use bytes::Bytes;
+    use decoder::{OrderMessageDecoder, items_entry_tail};
+    use message_header_codec::MessageHeaderDecoder;
+
+    /// Decoder owning a reference counted frame, so it is `Send + 'static` and cheap to clone.
+    /// Block, group and var data are checked against the frame at wrap, accessors do not fail.
+    #[derive(Clone, Debug, Default)]
+    pub struct OrderMessageBytesDecoder {
+        buf: Bytes,
+        offset: usize,
+        items_offset: usize,
+        customer_note: (usize, usize),
+        pub acting_block_length: u16,
+        pub acting_version: u16,
+    }
+
+    impl OrderMessageBytesDecoder {
+        pub fn wrap(
+            buf: Bytes,
+            offset: usize,
+            acting_block_length: u16,
+            acting_version: u16,
+        ) -> SbeResult<Self> {
+            let decoder = OrderMessageDecoder::default().wrap_checked(ReadBuf::new(&buf), offset, acting_block_length, acting_version)?;
+            let (items_offset, customer_note) = Self::layout(decoder)?;
+            Ok(Self { buf, offset, items_offset, customer_note, acting_block_length, acting_version })
+        }
+
+        pub fn header(buf: Bytes, offset: usize) -> SbeResult<Self> {
+            let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(&buf), offset);
+            let decoder = OrderMessageDecoder::default().header_checked(header, offset)?;
+            let (acting_block_length, acting_version) = (decoder.acting_block_length, decoder.acting_version);
+            let (items_offset, customer_note) = Self::layout(decoder)?;
+            Ok(Self {
+                buf,
+                offset: offset + message_header_codec::ENCODED_LENGTH,
+                items_offset,
+                customer_note,
+                acting_block_length,
+                acting_version,
+            })
+        }
+
+        /// positions of the items group and the customer note
+        fn layout(decoder: OrderMessageDecoder<'_>) -> SbeResult<(usize, (usize, usize))> {
+            let items_offset = decoder.get_limit();
+            let mut items = decoder.items_decoder_checked()?;
+            while items.advance()?.is_some() {
+                items.currency_str()?;
+                items.sku_str()?;
+                items = items.discounts_decoder_checked()?.parent()?;
+            }
+            let mut decoder = items.parent()?;
+            Ok((items_offset, decoder.customer_note_decoder_checked()?))
+        }
+
+        /// the whole frame this decoder was wrapped over
+        #[inline]
+        pub fn buf(&self) -> &Bytes {
+            &self.buf
+        }
+
+        /// borrowing flyweight over the same message
+        #[inline]
+        pub fn decoder(&self) -> OrderMessageDecoder<'_> {
+            OrderMessageDecoder::default().wrap(ReadBuf::new(&self.buf), self.offset, self.acting_block_length, self.acting_version)
+        }
+
+        #[inline]
+        pub fn encoded_length(&self) -> usize {
+            self.customer_note.0 + self.customer_note.1 - self.offset
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn order_id(&self) -> u64 {
+            self.decoder().order_id()
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn client_id(&self) -> u64 {
+            self.decoder().client_id()
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn timestamp(&self) -> u64 {
+            self.decoder().timestamp()
+        }
+
+        /// primitive field 'timestamp' as a `Timestamp`
+        #[inline]
+        pub fn timestamp_utc(&self) -> Timestamp {
+            Timestamp::from_nanos(self.timestamp())
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn order_type(&self) -> order_type::OrderType {
+            self.decoder().order_type()
+        }
+
+        #[inline]
+        pub fn order_flags(&self) -> order_flags::OrderFlags {
+            self.decoder().order_flags()
+        }
+
+        /// CONSTANT, no bytes on the wire
+        #[inline]
+        pub fn venue(&self) -> &'static str {
+            self.decoder().venue_str()
+        }
+
+        /// CONSTANT enum, no bytes on the wire
+        #[inline]
+        pub fn direction(&self) -> direction::Direction {
+            self.decoder().direction()
+        }
+
+        /// GROUP DECODER (id=20, description='List of items in the order.')
+        #[inline]
+        pub fn items_decoder(&self) -> ItemsBytesDecoder {
+            ItemsBytesDecoder::wrap(self.buf.clone(), self.items_offset, self.acting_version)
+        }
+
+        /// VAR_DATA DECODER - character encoding: 'UTF-8'
+        #[inline]
+        pub fn customer_note(&self) -> Bytes {
+            let (offset, len) = self.customer_note;
+            self.buf.slice(offset..offset + len)
+        }
+    }
+
+    #[derive(Clone, Debug, Default)]
+    pub struct ItemsBytesDecoder {
+        buf: Bytes,
+        block_length: u16,
+        count: u16,
+        index: usize,
+        offset: usize,
+        acting_version: u16,
+    }
+
+    impl ItemsBytesDecoder {
+        /// `initial_offset` is the group dimension, already checked by the message decoder
+        fn wrap(buf: Bytes, initial_offset: usize, acting_version: u16) -> Self {
+            let dimension = ReadBuf::new(&buf);
+            let block_length = dimension.get_u16_at(initial_offset);
+            let count = group_size_encoding_codec::acting_num_in_group(&dimension, initial_offset, acting_version);
+            Self {
+                buf,
+                block_length,
+                count,
+                index: usize::MAX,
+                offset: initial_offset + group_size_encoding_codec::acting_encoded_length(acting_version),
+                acting_version,
+            }
+        }
+
+        /// start of the discounts group of the current entry
+        #[inline]
+        fn nested_offset(&self) -> usize {
+            self.offset + self.block_length as usize
+        }
+
+        /// item note offset and end of the current entry, checked by the message decoder
+        #[inline]
+        fn tail(&self) -> (usize, usize) {
+            let nested = self.nested_offset();
+            if self.acting_version < 3 {
+                return (nested, nested);
+            }
+            items_entry_tail(&ReadBuf::new(&self.buf), nested, self.acting_version).unwrap_or((nested, nested))
+        }
+
+        #[inline]
+        pub fn count(&self) -> u16 {
+            self.count
+        }
+
+        /// will return Some(current index) when successful otherwise None
+        pub fn advance(&mut self) -> Option<usize> {
+            let index = self.index.wrapping_add(1);
+            if index >= self.count as usize {
+                return None;
+            }
+            if self.index != usize::MAX {
+                self.offset = self.tail().1;
+            }
+            self.index = index;
+            Some(index)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn product_id(&self) -> u32 {
+            ReadBuf::new(&self.buf).get_u32_at(self.offset)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn quantity(&self) -> u16 {
+            ReadBuf::new(&self.buf).get_u16_at(self.offset + 4)
+        }
+
+        /// COMPOSITE DECODER
+        #[inline]
+        pub fn unit_price_decoder(&self) -> decimal_codec::DecimalDecoder<ReadBuf<'_>> {
+            decimal_codec::DecimalDecoder::default().wrap(ReadBuf::new(&self.buf), self.offset + 6)
+        }
+
+        /// 'currency' up to its first NUL, empty for senders before version 5
+        #[inline]
+        pub fn currency(&self) -> &str {
+            self.char_array(14, 3)
+        }
+
+        /// 'sku' up to its first NUL, empty for senders before version 5
+        #[inline]
+        pub fn sku(&self) -> &str {
+            self.char_array(17, 16)
+        }
+
+        /// ASCII already checked by the message decoder
+        #[inline]
+        fn char_array(&self, at: usize, len: usize) -> &str {
+            if self.acting_version < 5 {
+                return "";
+            }
+            ReadBuf::new(&self.buf).get_char_array_at(self.offset + at, len).unwrap_or_default()
+        }
+
+        /// GROUP DECODER (id=24, description='Discounts applied to the item.'), empty for
+        /// senders before version 3
+        #[inline]
+        pub fn discounts_decoder(&self) -> DiscountsBytesDecoder {
+            if self.acting_version < 3 {
+                return DiscountsBytesDecoder::default();
+            }
+            DiscountsBytesDecoder::wrap(self.buf.clone(), self.nested_offset(), self.acting_version)
+        }
+
+        /// VAR_DATA DECODER - character encoding: 'UTF-8'
+        #[inline]
+        pub fn item_note(&self) -> Bytes {
+            if self.acting_version < 3 {
+                return Bytes::new();
+            }
+            let (offset, end) = self.tail();
+            self.buf.slice(offset + 2..end)
+        }
+    }
+
+    #[derive(Clone, Debug, Default)]
+    pub struct DiscountsBytesDecoder {
+        buf: Bytes,
+        block_length: u16,
+        count: u16,
+        index: usize,
+        offset: usize,
+    }
+
+    impl DiscountsBytesDecoder {
+        /// `initial_offset` is the group dimension, already checked by the message decoder
+        fn wrap(buf: Bytes, initial_offset: usize, acting_version: u16) -> Self {
+            let dimension = ReadBuf::new(&buf);
+            let block_length = dimension.get_u16_at(initial_offset);
+            let count = group_size_encoding_codec::acting_num_in_group(&dimension, initial_offset, acting_version);
+            Self {
+                buf,
+                block_length,
+                count,
+                index: usize::MAX,
+                offset: initial_offset + group_size_encoding_codec::acting_encoded_length(acting_version),
+            }
+        }
+
+        #[inline]
+        pub fn count(&self) -> u16 {
+            self.count
+        }
+
+        /// will return Some(current index) when successful otherwise None
+        pub fn advance(&mut self) -> Option<usize> {
+            let index = self.index.wrapping_add(1);
+            if index >= self.count as usize {
+                return None;
+            }
+            if self.index != usize::MAX {
+                self.offset += self.block_length as usize;
+            }
+            self.index = index;
+            Some(index)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn code(&self) -> u16 {
+            ReadBuf::new(&self.buf).get_u16_at(self.offset)
+        }
+
+        /// COMPOSITE DECODER
+        #[inline]
+        pub fn amount_decoder(&self) -> decimal_codec::DecimalDecoder<ReadBuf<'_>> {
+            decimal_codec::DecimalDecoder::default().wrap(ReadBuf::new(&self.buf), self.offset + 2)
+        }
+    }
+
+} // end shared
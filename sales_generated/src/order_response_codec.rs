@@ -56,6 +56,13 @@ pub mod encoder {
             self.limit - self.offset
         }
 
+        /// exact frame length including the message header, for a server note of
+        /// `server_note_len` bytes
+        #[inline]
+        pub fn compute_length(server_note_len: usize) -> usize {
+            message_header_codec::ENCODED_LENGTH + SBE_BLOCK_LENGTH as usize + 2 + server_note_len
+        }
+
         pub fn header(self, offset: usize) -> MessageHeaderEncoder<Self> {
             let mut header = MessageHeaderEncoder::default().wrap(self, offset);
             header.block_length(SBE_BLOCK_LENGTH);
@@ -85,7 +92,7 @@ pub mod encoder {
         /// - max value: -2
         /// - null value: 0xffffffffffffffff_u64
         /// - characterEncoding: null
-        /// - semanticType: null
+        /// - semanticType: UTCTimestamp
         /// - encodedOffset: 8
         /// - encodedLength: 8
         /// - version: 0
@@ -95,6 +102,20 @@ pub mod encoder {
             self.get_buf_mut().put_u64_at(offset, value);
         }
 
+        /// primitive field 'timestamp' from a `Timestamp`
+        #[inline]
+        pub fn timestamp_utc(&mut self, value: Timestamp) {
+            self.timestamp(value.as_nanos());
+        }
+
+        /// primitive field 'timestamp' set to the time of `clock`, which is returned
+        #[inline]
+        pub fn timestamp_now(&mut self, clock: &impl Clock) -> Timestamp {
+            let now = clock.now();
+            self.timestamp_utc(now);
+            now
+        }
+
         /// REQUIRED enum
         #[inline]
         pub fn status(&mut self, value: order_status::OrderStatus) {
@@ -124,14 +145,27 @@ pub mod encoder {
             decimal_codec::DecimalEncoder::default().wrap(self, offset)
         }
 
-        /// VAR_DATA ENCODER - character encoding: 'UTF-8'
+        // skipping CONSTANT venue
+
+        // skipping CONSTANT enum 'direction'
+
+        /// VAR_DATA ENCODER - character encoding: 'UTF-8'. Panics if `value` is longer than
+        /// `var_string_encoding_codec::LENGTH_MAX`
         #[inline]
         pub fn server_note(&mut self, value: &str) {
+            self.server_note_checked(value).expect("server_note too long");
+        }
+
+        /// same as server_note but fails with `SbeErr::DataTooLong` instead of writing a truncated
+        /// length
+        #[inline]
+        pub fn server_note_checked(&mut self, value: &str) -> SbeResult<()> {
+            let data_length = var_string_encoding_codec::length(value.len())?;
             let limit = self.get_limit();
-            let data_length = value.len();
-            self.set_limit(limit + 2 + data_length);
-            self.get_buf_mut().put_u16_at(limit, data_length as u16);
+            self.set_limit(limit + 2 + value.len());
+            self.get_buf_mut().put_u16_at(limit, data_length);
             self.get_buf_mut().put_slice_at(limit + 2, value.as_bytes());
+            Ok(())
         }
 
     }
@@ -196,6 +230,21 @@ pub mod decoder {
             self
         }
 
+        /// same as wrap but fails instead of panicking later if the block does not fit the buffer
+        pub fn wrap_checked(
+            self,
+            buf: ReadBuf<'a>,
+            offset: usize,
+            acting_block_length: u16,
+            acting_version: u16,
+        ) -> SbeResult<Self> {
+            if acting_block_length < SBE_BLOCK_LENGTH {
+                return Err(SbeErr::BlockLengthTooShort);
+            }
+            buf.check_bounds(offset, acting_block_length as usize)?;
+            Ok(self.wrap(buf, offset, acting_block_length, acting_version))
+        }
+
         #[inline]
         pub fn encoded_length(&self) -> usize {
             self.limit - self.offset
@@ -214,6 +263,23 @@ pub mod decoder {
             )
         }
 
+        /// same as header but validates the header and block against the buffer
+        pub fn header_checked(self, mut header: MessageHeaderDecoder<ReadBuf<'a>>, offset: usize) -> SbeResult<Self> {
+            let buf = header.parent()?;
+            buf.check_bounds(offset, message_header_codec::ENCODED_LENGTH)?;
+            let header = MessageHeaderDecoder::default().wrap(buf, offset);
+            if header.template_id() != SBE_TEMPLATE_ID {
+                return Err(SbeErr::WrongTemplateId);
+            }
+
+            self.wrap_checked(
+                buf,
+                offset + message_header_codec::ENCODED_LENGTH,
+                header.block_length(),
+                header.version(),
+            )
+        }
+
         /// primitive field - 'REQUIRED'
         #[inline]
         pub fn order_id(&self) -> u64 {
@@ -226,6 +292,12 @@ pub mod decoder {
             self.get_buf().get_u64_at(self.offset + 8)
         }
 
+        /// primitive field 'timestamp' as a `Timestamp`
+        #[inline]
+        pub fn timestamp_utc(&self) -> Timestamp {
+            Timestamp::from_nanos(self.timestamp())
+        }
+
         /// REQUIRED enum
         #[inline]
         pub fn status(&self) -> order_status::OrderStatus {
@@ -245,6 +317,25 @@ pub mod decoder {
             decimal_codec::DecimalDecoder::default().wrap(self, offset)
         }
 
+        /// CONSTANT 
+        /// characterEncoding: 'US-ASCII'
+        #[inline]
+        pub fn venue(&self) -> &'static [u8] {
+            b"XSAL"
+        }
+
+        /// CONSTANT 'venue' as text
+        #[inline]
+        pub fn venue_str(&self) -> &'static str {
+            "XSAL"
+        }
+
+        /// CONSTANT enum
+        #[inline]
+        pub fn direction(&self) -> direction::Direction {
+            direction::Direction::Outbound
+        }
+
         /// VAR_DATA DECODER - character encoding: 'UTF-8'
         #[inline]
         pub fn server_note_decoder(&mut self) -> (usize, usize) {
@@ -254,6 +345,37 @@ pub mod decoder {
             (offset + 2, data_length)
         }
 
+        /// same as server_note_decoder but fails if length or data do not fit the buffer
+        #[inline]
+        pub fn server_note_decoder_checked(&mut self) -> SbeResult<(usize, usize)> {
+            let offset = self.get_limit();
+            self.get_buf().check_bounds(offset, 2)?;
+            let data_length = self.get_buf().get_u16_at(offset) as usize;
+            self.get_buf().check_bounds(offset + 2, data_length)?;
+            self.set_limit(offset + 2 + data_length);
+            Ok((offset + 2, data_length))
+        }
+
+        /// moves the limit past server_note without reading the data, returns its length
+        #[inline]
+        pub fn server_note_skip(&mut self) -> usize {
+            let offset = self.get_limit();
+            let data_length = self.get_buf().get_u16_at(offset) as usize;
+            self.set_limit(offset + 2 + data_length);
+            data_length
+        }
+
+        /// same as server_note_skip but fails if length or data do not fit the buffer
+        #[inline]
+        pub fn server_note_skip_checked(&mut self) -> SbeResult<usize> {
+            let offset = self.get_limit();
+            self.get_buf().check_bounds(offset, 2)?;
+            let data_length = self.get_buf().get_u16_at(offset) as usize;
+            self.get_buf().check_bounds(offset + 2, data_length)?;
+            self.set_limit(offset + 2 + data_length);
+            Ok(data_length)
+        }
+
         #[inline]
         pub fn server_note_slice(&'a self, coordinates: (usize, usize)) -> &'a [u8] {
             debug_assert!(self.get_limit() >= coordinates.0 + coordinates.1);
@@ -264,3 +386,128 @@ pub mod decoder {
 
 } // end decoder
 
+
+#[cfg(feature = "bytes")]
+pub mod shared {
+    use super::*;
+    use bytes::Bytes;
+    use decoder::OrderResponseDecoder;
+    use message_header_codec::MessageHeaderDecoder;
+
+    /// Decoder owning a reference counted frame, so it is `Send + 'static` and cheap to clone.
+    /// Block and var data are checked against the frame at wrap, accessors do not fail.
+    #[derive(Clone, Debug, Default)]
+    pub struct OrderResponseBytesDecoder {
+        buf: Bytes,
+        offset: usize,
+        server_note: (usize, usize),
+        pub acting_block_length: u16,
+        pub acting_version: u16,
+    }
+
+    impl OrderResponseBytesDecoder {
+        pub fn wrap(
+            buf: Bytes,
+            offset: usize,
+            acting_block_length: u16,
+            acting_version: u16,
+        ) -> SbeResult<Self> {
+            let decoder = OrderResponseDecoder::default().wrap_checked(ReadBuf::new(&buf), offset, acting_block_length, acting_version)?;
+            let server_note = Self::layout(decoder)?;
+            Ok(Self { buf, offset, server_note, acting_block_length, acting_version })
+        }
+
+        pub fn header(buf: Bytes, offset: usize) -> SbeResult<Self> {
+            let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(&buf), offset);
+            let decoder = OrderResponseDecoder::default().header_checked(header, offset)?;
+            let (acting_block_length, acting_version) = (decoder.acting_block_length, decoder.acting_version);
+            let server_note = Self::layout(decoder)?;
+            Ok(Self {
+                buf,
+                offset: offset + message_header_codec::ENCODED_LENGTH,
+                server_note,
+                acting_block_length,
+                acting_version,
+            })
+        }
+
+        /// position of the server note
+        fn layout(mut decoder: OrderResponseDecoder<'_>) -> SbeResult<(usize, usize)> {
+            decoder.server_note_decoder_checked()
+        }
+
+        /// the whole frame this decoder was wrapped over
+        #[inline]
+        pub fn buf(&self) -> &Bytes {
+            &self.buf
+        }
+
+        /// borrowing flyweight over the same message
+        #[inline]
+        pub fn decoder(&self) -> OrderResponseDecoder<'_> {
+            OrderResponseDecoder::default().wrap(ReadBuf::new(&self.buf), self.offset, self.acting_block_length, self.acting_version)
+        }
+
+        #[inline]
+        pub fn encoded_length(&self) -> usize {
+            self.server_note.0 + self.server_note.1 - self.offset
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn order_id(&self) -> u64 {
+            self.decoder().order_id()
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn timestamp(&self) -> u64 {
+            self.decoder().timestamp()
+        }
+
+        /// primitive field 'timestamp' as a `Timestamp`
+        #[inline]
+        pub fn timestamp_utc(&self) -> Timestamp {
+            Timestamp::from_nanos(self.timestamp())
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn status(&self) -> order_status::OrderStatus {
+            self.decoder().status()
+        }
+
+        /// CONSTANT, no bytes on the wire
+        #[inline]
+        pub fn venue(&self) -> &'static str {
+            self.decoder().venue_str()
+        }
+
+        /// CONSTANT enum, no bytes on the wire
+        #[inline]
+        pub fn direction(&self) -> direction::Direction {
+            self.decoder().direction()
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn filled_qty(&self) -> u32 {
+            self.decoder().filled_qty()
+        }
+
+        /// COMPOSITE DECODER
+        #[inline]
+        pub fn fill_price_decoder(&self) -> decimal_codec::DecimalDecoder<ReadBuf<'_>> {
+            let offset = self.offset + 21;
+            decimal_codec::DecimalDecoder::default().wrap(ReadBuf::new(&self.buf), offset)
+        }
+
+        /// VAR_DATA DECODER - character encoding: 'UTF-8'
+        #[inline]
+        pub fn server_note(&self) -> Bytes {
+            let (offset, len) = self.server_note;
+            self.buf.slice(offset..offset + len)
+        }
+    }
+
+} // end shared
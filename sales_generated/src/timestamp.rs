@@ -0,0 +1,185 @@
+//! `UTCTimestamp` fields of the schema, `timeUnit="nanosecond"` since `epoch="unix"`.
+
+use crate::*;
+use core::time::Duration;
+
+/// Unit of one tick of a `Timestamp`, the `timeUnit` of the schema fields
+pub const TIME_UNIT: &str = "nanosecond";
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// Nanoseconds since the unix epoch, UTC. Covers 1970 up to 2554, the wire value is the bare
+/// `u64` so the `*_utc` accessors are free.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(u64);
+
+impl Timestamp {
+    pub const UNIX_EPOCH: Timestamp = Timestamp(0);
+    pub const MAX: Timestamp = Timestamp(u64::MAX);
+
+    #[inline]
+    pub const fn from_nanos(nanos: u64) -> Self {
+        Self(nanos)
+    }
+
+    #[inline]
+    pub const fn as_nanos(self) -> u64 {
+        self.0
+    }
+
+    /// Time since the unix epoch
+    #[inline]
+    pub const fn since_epoch(self) -> Duration {
+        Duration::from_nanos(self.0)
+    }
+
+    /// Fails with `TimestampOutOfRange` past `Timestamp::MAX`
+    #[inline]
+    pub fn from_since_epoch(since_epoch: Duration) -> SbeResult<Self> {
+        u64::try_from(since_epoch.as_nanos())
+            .map(Self)
+            .map_err(|_| SbeErr::TimestampOutOfRange)
+    }
+
+    #[inline]
+    pub fn checked_add(self, duration: Duration) -> Option<Self> {
+        let nanos = u64::try_from(duration.as_nanos()).ok()?;
+        self.0.checked_add(nanos).map(Self)
+    }
+
+    /// Time from `earlier` to `self`, `None` if `earlier` is later
+    #[inline]
+    pub fn checked_duration_since(self, earlier: Self) -> Option<Duration> {
+        self.0.checked_sub(earlier.0).map(Duration::from_nanos)
+    }
+}
+
+impl From<u64> for Timestamp {
+    #[inline]
+    fn from(nanos: u64) -> Self {
+        Self(nanos)
+    }
+}
+
+impl From<Timestamp> for u64 {
+    #[inline]
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.0
+    }
+}
+
+/// Seconds and nanoseconds since the epoch, as `1700000000.000000042`
+impl core::fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}.{:09}",
+            self.0 / NANOS_PER_SEC,
+            self.0 % NANOS_PER_SEC
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Timestamp> for std::time::SystemTime {
+    #[inline]
+    fn from(timestamp: Timestamp) -> Self {
+        std::time::UNIX_EPOCH + timestamp.since_epoch()
+    }
+}
+
+/// Fails with `TimestampOutOfRange` before the unix epoch or past `Timestamp::MAX`
+#[cfg(feature = "std")]
+impl TryFrom<std::time::SystemTime> for Timestamp {
+    type Error = SbeErr;
+
+    #[inline]
+    fn try_from(time: std::time::SystemTime) -> SbeResult<Self> {
+        let since_epoch = time
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_err(|_| SbeErr::TimestampOutOfRange)?;
+        Self::from_since_epoch(since_epoch)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<Timestamp> for chrono::DateTime<chrono::Utc> {
+    #[inline]
+    fn from(timestamp: Timestamp) -> Self {
+        let secs = (timestamp.0 / NANOS_PER_SEC) as i64;
+        let nanos = (timestamp.0 % NANOS_PER_SEC) as u32;
+        // every u64 of nanoseconds is inside the range of chrono
+        chrono::DateTime::from_timestamp(secs, nanos).expect("timestamp in chrono range")
+    }
+}
+
+/// Fails with `TimestampOutOfRange` before the unix epoch or past `Timestamp::MAX`
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::DateTime<chrono::Utc>> for Timestamp {
+    type Error = SbeErr;
+
+    #[inline]
+    fn try_from(time: chrono::DateTime<chrono::Utc>) -> SbeResult<Self> {
+        let secs = u64::try_from(time.timestamp()).map_err(|_| SbeErr::TimestampOutOfRange)?;
+        secs.checked_mul(NANOS_PER_SEC)
+            .and_then(|nanos| nanos.checked_add(time.timestamp_subsec_nanos() as u64))
+            .map(Self)
+            .ok_or(SbeErr::TimestampOutOfRange)
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<Timestamp> for time::OffsetDateTime {
+    #[inline]
+    fn from(timestamp: Timestamp) -> Self {
+        // every u64 of nanoseconds is before the year 9999 limit of time
+        time::OffsetDateTime::from_unix_timestamp_nanos(timestamp.0 as i128)
+            .expect("timestamp in time range")
+    }
+}
+
+/// Fails with `TimestampOutOfRange` before the unix epoch or past `Timestamp::MAX`
+#[cfg(feature = "time")]
+impl TryFrom<time::OffsetDateTime> for Timestamp {
+    type Error = SbeErr;
+
+    #[inline]
+    fn try_from(time: time::OffsetDateTime) -> SbeResult<Self> {
+        u64::try_from(time.unix_timestamp_nanos())
+            .map(Self)
+            .map_err(|_| SbeErr::TimestampOutOfRange)
+    }
+}
+
+/// Source of the time written by the `timestamp_now` encoder methods. A `Timestamp` is a clock
+/// stopped at itself and any `Fn() -> Timestamp` is a clock, so tests stay deterministic.
+pub trait Clock {
+    fn now(&self) -> Timestamp;
+}
+
+impl Clock for Timestamp {
+    #[inline]
+    fn now(&self) -> Timestamp {
+        *self
+    }
+}
+
+impl<F: Fn() -> Timestamp> Clock for F {
+    #[inline]
+    fn now(&self) -> Timestamp {
+        self()
+    }
+}
+
+/// `std::time::SystemTime::now`, a system clock set before the unix epoch reads as the epoch
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    #[inline]
+    fn now(&self) -> Timestamp {
+        Timestamp::try_from(std::time::SystemTime::now()).unwrap_or(Timestamp::UNIX_EPOCH)
+    }
+}
@@ -3,6 +3,15 @@ use crate::*;
 pub use encoder::VarStringEncodingEncoder;
 pub use decoder::VarStringEncodingDecoder;
 
+/// longest var data the uint16 'length' can count
+pub const LENGTH_MAX: u16 = u16::MAX;
+
+/// `len` as 'length', `SbeErr::DataTooLong` when it exceeds `LENGTH_MAX`
+#[inline]
+pub fn length(len: usize) -> SbeResult<u16> {
+    u16::try_from(len).map_err(|_| SbeErr::DataTooLong)
+}
+
 pub mod encoder {
     use super::*;
 
@@ -0,0 +1,50 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum Direction {
+    Inbound = 0x0_u8, 
+    Outbound = 0x1_u8, 
+    #[default]
+    NullVal = 0xff_u8, 
+}
+impl From<u8> for Direction {
+    #[inline]
+    fn from(v: u8) -> Self {
+        match v {
+            0x0_u8 => Self::Inbound, 
+            0x1_u8 => Self::Outbound, 
+            _ => Self::NullVal,
+        }
+    }
+}
+impl From<Direction> for u8 {
+    #[inline]
+    fn from(v: Direction) -> Self {
+        match v {
+            Direction::Inbound => 0x0_u8, 
+            Direction::Outbound => 0x1_u8, 
+            Direction::NullVal => 0xff_u8,
+        }
+    }
+}
+impl core::str::FromStr for Direction {
+    type Err = ();
+
+    #[inline]
+    fn from_str(v: &str) -> core::result::Result<Self, Self::Err> {
+        match v {
+            "Inbound" => Ok(Self::Inbound), 
+            "Outbound" => Ok(Self::Outbound), 
+            _ => Ok(Self::NullVal),
+        }
+    }
+}
+impl core::fmt::Display for Direction {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Inbound => write!(f, "Inbound"), 
+            Self::Outbound => write!(f, "Outbound"), 
+            Self::NullVal => write!(f, "NullVal"),
+        }
+    }
+}
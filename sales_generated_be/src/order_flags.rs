@@ -0,0 +1,81 @@
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OrderFlags(pub u8);
+impl OrderFlags {
+    #[inline]
+    pub fn new(value: u8) -> Self {
+        OrderFlags(value)
+    }
+
+    #[inline]
+    pub fn clear(&mut self) -> &mut Self {
+        self.0 = 0;
+        self
+    }
+
+    #[inline]
+    pub fn get_ioc(&self) -> bool {
+        0 != self.0 & (1 << 0)
+    }
+
+    #[inline]
+    pub fn set_ioc(&mut self, value: bool) -> &mut Self {
+        self.0 = if value {
+            self.0 | (1 << 0)
+        } else {
+            self.0 & !(1 << 0)
+        };
+        self
+    }
+
+    #[inline]
+    pub fn get_post_only(&self) -> bool {
+        0 != self.0 & (1 << 1)
+    }
+
+    #[inline]
+    pub fn set_post_only(&mut self, value: bool) -> &mut Self {
+        self.0 = if value {
+            self.0 | (1 << 1)
+        } else {
+            self.0 & !(1 << 1)
+        };
+        self
+    }
+
+    #[inline]
+    pub fn get_gift_wrap(&self) -> bool {
+        0 != self.0 & (1 << 2)
+    }
+
+    #[inline]
+    pub fn set_gift_wrap(&mut self, value: bool) -> &mut Self {
+        self.0 = if value {
+            self.0 | (1 << 2)
+        } else {
+            self.0 & !(1 << 2)
+        };
+        self
+    }
+
+    #[inline]
+    pub fn get_expedited(&self) -> bool {
+        0 != self.0 & (1 << 3)
+    }
+
+    #[inline]
+    pub fn set_expedited(&mut self, value: bool) -> &mut Self {
+        self.0 = if value {
+            self.0 | (1 << 3)
+        } else {
+            self.0 & !(1 << 3)
+        };
+        self
+    }
+}
+impl core::fmt::Debug for OrderFlags {
+    #[inline]
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(fmt, "OrderFlags[ioc(0)={},post_only(1)={},gift_wrap(2)={},expedited(3)={}]",
+            self.get_ioc(),self.get_post_only(),self.get_gift_wrap(),self.get_expedited(),)
+    }
+}
@@ -0,0 +1,903 @@
+use crate::*;
+
+pub use decoder::OrderMessageDecoder;
+pub use encoder::OrderMessageEncoder;
+
+pub use crate::SBE_SCHEMA_ID;
+pub use crate::SBE_SCHEMA_VERSION;
+pub use crate::SBE_SEMANTIC_VERSION;
+
+pub const SBE_BLOCK_LENGTH: u16 = 26;
+pub const SBE_TEMPLATE_ID: u16 = 1;
+
+pub mod encoder {
+    use super::*;
+    use message_header_codec::*;
+
+    #[derive(Debug, Default)]
+    pub struct OrderMessageEncoder<'a> {
+        buf: WriteBuf<'a>,
+        initial_offset: usize,
+        offset: usize,
+        limit: usize,
+    }
+
+    impl<'a> Writer<'a> for OrderMessageEncoder<'a> {
+        #[inline]
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a> {
+            &mut self.buf
+        }
+    }
+
+    impl<'a> Encoder<'a> for OrderMessageEncoder<'a> {
+        #[inline]
+        fn get_limit(&self) -> usize {
+            self.limit
+        }
+
+        #[inline]
+        fn set_limit(&mut self, limit: usize) {
+            self.limit = limit;
+        }
+    }
+
+    impl<'a> OrderMessageEncoder<'a> {
+        pub fn wrap(mut self, buf: WriteBuf<'a>, offset: usize) -> Self {
+            let limit = offset + SBE_BLOCK_LENGTH as usize;
+            self.buf = buf;
+            self.initial_offset = offset;
+            self.offset = offset;
+            self.limit = limit;
+            self
+        }
+
+        #[inline]
+        pub fn encoded_length(&self) -> usize {
+            self.limit - self.offset
+        }
+
+        pub fn header(self, offset: usize) -> MessageHeaderEncoder<Self> {
+            let mut header = MessageHeaderEncoder::default().wrap(self, offset);
+            header.block_length(SBE_BLOCK_LENGTH);
+            header.template_id(SBE_TEMPLATE_ID);
+            header.schema_id(SBE_SCHEMA_ID);
+            header.version(SBE_SCHEMA_VERSION);
+            header
+        }
+
+        /// primitive field 'orderId'
+        /// - min value: 0
+        /// - max value: -2
+        /// - null value: 0xffffffffffffffff_u64
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 0
+        /// - encodedLength: 8
+        /// - version: 0
+        #[inline]
+        pub fn order_id(&mut self, value: u64) {
+            let offset = self.offset;
+            self.get_buf_mut().put_u64_at(offset, value);
+        }
+
+        /// primitive field 'clientId'
+        /// - min value: 0
+        /// - max value: -2
+        /// - null value: 0xffffffffffffffff_u64
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 8
+        /// - encodedLength: 8
+        /// - version: 0
+        #[inline]
+        pub fn client_id(&mut self, value: u64) {
+            let offset = self.offset + 8;
+            self.get_buf_mut().put_u64_at(offset, value);
+        }
+
+        /// primitive field 'timestamp'
+        /// - min value: 0
+        /// - max value: -2
+        /// - null value: 0xffffffffffffffff_u64
+        /// - characterEncoding: null
+        /// - semanticType: UTCTimestamp
+        /// - encodedOffset: 16
+        /// - encodedLength: 8
+        /// - version: 0
+        #[inline]
+        pub fn timestamp(&mut self, value: u64) {
+            let offset = self.offset + 16;
+            self.get_buf_mut().put_u64_at(offset, value);
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn order_type(&mut self, value: order_type::OrderType) {
+            let offset = self.offset + 24;
+            self.get_buf_mut().put_u8_at(offset, value as u8)
+        }
+
+        #[inline]
+        pub fn order_flags(&mut self, value: order_flags::OrderFlags) {
+            let offset = self.offset + 25;
+            self.get_buf_mut().put_u8_at(offset, value.0)
+        }
+
+        // skipping CONSTANT venue
+
+        // skipping CONSTANT enum 'direction'
+
+        /// GROUP ENCODER (id=20, description='List of items in the order.')
+        #[inline]
+        pub fn items_encoder(self, count: u16, items_encoder: ItemsEncoder<Self>) -> ItemsEncoder<Self> {
+            items_encoder.wrap(self, count)
+        }
+
+        /// VAR_DATA ENCODER - character encoding: 'UTF-8'
+        #[inline]
+        pub fn customer_note(&mut self, value: &str) {
+            let limit = self.get_limit();
+            let data_length = value.len();
+            self.set_limit(limit + 2 + data_length);
+            self.get_buf_mut().put_u16_at(limit, data_length as u16);
+            self.get_buf_mut().put_slice_at(limit + 2, value.as_bytes());
+        }
+
+    }
+
+    #[derive(Debug, Default)]
+    pub struct ItemsEncoder<P> {
+        parent: Option<P>,
+        count: u16,
+        index: usize,
+        offset: usize,
+        initial_limit: usize,
+    }
+
+    impl<'a, P> Writer<'a> for ItemsEncoder<P> where P: Writer<'a> + Default {
+        #[inline]
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a> {
+            if let Some(parent) = self.parent.as_mut() {
+                parent.get_buf_mut()
+            } else {
+                panic!("parent was None")
+            }
+        }
+    }
+
+    impl<'a, P> Encoder<'a> for ItemsEncoder<P> where P: Encoder<'a> + Default {
+        #[inline]
+        fn get_limit(&self) -> usize {
+            self.parent.as_ref().expect("parent missing").get_limit()
+        }
+
+        #[inline]
+        fn set_limit(&mut self, limit: usize) {
+            self.parent.as_mut().expect("parent missing").set_limit(limit);
+        }
+    }
+
+    impl<'a, P> ItemsEncoder<P> where P: Encoder<'a> + Default {
+        #[inline]
+        pub fn wrap(
+            mut self,
+            mut parent: P,
+            count: u16,
+        ) -> Self {
+            let initial_limit = parent.get_limit();
+            parent.set_limit(initial_limit + 4);
+            parent.get_buf_mut().put_u16_at(initial_limit, Self::block_length());
+            parent.get_buf_mut().put_u16_at(initial_limit + 2, count);
+            self.parent = Some(parent);
+            self.count = count;
+            self.index = usize::MAX;
+            self.offset = usize::MAX;
+            self.initial_limit = initial_limit;
+            self
+        }
+
+        #[inline]
+        pub fn block_length() -> u16 {
+            33
+        }
+
+        #[inline]
+        pub fn parent(&mut self) -> SbeResult<P> {
+            self.parent.take().ok_or(SbeErr::ParentNotSet)
+        }
+
+        /// will return Some(current index) when successful otherwise None
+        #[inline]
+        pub fn advance(&mut self) -> SbeResult<Option<usize>> {
+            let index = self.index.wrapping_add(1);
+            if index >= self.count as usize {
+                return Ok(None);
+            }
+            if let Some(parent) = self.parent.as_mut() {
+                self.offset = parent.get_limit();
+                parent.set_limit(self.offset + Self::block_length() as usize);
+                self.index = index;
+                Ok(Some(index))
+            } else {
+                Err(SbeErr::ParentNotSet)
+            }
+        }
+
+        /// primitive field 'productId'
+        /// - min value: 0
+        /// - max value: 4294967294
+        /// - null value: 0xffffffff_u32
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 0
+        /// - encodedLength: 4
+        /// - version: 0
+        #[inline]
+        pub fn product_id(&mut self, value: u32) {
+            let offset = self.offset;
+            self.get_buf_mut().put_u32_at(offset, value);
+        }
+
+        /// primitive field 'quantity'
+        /// - min value: 0
+        /// - max value: 65534
+        /// - null value: 0xffff_u16
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 4
+        /// - encodedLength: 2
+        /// - version: 0
+        #[inline]
+        pub fn quantity(&mut self, value: u16) {
+            let offset = self.offset + 4;
+            self.get_buf_mut().put_u16_at(offset, value);
+        }
+
+        /// COMPOSITE ENCODER
+        #[inline]
+        pub fn unit_price_encoder(self) -> decimal_codec::DecimalEncoder<Self> {
+            let offset = self.offset + 6;
+            decimal_codec::DecimalEncoder::default().wrap(self, offset)
+        }
+
+        #[inline]
+        pub fn currency_at(&mut self, index: usize, value: u8) {
+            let offset = self.offset + 14;
+            let buf = self.get_buf_mut();
+            buf.put_u8_at(offset + index, value);
+        }
+
+        /// primitive array field 'currency'
+        /// - min value: 32
+        /// - max value: 126
+        /// - null value: 0_u8
+        /// - characterEncoding: US-ASCII
+        /// - semanticType: null
+        /// - encodedOffset: 14
+        /// - encodedLength: 3
+        /// - version: 5
+        #[inline]
+        pub fn currency(&mut self, value: &[u8]) {
+            debug_assert_eq!(3, value.len());
+            let offset = self.offset + 14;
+            let buf = self.get_buf_mut();
+            buf.put_slice_at(offset, value);
+        }
+
+        /// primitive array field 'currency' from an Iterator
+        /// - min value: 32
+        /// - max value: 126
+        /// - null value: 0_u8
+        /// - characterEncoding: US-ASCII
+        /// - semanticType: null
+        /// - encodedOffset: 14
+        /// - encodedLength: 3
+        /// - version: 5
+        #[inline]
+        pub fn currency_from_iter(&mut self, iter: impl Iterator<Item = u8>) {
+            let offset = self.offset + 14;
+            let buf = self.get_buf_mut();
+            for (i, v) in iter.enumerate() {
+                buf.put_u8_at(offset + i, v);
+            }
+        }
+
+        /// primitive array field 'currency' with zero padding
+        /// - min value: 32
+        /// - max value: 126
+        /// - null value: 0_u8
+        /// - characterEncoding: US-ASCII
+        /// - semanticType: null
+        /// - encodedOffset: 14
+        /// - encodedLength: 3
+        /// - version: 5
+        #[inline]
+        pub fn currency_zero_padded(&mut self, value: &[u8]) {
+            let iter = value.iter().copied().chain(std::iter::repeat(0_u8)).take(3);
+            self.currency_from_iter(iter);
+        }
+
+        #[inline]
+        pub fn sku_at(&mut self, index: usize, value: u8) {
+            let offset = self.offset + 17;
+            let buf = self.get_buf_mut();
+            buf.put_u8_at(offset + index, value);
+        }
+
+        /// primitive array field 'sku'
+        /// - min value: 32
+        /// - max value: 126
+        /// - null value: 0_u8
+        /// - characterEncoding: US-ASCII
+        /// - semanticType: null
+        /// - encodedOffset: 17
+        /// - encodedLength: 16
+        /// - version: 5
+        #[inline]
+        pub fn sku(&mut self, value: &[u8]) {
+            debug_assert_eq!(16, value.len());
+            let offset = self.offset + 17;
+            let buf = self.get_buf_mut();
+            buf.put_slice_at(offset, value);
+        }
+
+        /// primitive array field 'sku' from an Iterator
+        /// - min value: 32
+        /// - max value: 126
+        /// - null value: 0_u8
+        /// - characterEncoding: US-ASCII
+        /// - semanticType: null
+        /// - encodedOffset: 17
+        /// - encodedLength: 16
+        /// - version: 5
+        #[inline]
+        pub fn sku_from_iter(&mut self, iter: impl Iterator<Item = u8>) {
+            let offset = self.offset + 17;
+            let buf = self.get_buf_mut();
+            for (i, v) in iter.enumerate() {
+                buf.put_u8_at(offset + i, v);
+            }
+        }
+
+        /// primitive array field 'sku' with zero padding
+        /// - min value: 32
+        /// - max value: 126
+        /// - null value: 0_u8
+        /// - characterEncoding: US-ASCII
+        /// - semanticType: null
+        /// - encodedOffset: 17
+        /// - encodedLength: 16
+        /// - version: 5
+        #[inline]
+        pub fn sku_zero_padded(&mut self, value: &[u8]) {
+            let iter = value.iter().copied().chain(std::iter::repeat(0_u8)).take(16);
+            self.sku_from_iter(iter);
+        }
+
+        /// GROUP ENCODER (id=24, description='Discounts applied to the item.')
+        #[inline]
+        pub fn discounts_encoder(self, count: u16, discounts_encoder: DiscountsEncoder<Self>) -> DiscountsEncoder<Self> {
+            discounts_encoder.wrap(self, count)
+        }
+
+        /// VAR_DATA ENCODER - character encoding: 'UTF-8'
+        #[inline]
+        pub fn item_note(&mut self, value: &str) {
+            let limit = self.get_limit();
+            let data_length = value.len();
+            self.set_limit(limit + 2 + data_length);
+            self.get_buf_mut().put_u16_at(limit, data_length as u16);
+            self.get_buf_mut().put_slice_at(limit + 2, value.as_bytes());
+        }
+
+    }
+
+    #[derive(Debug, Default)]
+    pub struct DiscountsEncoder<P> {
+        parent: Option<P>,
+        count: u16,
+        index: usize,
+        offset: usize,
+        initial_limit: usize,
+    }
+
+    impl<'a, P> Writer<'a> for DiscountsEncoder<P> where P: Writer<'a> + Default {
+        #[inline]
+        fn get_buf_mut(&mut self) -> &mut WriteBuf<'a> {
+            if let Some(parent) = self.parent.as_mut() {
+                parent.get_buf_mut()
+            } else {
+                panic!("parent was None")
+            }
+        }
+    }
+
+    impl<'a, P> Encoder<'a> for DiscountsEncoder<P> where P: Encoder<'a> + Default {
+        #[inline]
+        fn get_limit(&self) -> usize {
+            self.parent.as_ref().expect("parent missing").get_limit()
+        }
+
+        #[inline]
+        fn set_limit(&mut self, limit: usize) {
+            self.parent.as_mut().expect("parent missing").set_limit(limit);
+        }
+    }
+
+    impl<'a, P> DiscountsEncoder<P> where P: Encoder<'a> + Default {
+        #[inline]
+        pub fn wrap(
+            mut self,
+            mut parent: P,
+            count: u16,
+        ) -> Self {
+            let initial_limit = parent.get_limit();
+            parent.set_limit(initial_limit + 4);
+            parent.get_buf_mut().put_u16_at(initial_limit, Self::block_length());
+            parent.get_buf_mut().put_u16_at(initial_limit + 2, count);
+            self.parent = Some(parent);
+            self.count = count;
+            self.index = usize::MAX;
+            self.offset = usize::MAX;
+            self.initial_limit = initial_limit;
+            self
+        }
+
+        #[inline]
+        pub fn block_length() -> u16 {
+            10
+        }
+
+        #[inline]
+        pub fn parent(&mut self) -> SbeResult<P> {
+            self.parent.take().ok_or(SbeErr::ParentNotSet)
+        }
+
+        /// will return Some(current index) when successful otherwise None
+        #[inline]
+        pub fn advance(&mut self) -> SbeResult<Option<usize>> {
+            let index = self.index.wrapping_add(1);
+            if index >= self.count as usize {
+                return Ok(None);
+            }
+            if let Some(parent) = self.parent.as_mut() {
+                self.offset = parent.get_limit();
+                parent.set_limit(self.offset + Self::block_length() as usize);
+                self.index = index;
+                Ok(Some(index))
+            } else {
+                Err(SbeErr::ParentNotSet)
+            }
+        }
+
+        /// primitive field 'code'
+        /// - min value: 0
+        /// - max value: 65534
+        /// - null value: 0xffff_u16
+        /// - characterEncoding: null
+        /// - semanticType: null
+        /// - encodedOffset: 0
+        /// - encodedLength: 2
+        /// - version: 0
+        #[inline]
+        pub fn code(&mut self, value: u16) {
+            let offset = self.offset;
+            self.get_buf_mut().put_u16_at(offset, value);
+        }
+
+        /// COMPOSITE ENCODER
+        #[inline]
+        pub fn amount_encoder(self) -> decimal_codec::DecimalEncoder<Self> {
+            let offset = self.offset + 2;
+            decimal_codec::DecimalEncoder::default().wrap(self, offset)
+        }
+
+    }
+
+} // end encoder
+
+pub mod decoder {
+    use super::*;
+    use message_header_codec::*;
+
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct OrderMessageDecoder<'a> {
+        buf: ReadBuf<'a>,
+        initial_offset: usize,
+        offset: usize,
+        limit: usize,
+        pub acting_block_length: u16,
+        pub acting_version: u16,
+    }
+
+    impl ActingVersion for OrderMessageDecoder<'_> {
+        #[inline]
+        fn acting_version(&self) -> u16 {
+            self.acting_version
+        }
+    }
+
+    impl<'a> Reader<'a> for OrderMessageDecoder<'a> {
+        #[inline]
+        fn get_buf(&self) -> &ReadBuf<'a> {
+            &self.buf
+        }
+    }
+
+    impl<'a> Decoder<'a> for OrderMessageDecoder<'a> {
+        #[inline]
+        fn get_limit(&self) -> usize {
+            self.limit
+        }
+
+        #[inline]
+        fn set_limit(&mut self, limit: usize) {
+            self.limit = limit;
+        }
+    }
+
+    impl<'a> OrderMessageDecoder<'a> {
+        pub fn wrap(
+            mut self,
+            buf: ReadBuf<'a>,
+            offset: usize,
+            acting_block_length: u16,
+            acting_version: u16,
+        ) -> Self {
+            let limit = offset + acting_block_length as usize;
+            self.buf = buf;
+            self.initial_offset = offset;
+            self.offset = offset;
+            self.limit = limit;
+            self.acting_block_length = acting_block_length;
+            self.acting_version = acting_version;
+            self
+        }
+
+        #[inline]
+        pub fn encoded_length(&self) -> usize {
+            self.limit - self.offset
+        }
+
+        pub fn header(self, mut header: MessageHeaderDecoder<ReadBuf<'a>>, offset: usize) -> Self {
+            debug_assert_eq!(SBE_TEMPLATE_ID, header.template_id());
+            let acting_block_length = header.block_length();
+            let acting_version = header.version();
+
+            self.wrap(
+                header.parent().unwrap(),
+                offset + message_header_codec::ENCODED_LENGTH,
+                acting_block_length,
+                acting_version,
+            )
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn order_id(&self) -> u64 {
+            self.get_buf().get_u64_at(self.offset)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn client_id(&self) -> u64 {
+            self.get_buf().get_u64_at(self.offset + 8)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn timestamp(&self) -> u64 {
+            self.get_buf().get_u64_at(self.offset + 16)
+        }
+
+        /// REQUIRED enum
+        #[inline]
+        pub fn order_type(&self) -> order_type::OrderType {
+            self.get_buf().get_u8_at(self.offset + 24).into()
+        }
+
+        /// BIT SET DECODER
+        #[inline]
+        pub fn order_flags(&self) -> order_flags::OrderFlags {
+            if self.acting_version() < 2 {
+                return order_flags::OrderFlags::default();
+            }
+
+            order_flags::OrderFlags::new(self.get_buf().get_u8_at(self.offset + 25))
+        }
+
+        /// CONSTANT 
+        /// characterEncoding: 'US-ASCII'
+        #[inline]
+        pub fn venue(&self) -> &'static [u8] {
+            b"XSAL"
+        }
+
+        /// CONSTANT enum
+        #[inline]
+        pub fn direction(&self) -> direction::Direction {
+            direction::Direction::Inbound
+        }
+
+        /// GROUP DECODER (id=20, description='List of items in the order.')
+        #[inline]
+        pub fn items_decoder(self) -> ItemsDecoder<Self> {
+            ItemsDecoder::default().wrap(self)
+        }
+
+        /// VAR_DATA DECODER - character encoding: 'UTF-8'
+        #[inline]
+        pub fn customer_note_decoder(&mut self) -> (usize, usize) {
+            let offset = self.get_limit();
+            let data_length = self.get_buf().get_u16_at(offset) as usize;
+            self.set_limit(offset + 2 + data_length);
+            (offset + 2, data_length)
+        }
+
+        #[inline]
+        pub fn customer_note_slice(&'a self, coordinates: (usize, usize)) -> &'a [u8] {
+            debug_assert!(self.get_limit() >= coordinates.0 + coordinates.1);
+            self.get_buf().get_slice_at(coordinates.0, coordinates.1)
+        }
+
+    }
+
+    #[derive(Debug, Default)]
+    pub struct ItemsDecoder<P> {
+        parent: Option<P>,
+        block_length: u16,
+        count: u16,
+        index: usize,
+        offset: usize,
+    }
+
+    impl<'a, P> ActingVersion for ItemsDecoder<P> where P: Reader<'a> + ActingVersion + Default {
+        #[inline]
+        fn acting_version(&self) -> u16 {
+            self.parent.as_ref().unwrap().acting_version()
+        }
+    }
+
+    impl<'a, P> Reader<'a> for ItemsDecoder<P> where P: Reader<'a> + Default {
+        #[inline]
+        fn get_buf(&self) -> &ReadBuf<'a> {
+            self.parent.as_ref().expect("parent missing").get_buf()
+        }
+    }
+
+    impl<'a, P> Decoder<'a> for ItemsDecoder<P> where P: Decoder<'a> + ActingVersion + Default {
+        #[inline]
+        fn get_limit(&self) -> usize {
+            self.parent.as_ref().expect("parent missing").get_limit()
+        }
+
+        #[inline]
+        fn set_limit(&mut self, limit: usize) {
+            self.parent.as_mut().expect("parent missing").set_limit(limit);
+        }
+    }
+
+    impl<'a, P> ItemsDecoder<P> where P: Decoder<'a> + ActingVersion + Default {
+        pub fn wrap(
+            mut self,
+            mut parent: P,
+        ) -> Self {
+            let initial_offset = parent.get_limit();
+            let block_length = parent.get_buf().get_u16_at(initial_offset);
+            let count = parent.get_buf().get_u16_at(initial_offset + 2);
+            parent.set_limit(initial_offset + 4);
+            self.parent = Some(parent);
+            self.block_length = block_length;
+            self.count = count;
+            self.index = usize::MAX;
+            self.offset = 0;
+            self
+        }
+
+        /// group token - Token{signal=BEGIN_GROUP, name='items', referencedName='null', description='List of items in the order.', packageName='null', id=20, version=0, deprecated=0, encodedLength=33, offset=26, componentTokenCount=45, encoding=Encoding{presence=REQUIRED, primitiveType=null, byteOrder=LITTLE_ENDIAN, minValue=null, maxValue=null, nullValue=null, constValue=null, characterEncoding='null', epoch='null', timeUnit=null, semanticType='null'}}
+        #[inline]
+        pub fn parent(&mut self) -> SbeResult<P> {
+            self.parent.take().ok_or(SbeErr::ParentNotSet)
+        }
+
+        #[inline]
+        pub fn acting_version(&mut self) -> u16 {
+            self.parent.as_ref().unwrap().acting_version()
+        }
+
+        #[inline]
+        pub fn count(&self) -> u16 {
+            self.count
+        }
+
+        /// will return Some(current index) when successful otherwise None
+        pub fn advance(&mut self) -> SbeResult<Option<usize>> {
+            let index = self.index.wrapping_add(1);
+            if index >= self.count as usize {
+                 return Ok(None);
+            }
+            if let Some(parent) = self.parent.as_mut() {
+                self.offset = parent.get_limit();
+                parent.set_limit(self.offset + self.block_length as usize);
+                self.index = index;
+                Ok(Some(index))
+            } else {
+                Err(SbeErr::ParentNotSet)
+            }
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn product_id(&self) -> u32 {
+            self.get_buf().get_u32_at(self.offset)
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn quantity(&self) -> u16 {
+            self.get_buf().get_u16_at(self.offset + 4)
+        }
+
+        /// COMPOSITE DECODER
+        #[inline]
+        pub fn unit_price_decoder(self) -> decimal_codec::DecimalDecoder<Self> {
+            let offset = self.offset + 6;
+            decimal_codec::DecimalDecoder::default().wrap(self, offset)
+        }
+
+        #[inline]
+        pub fn currency(&self) -> [u8; 3] {
+            if self.acting_version() < 5 {
+                return [0_u8; 3];
+            }
+
+            let buf = self.get_buf();
+            ReadBuf::get_bytes_at(buf.data, self.offset + 14)
+        }
+
+        #[inline]
+        pub fn sku(&self) -> [u8; 16] {
+            if self.acting_version() < 5 {
+                return [0_u8; 16];
+            }
+
+            let buf = self.get_buf();
+            ReadBuf::get_bytes_at(buf.data, self.offset + 17)
+        }
+
+        /// GROUP DECODER (id=24, description='Discounts applied to the item.')
+        #[inline]
+        pub fn discounts_decoder(self) -> Option<DiscountsDecoder<Self>> {
+            if self.acting_version() < 3 {
+                return None;
+            }
+
+            Some(DiscountsDecoder::default().wrap(self))
+        }
+
+        /// VAR_DATA DECODER - character encoding: 'UTF-8'
+        #[inline]
+        pub fn item_note_decoder(&mut self) -> (usize, usize) {
+            if self.acting_version() < 3 {
+                return (self.parent.as_ref().unwrap().get_limit(), 0);
+            }
+
+            let offset = self.parent.as_ref().expect("parent missing").get_limit();
+            let data_length = self.get_buf().get_u16_at(offset) as usize;
+            self.parent.as_mut().unwrap().set_limit(offset + 2 + data_length);
+            (offset + 2, data_length)
+        }
+
+        #[inline]
+        pub fn item_note_slice(&'a self, coordinates: (usize, usize)) -> &'a [u8] {
+            if self.acting_version() < 3 {
+                return &[] as &[u8];
+            }
+
+            debug_assert!(self.get_limit() >= coordinates.0 + coordinates.1);
+            self.get_buf().get_slice_at(coordinates.0, coordinates.1)
+        }
+
+    }
+
+    #[derive(Debug, Default)]
+    pub struct DiscountsDecoder<P> {
+        parent: Option<P>,
+        block_length: u16,
+        count: u16,
+        index: usize,
+        offset: usize,
+    }
+
+    impl<'a, P> ActingVersion for DiscountsDecoder<P> where P: Reader<'a> + ActingVersion + Default {
+        #[inline]
+        fn acting_version(&self) -> u16 {
+            self.parent.as_ref().unwrap().acting_version()
+        }
+    }
+
+    impl<'a, P> Reader<'a> for DiscountsDecoder<P> where P: Reader<'a> + ActingVersion + Default {
+        #[inline]
+        fn get_buf(&self) -> &ReadBuf<'a> {
+            self.parent.as_ref().expect("parent missing").get_buf()
+        }
+    }
+
+    impl<'a, P> Decoder<'a> for DiscountsDecoder<P> where P: Decoder<'a> + ActingVersion + Default {
+        #[inline]
+        fn get_limit(&self) -> usize {
+            self.parent.as_ref().expect("parent missing").get_limit()
+        }
+
+        #[inline]
+        fn set_limit(&mut self, limit: usize) {
+            self.parent.as_mut().expect("parent missing").set_limit(limit);
+        }
+    }
+
+    impl<'a, P> DiscountsDecoder<P> where P: Decoder<'a> + ActingVersion + Default {
+        pub fn wrap(
+            mut self,
+            mut parent: P,
+        ) -> Self {
+            let initial_offset = parent.get_limit();
+            let block_length = parent.get_buf().get_u16_at(initial_offset);
+            let count = parent.get_buf().get_u16_at(initial_offset + 2);
+            parent.set_limit(initial_offset + 4);
+            self.parent = Some(parent);
+            self.block_length = block_length;
+            self.count = count;
+            self.index = usize::MAX;
+            self.offset = 0;
+            self
+        }
+
+        /// group token - Token{signal=BEGIN_GROUP, name='discounts', referencedName='null', description='Discounts applied to the item.', packageName='null', id=24, version=3, deprecated=0, encodedLength=10, offset=33, componentTokenCount=15, encoding=Encoding{presence=REQUIRED, primitiveType=null, byteOrder=LITTLE_ENDIAN, minValue=null, maxValue=null, nullValue=null, constValue=null, characterEncoding='null', epoch='null', timeUnit=null, semanticType='null'}}
+        #[inline]
+        pub fn parent(&mut self) -> SbeResult<P> {
+            self.parent.take().ok_or(SbeErr::ParentNotSet)
+        }
+
+        #[inline]
+        pub fn acting_version(&mut self) -> u16 {
+            self.parent.as_ref().unwrap().acting_version()
+        }
+
+        #[inline]
+        pub fn count(&self) -> u16 {
+            self.count
+        }
+
+        /// will return Some(current index) when successful otherwise None
+        pub fn advance(&mut self) -> SbeResult<Option<usize>> {
+            let index = self.index.wrapping_add(1);
+            if index >= self.count as usize {
+                 return Ok(None);
+            }
+            if let Some(parent) = self.parent.as_mut() {
+                self.offset = parent.get_limit();
+                parent.set_limit(self.offset + self.block_length as usize);
+                self.index = index;
+                Ok(Some(index))
+            } else {
+                Err(SbeErr::ParentNotSet)
+            }
+        }
+
+        /// primitive field - 'REQUIRED'
+        #[inline]
+        pub fn code(&self) -> u16 {
+            self.get_buf().get_u16_at(self.offset)
+        }
+
+        /// COMPOSITE DECODER
+        #[inline]
+        pub fn amount_decoder(self) -> decimal_codec::DecimalDecoder<Self> {
+            let offset = self.offset + 2;
+            decimal_codec::DecimalDecoder::default().wrap(self, offset)
+        }
+
+    }
+
+} // end decoder
+